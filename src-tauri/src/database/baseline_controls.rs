@@ -1,4 +1,4 @@
-use crate::models::BaselineControl;
+use crate::models::{BaselineControl, ControlSystemUsage};
 use rusqlite::{params, Connection};
 use super::utils::DatabaseError;
 
@@ -63,6 +63,33 @@ impl<'a> BaselineControlOperations<'a> {
         Ok(())
     }
 
+    /// Insert the controls a system doesn't already have, in one transaction.
+    /// Returns (added, skipped).
+    pub fn add_missing_baseline_controls(&mut self, system_id: &str, controls: &[BaselineControl]) -> Result<(usize, usize), DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let mut added = 0;
+        for control in controls {
+            added += tx.execute(
+                "INSERT OR IGNORE INTO baseline_controls (id, family, title, implementation_status, date_added, responsible_party, notes, system_id) 
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    control.id,
+                    control.family,
+                    control.title,
+                    control.implementation_status,
+                    control.date_added,
+                    control.responsible_party,
+                    control.notes,
+                    system_id
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        println!("Added {} of {} baseline controls to system {}", added, controls.len(), system_id);
+        Ok((added, controls.len() - added))
+    }
+
     pub fn remove_baseline_control(&mut self, control_id: &str, system_id: &str) -> Result<(), DatabaseError> {
         println!("Removing baseline control {} from system {}", control_id, system_id);
         
@@ -109,4 +136,73 @@ impl<'a> BaselineControlQueries<'a> {
         println!("Found {} baseline controls for system {}", controls.len(), system_id);
         Ok(controls)
     }
+
+    // Every system tracking `control_id` in its baseline (matched case-insensitively), with the
+    // number of POAMs associated with the control in that system
+    pub fn find_control_across_systems(&self, control_id: &str) -> Result<Vec<ControlSystemUsage>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.id, s.name, b.id, b.implementation_status,
+                    (SELECT COUNT(DISTINCT a.poam_id) FROM control_poam_associations a
+                     WHERE a.control_id = b.id AND a.system_id = b.system_id)
+             FROM baseline_controls b
+             JOIN systems s ON s.id = b.system_id
+             WHERE UPPER(TRIM(b.id)) = UPPER(TRIM(?1))
+             ORDER BY s.name COLLATE NOCASE, s.id",
+        )?;
+        let usages = stmt
+            .query_map(params![control_id], |row| {
+                Ok(ControlSystemUsage {
+                    system_id: row.get(0)?,
+                    system_name: row.get(1)?,
+                    control_id: row.get(2)?,
+                    implementation_status: row.get(3)?,
+                    poam_count: row.get::<_, i64>(4)? as usize,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(usages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::{db_with_systems, poam};
+    use crate::models::{BaselineControl, System};
+
+    fn control(id: &str, system_id: &str, status: &str) -> BaselineControl {
+        BaselineControl {
+            id: id.to_string(),
+            family: id[..2].to_string(),
+            title: format!("Control {}", id),
+            implementation_status: status.to_string(),
+            date_added: "2024-01-01".to_string(),
+            responsible_party: String::new(),
+            notes: String::new(),
+            system_id: system_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn finds_a_control_in_every_system_that_tracks_it() {
+        let mut db = db_with_systems(&["hr", "web", "lab"]);
+        db.update_system(&System { name: "Alpha HR".to_string(), ..crate::database::test_support::system("hr") }).unwrap();
+        db.add_baseline_control(&control("AC-2", "web", "Implemented")).unwrap();
+        db.add_baseline_control(&control("AC-2", "hr", "Partially Implemented")).unwrap();
+        db.add_baseline_control(&control("AC-3", "lab", "Implemented")).unwrap();
+        for id in 1..=3 {
+            db.create_poam(&poam(id), "web").unwrap();
+        }
+        db.create_control_poam_associations("AC-2", &[1, 2], "web", None, None).unwrap();
+        db.create_control_poam_associations("AC-3", &[3], "web", None, None).unwrap();
+
+        let usages = db.find_control_across_systems(" ac-2 ").unwrap();
+        let found: Vec<(&str, &str, &str, usize)> = usages.iter()
+            .map(|u| (u.system_id.as_str(), u.system_name.as_str(), u.implementation_status.as_str(), u.poam_count))
+            .collect();
+        assert_eq!(found, [
+            ("hr", "Alpha HR", "Partially Implemented", 0),
+            ("web", "System web", "Implemented", 2),
+        ]);
+        assert!(db.find_control_across_systems("SI-4").unwrap().is_empty());
+    }
 }
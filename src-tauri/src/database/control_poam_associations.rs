@@ -51,6 +51,55 @@ impl<'a> ControlPOAMAssociationOperations<'a> {
         Ok(id)
     }
 
+    // Links several POAMs to one control in a single transaction. POAMs that are already
+    // associated, or that are not in the system, are skipped; returns the new association ids.
+    pub fn create_control_poam_associations(
+        &mut self,
+        control_id: &str,
+        poam_ids: &[i64],
+        system_id: &str,
+        created_by: Option<&str>,
+        notes: Option<&str>
+    ) -> Result<Vec<String>, DatabaseError> {
+        println!("Creating control-POAM associations for control {} and {} POAMs", control_id, poam_ids.len());
+
+        let tx = self.conn.transaction()?;
+        let association_date = chrono::Utc::now().to_rfc3339();
+        let mut created = Vec::new();
+
+        for &poam_id in poam_ids {
+            let poam_exists: i64 = tx.query_row(
+                "SELECT COUNT(*) FROM poams WHERE id = ?1 AND system_id = ?2",
+                params![poam_id, system_id],
+                |row| row.get(0),
+            )?;
+            let already_associated: i64 = tx.query_row(
+                "SELECT COUNT(*) FROM control_poam_associations 
+                 WHERE control_id = ?1 AND poam_id = ?2 AND system_id = ?3",
+                params![control_id, poam_id, system_id],
+                |row| row.get(0),
+            )?;
+
+            if poam_exists == 0 || already_associated > 0 {
+                println!("Skipping POAM {} for control {}", poam_id, control_id);
+                continue;
+            }
+
+            let id = uuid::Uuid::new_v4().to_string();
+            tx.execute(
+                "INSERT INTO control_poam_associations (id, control_id, poam_id, association_date, system_id, created_by, notes) 
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![id, control_id, poam_id, association_date, system_id, created_by, notes],
+            )?;
+            created.push(id);
+        }
+
+        tx.commit()?;
+
+        println!("Created {} control-POAM associations for control {}", created.len(), control_id);
+        Ok(created)
+    }
+
     pub fn delete_control_poam_association(
         &mut self,
         association_id: &str,
@@ -106,6 +155,17 @@ impl<'a> ControlPOAMAssociationQueries<'a> {
         Ok(associations)
     }
 
+    // Control ids as stored on the system's associations, which may differ in case or padding
+    pub fn get_associated_control_ids(&self, system_id: &str) -> Result<Vec<String>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT control_id FROM control_poam_associations WHERE system_id = ?1 ORDER BY control_id",
+        )?;
+        let control_ids = stmt
+            .query_map(params![system_id], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(control_ids)
+    }
+
     pub fn get_control_poam_associations_by_poam(
         &self,
         poam_id: i64,
@@ -139,3 +199,34 @@ impl<'a> ControlPOAMAssociationQueries<'a> {
         Ok(associations)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::{db_with_systems, poam};
+
+    #[test]
+    fn bulk_association_skips_existing_links_and_other_systems() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        for id in 1..=3 {
+            db.create_poam(&poam(id), "sys-1").unwrap();
+        }
+        db.create_poam(&poam(4), "sys-2").unwrap();
+        let existing = db.create_control_poam_association("AC-2", 1, "sys-1", None, None).unwrap();
+
+        let created = db
+            .create_control_poam_associations("AC-2", &[1, 2, 3, 3, 4, 99], "sys-1", Some("analyst"), Some("bulk"))
+            .unwrap();
+        assert_eq!(created.len(), 2);
+        assert!(!created.contains(&existing));
+
+        let mut linked: Vec<i64> = db
+            .get_control_poam_associations_by_control("AC-2", "sys-1")
+            .unwrap()
+            .into_iter()
+            .map(|a| a.poam_id)
+            .collect();
+        linked.sort();
+        assert_eq!(linked, [1, 2, 3]);
+        assert!(db.get_control_poam_associations_by_control("AC-2", "sys-2").unwrap().is_empty());
+    }
+}
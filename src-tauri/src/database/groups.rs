@@ -1,4 +1,4 @@
-use crate::models::{SystemGroup, GroupSummary, SystemSummary, GroupPOAM, Milestone, GroupExportData};
+use crate::models::{SystemGroup, GroupSummary, SystemSummary, GroupPOAM, Milestone, GroupExportData, SystemPOAMRef, LinkedSystemPOAM, GroupPOAMRollup};
 use rusqlite::{params, Connection};
 use serde_json;
 use super::utils::DatabaseError;
@@ -429,10 +429,93 @@ impl<'a> GroupOperations<'a> {
 
     pub fn delete_group_poam(&mut self, id: i64) -> Result<(), DatabaseError> {
         self.conn.execute("DELETE FROM group_milestones WHERE group_poam_id = ?1", params![id])?;
+        self.conn.execute("DELETE FROM group_poam_system_links WHERE group_poam_id = ?1", params![id])?;
         self.conn.execute("DELETE FROM group_poams WHERE id = ?1", params![id])?;
         Ok(())
     }
 
+    // Links are additive; re-linking an existing system POAM is a no-op
+    pub fn link_group_poam_to_system_poams(&mut self, group_poam_id: i64, refs: &[SystemPOAMRef]) -> Result<usize, DatabaseError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = self.conn.transaction()?;
+
+        let mut linked = 0;
+        for r in refs {
+            let exists = tx.query_row(
+                "SELECT COUNT(*) FROM poams WHERE id = ?1 AND system_id = ?2",
+                params![r.poam_id, r.system_id],
+                |row| row.get::<_, i64>(0)
+            )? > 0;
+
+            if !exists {
+                return Err(DatabaseError::NotFound(format!("POAM {} not found in system {}", r.poam_id, r.system_id)));
+            }
+
+            linked += tx.execute(
+                "INSERT OR IGNORE INTO group_poam_system_links (group_poam_id, system_id, poam_id, linked_date)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![group_poam_id, r.system_id, r.poam_id, now],
+            )?;
+        }
+
+        tx.commit()?;
+        println!("Linked {} system POAMs to group POAM {}", linked, group_poam_id);
+        Ok(linked)
+    }
+
+    pub fn unlink_group_poam_from_system_poam(&mut self, group_poam_id: i64, system_id: &str, poam_id: i64) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "DELETE FROM group_poam_system_links WHERE group_poam_id = ?1 AND system_id = ?2 AND poam_id = ?3",
+            params![group_poam_id, system_id, poam_id],
+        )?;
+        Ok(())
+    }
+
+    // Group milestone operations
+    pub fn create_group_milestone(&mut self, group_poam_id: i64, milestone: &Milestone) -> Result<(), DatabaseError> {
+        let exists = self.conn.query_row(
+            "SELECT COUNT(*) FROM group_poams WHERE id = ?1",
+            params![group_poam_id],
+            |row| row.get::<_, i64>(0)
+        )? > 0;
+
+        if !exists {
+            return Err(DatabaseError::NotFound(format!("Group POAM {} not found", group_poam_id)));
+        }
+
+        self.conn.execute(
+            "INSERT INTO group_milestones (id, group_poam_id, title, due_date, status, description) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![milestone.id, group_poam_id, milestone.title, milestone.due_date, milestone.status, milestone.description],
+        )?;
+
+        println!("Created milestone {} for group POAM {}", milestone.id, group_poam_id);
+        Ok(())
+    }
+
+    pub fn update_group_milestone_status(&mut self, milestone_id: &str, group_poam_id: i64, status: &str) -> Result<(), DatabaseError> {
+        let updated_rows = self.conn.execute(
+            "UPDATE group_milestones SET status = ?1 WHERE id = ?2 AND group_poam_id = ?3",
+            params![status, milestone_id, group_poam_id],
+        )?;
+
+        if updated_rows == 0 {
+            return Err(DatabaseError::NotFound(
+                format!("Milestone {} not found for group POAM {}", milestone_id, group_poam_id)
+            ));
+        }
+
+        println!("Updated group milestone {} to status {}", milestone_id, status);
+        Ok(())
+    }
+
+    pub fn delete_group_milestone(&mut self, milestone_id: &str, group_poam_id: i64) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "DELETE FROM group_milestones WHERE id = ?1 AND group_poam_id = ?2",
+            params![milestone_id, group_poam_id],
+        )?;
+        Ok(())
+    }
+
 }
 
 impl<'a> GroupQueries<'a> {
@@ -553,6 +636,7 @@ impl<'a> GroupQueries<'a> {
                     due_date: row.get(2)?,
                     status: row.get(3)?,
                     description: row.get(4)?,
+                    depends_on: None,
                 })
             })?;
             let mut milestones = Vec::new();
@@ -629,7 +713,7 @@ impl<'a> GroupQueries<'a> {
             "SELECT id, title, due_date, status, description FROM group_milestones WHERE group_poam_id = ?1 ORDER BY due_date"
         )?;
         let ms_rows = ms_stmt.query_map(params![poam.id], |row| {
-            Ok(Milestone { id: row.get(0)?, title: row.get(1)?, due_date: row.get(2)?, status: row.get(3)?, description: row.get(4)? })
+            Ok(Milestone { id: row.get(0)?, title: row.get(1)?, due_date: row.get(2)?, status: row.get(3)?, description: row.get(4)?, depends_on: None })
         })?;
         let mut milestones = Vec::new();
         for m in ms_rows { milestones.push(m?); }
@@ -638,6 +722,55 @@ impl<'a> GroupQueries<'a> {
         Ok(Some(poam))
     }
 
+    pub fn get_group_poam_rollup(&self, group_poam_id: i64) -> Result<GroupPOAMRollup, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT l.system_id, COALESCE(s.name, l.system_id), p.id, p.title, p.status
+             FROM group_poam_system_links l
+             JOIN poams p ON p.id = l.poam_id AND p.system_id = l.system_id
+             LEFT JOIN systems s ON s.id = l.system_id
+             WHERE l.group_poam_id = ?1
+             ORDER BY s.name, p.title"
+        )?;
+
+        let linked_poams = stmt
+            .query_map(params![group_poam_id], |row| {
+                Ok(LinkedSystemPOAM {
+                    system_id: row.get(0)?,
+                    system_name: row.get(1)?,
+                    poam_id: row.get(2)?,
+                    title: row.get(3)?,
+                    status: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut status_counts = std::collections::HashMap::new();
+        for poam in &linked_poams {
+            *status_counts.entry(poam.status.clone()).or_insert(0) += 1;
+        }
+
+        let completed = linked_poams
+            .iter()
+            .filter(|p| matches!(p.status.to_lowercase().as_str(), "completed" | "closed"))
+            .count();
+        let total_linked = linked_poams.len();
+        let completion_percentage = if total_linked > 0 {
+            (completed as f64 / total_linked as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(GroupPOAMRollup {
+            group_poam_id,
+            total_linked,
+            completed,
+            completion_percentage,
+            status_counts,
+            summary: format!("{} of {} member POAMs completed", completed, total_linked),
+            linked_poams,
+        })
+    }
+
     pub fn get_systems_in_group(&self, group_id: &str) -> Result<Vec<SystemSummary>, DatabaseError> {
         let mut stmt = self.conn.prepare(
             "SELECT s.id, s.name, s.description, s.owner, s.classification, s.tags, s.created_date, s.last_accessed, s.group_id,
@@ -739,4 +872,120 @@ impl<'a> GroupQueries<'a> {
 
         Ok(systems)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::{group, group_poam, memory_db, milestone, poam, system};
+    use crate::models::SystemPOAMRef;
+    use crate::database::Database;
+
+    fn db_with_group_poam() -> Database {
+        let mut db = memory_db();
+        db.create_group(&group("grp-1")).unwrap();
+        db.create_group_poam(&group_poam(1, "grp-1")).unwrap();
+        db
+    }
+
+    fn group_milestone_count(db: &Database, group_poam_id: i64) -> i64 {
+        db.conn
+            .query_row("SELECT COUNT(*) FROM group_milestones WHERE group_poam_id = ?1", [group_poam_id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn milestones_added_to_group_poam_load_with_it() {
+        let mut db = db_with_group_poam();
+        let mut later = milestone("m-2");
+        later.due_date = "2024-05-01".to_string();
+        db.create_group_milestone(1, &later).unwrap();
+        db.create_group_milestone(1, &milestone("m-1")).unwrap();
+        db.update_group_milestone_status("m-1", 1, "Completed").unwrap();
+
+        let loaded = db.get_group_poam_by_id(1).unwrap().unwrap();
+        let ids: Vec<&str> = loaded.milestones.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["m-1", "m-2"]);
+        assert_eq!(loaded.milestones[0].status, "Completed");
+
+        let listed = db.get_group_poams("grp-1").unwrap();
+        assert_eq!(listed[0].milestones.len(), 2);
+    }
+
+    #[test]
+    fn group_milestone_requires_existing_group_poam() {
+        let mut db = db_with_group_poam();
+        assert!(db.create_group_milestone(99, &milestone("m-1")).is_err());
+        assert!(db.update_group_milestone_status("missing", 1, "Completed").is_err());
+    }
+
+    #[test]
+    fn deleting_group_poam_removes_its_milestones() {
+        let mut db = db_with_group_poam();
+        db.create_group_poam(&group_poam(2, "grp-1")).unwrap();
+        db.create_group_milestone(1, &milestone("m-1")).unwrap();
+        db.create_group_milestone(2, &milestone("m-2")).unwrap();
+
+        db.delete_group_poam(1).unwrap();
+
+        assert_eq!(group_milestone_count(&db, 1), 0);
+        assert_eq!(group_milestone_count(&db, 2), 1);
+        assert!(db.get_group_poam_by_id(1).unwrap().is_none());
+    }
+
+    fn poam_in(db: &mut Database, system_id: &str, id: i64, status: &str) -> SystemPOAMRef {
+        let mut p = poam(id);
+        p.status = status.to_string();
+        db.create_poam(&p, system_id).unwrap();
+        SystemPOAMRef { system_id: system_id.to_string(), poam_id: id }
+    }
+
+    #[test]
+    fn linking_is_additive_and_checks_poam_system() {
+        let mut db = db_with_group_poam();
+        for id in ["sys-a", "sys-b"] {
+            db.create_system(&system(id)).unwrap();
+        }
+        let a = poam_in(&mut db, "sys-a", 10, "Open");
+        let b = poam_in(&mut db, "sys-b", 20, "Open");
+
+        assert_eq!(db.link_group_poam_to_system_poams(1, &[a.clone(), b.clone()]).unwrap(), 2);
+        assert_eq!(db.link_group_poam_to_system_poams(1, &[a]).unwrap(), 0);
+
+        // POAM 10 belongs to sys-a, so the ref is rejected and nothing from the batch is kept
+        let wrong_system = SystemPOAMRef { system_id: "sys-b".to_string(), poam_id: 10 };
+        let c = poam_in(&mut db, "sys-b", 30, "Open");
+        assert!(db.link_group_poam_to_system_poams(1, &[c, wrong_system]).is_err());
+        assert_eq!(db.get_group_poam_rollup(1).unwrap().total_linked, 2);
+
+        db.unlink_group_poam_from_system_poam(1, "sys-b", 20).unwrap();
+        assert_eq!(db.get_group_poam_rollup(1).unwrap().total_linked, 1);
+    }
+
+    #[test]
+    fn rollup_counts_completed_and_closed_member_poams() {
+        let mut db = db_with_group_poam();
+        for id in ["sys-a", "sys-b"] {
+            db.create_system(&system(id)).unwrap();
+        }
+        let refs = vec![
+            poam_in(&mut db, "sys-a", 10, "Completed"),
+            poam_in(&mut db, "sys-a", 11, "Open"),
+            poam_in(&mut db, "sys-b", 20, "Closed"),
+            poam_in(&mut db, "sys-b", 21, "In Progress"),
+            poam_in(&mut db, "sys-b", 22, "completed"),
+        ];
+        db.link_group_poam_to_system_poams(1, &refs).unwrap();
+
+        let rollup = db.get_group_poam_rollup(1).unwrap();
+        assert_eq!(rollup.total_linked, 5);
+        assert_eq!(rollup.completed, 3);
+        assert_eq!(rollup.completion_percentage, 60.0);
+        assert_eq!(rollup.summary, "3 of 5 member POAMs completed");
+        assert_eq!(rollup.status_counts.get("Open"), Some(&1));
+
+        // Deleting a member POAM drops it from the rollup
+        db.delete_poam(11, "sys-a").unwrap();
+        let rollup = db.get_group_poam_rollup(1).unwrap();
+        assert_eq!((rollup.completed, rollup.total_linked), (3, 4));
+    }
+}
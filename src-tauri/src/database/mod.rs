@@ -11,6 +11,12 @@ pub mod baseline_controls;
 pub mod group_baseline_controls;
 pub mod nessus;
 pub mod stig_files;
+pub mod test_case_templates;
+pub mod settings;
+pub mod poam_references;
+pub mod posture;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use utils::{DatabaseError, get_database};
 pub use systems::{SystemOperations, SystemQueries};
@@ -22,9 +28,13 @@ pub use stig_mappings::{STIGMappingOperations, STIGMappingQueries};
 pub use security_test_plans::{SecurityTestPlanOperations, SecurityTestPlanQueries};
 pub use control_poam_associations::{ControlPOAMAssociationOperations, ControlPOAMAssociationQueries};
 pub use baseline_controls::{BaselineControlOperations, BaselineControlQueries};
+pub use settings::{SettingsOperations, SettingsQueries};
+pub use poam_references::{POAMReferenceOperations, POAMReferenceQueries};
+pub use posture::{PostureOperations, PostureQueries};
+pub use test_case_templates::{TestCaseTemplateOperations, TestCaseTemplateQueries};
 pub use group_baseline_controls::{GroupBaselineControlOperations, GroupBaselineControlQueries, GroupControlPOAMAssociationOperations, GroupControlPOAMAssociationQueries, GroupBaselineControl, GroupControlPOAMAssociation};
 
-use crate::models::{POAM, POAMData, Note, STIGMappingData, SecurityTestPlan, StpPrepList, System, SystemSummary, ControlPOAMAssociation, BaselineControl, SystemGroup, GroupPOAM, STIGFileRecord, GroupSummary};
+use crate::models::{POAM, POAMData, Note, STIGMappingData, SecurityTestPlan, StpPrepList, TestCaseTemplate, System, SystemSummary, ControlPOAMAssociation, BaselineControl, SystemGroup, GroupPOAM, STIGFileRecord, GroupSummary};
 use rusqlite::Connection;
 use tauri::AppHandle;
 
@@ -34,30 +44,91 @@ pub struct Database {
 
 impl Database {
     pub fn new(app_handle: &AppHandle) -> Result<Self, DatabaseError> {
-        let mut conn = DatabaseSetup::create_database(app_handle)?;
+        let conn = DatabaseSetup::create_database(app_handle)?;
+        Self::from_connection(conn)
+    }
+
+    // Wraps an open connection, creating or migrating the schema as needed
+    pub fn from_connection(mut conn: Connection) -> Result<Self, DatabaseError> {
+        // WAL lets a read snapshot (see begin_read_snapshot) coexist with writes from other
+        // connections; the busy timeout makes a writer wait out a short lock instead of failing
+        conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get::<_, String>(0))?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+
         let mut setup = DatabaseSetup::new(&mut conn);
         setup.initialize_tables()?;
-        
+
         Ok(Self { conn })
     }
 
+    pub fn get_schema_version(&self) -> Result<i32, DatabaseError> {
+        setup::schema_version(&self.conn)
+    }
+
+    // Starts a deferred transaction used purely for reads. Every query issued before it is
+    // committed or dropped sees the same point-in-time snapshot of the database; the connection
+    // runs in WAL mode, so other readers and writers are not blocked while it is held.
+    pub fn begin_read_snapshot(&self) -> Result<rusqlite::Transaction<'_>, DatabaseError> {
+        let tx = self.conn.unchecked_transaction()?;
+        // A deferred transaction only pins its snapshot on the first read
+        tx.query_row("SELECT COUNT(*) FROM systems", [], |row| row.get::<_, i64>(0))?;
+        Ok(tx)
+    }
+
+    // Copies the database page by page through SQLite's online backup API, so the copy is
+    // consistent even if another connection writes while it runs
+    pub fn backup_to_file(&self, destination: &std::path::Path) -> Result<(), DatabaseError> {
+        self.conn.backup(rusqlite::DatabaseName::Main, destination, None)?;
+        Ok(())
+    }
+
     // Essential System Queries (read-only)
     pub fn get_system_by_id(&self, id: &str) -> Result<Option<System>, DatabaseError> {
         let system_queries = SystemQueries::new(&self.conn);
         system_queries.get_system_by_id(id)
     }
 
+    pub fn get_system_export_data(&self, system_id: &str) -> Result<crate::models::SystemExportData, DatabaseError> {
+        let system_queries = SystemQueries::new(&self.conn);
+        system_queries.get_system_export_data(system_id)
+    }
+
     pub fn get_all_systems(&self) -> Result<Vec<SystemSummary>, DatabaseError> {
         let system_queries = SystemQueries::new(&self.conn);
         system_queries.get_all_systems()
     }
 
+    pub fn get_recent_systems(&self, limit: usize) -> Result<Vec<SystemSummary>, DatabaseError> {
+        let system_queries = SystemQueries::new(&self.conn);
+        system_queries.get_recent_systems(limit)
+    }
+
+    pub fn get_stale_systems(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<Vec<SystemSummary>, DatabaseError> {
+        let system_queries = SystemQueries::new(&self.conn);
+        system_queries.get_stale_systems(cutoff)
+    }
+
+    pub fn get_systems_by_tag(&self, tag: &str) -> Result<Vec<SystemSummary>, DatabaseError> {
+        let system_queries = SystemQueries::new(&self.conn);
+        system_queries.get_systems_by_tag(tag)
+    }
+
+    pub fn get_all_tags(&self) -> Result<Vec<crate::models::TagCount>, DatabaseError> {
+        let system_queries = SystemQueries::new(&self.conn);
+        system_queries.get_all_tags()
+    }
+
     // System Operations (mutable)
     pub fn create_system(&mut self, system: &System) -> Result<(), DatabaseError> {
         let system_ops = SystemOperations::new(&mut self.conn);
         system_ops.create_system(system)
     }
 
+    pub fn create_systems_skipping_existing(&mut self, systems: &[System]) -> Result<Vec<bool>, DatabaseError> {
+        let mut system_ops = SystemOperations::new(&mut self.conn);
+        system_ops.create_systems_skipping_existing(systems)
+    }
+
     pub fn update_system(&mut self, system: &System) -> Result<(), DatabaseError> {
         let system_ops = SystemOperations::new(&mut self.conn);
         system_ops.update_system(system)
@@ -68,12 +139,58 @@ impl Database {
         system_ops.delete_system(id)
     }
 
+    pub fn reassign_system_data(&mut self, from_system_id: &str, to_system_id: &str) -> Result<std::collections::BTreeMap<String, usize>, DatabaseError> {
+        let mut system_ops = SystemOperations::new(&mut self.conn);
+        system_ops.reassign_system_data(from_system_id, to_system_id)
+    }
+
+    pub fn reset_system_data(&mut self, system_id: &str, options: &crate::models::SystemDataResetOptions) -> Result<std::collections::BTreeMap<String, usize>, DatabaseError> {
+        let mut system_ops = SystemOperations::new(&mut self.conn);
+        system_ops.reset_system_data(system_id, options)
+    }
+
+    pub fn normalize_severities(&mut self, system_id: &str) -> Result<std::collections::BTreeMap<String, usize>, DatabaseError> {
+        let mut system_ops = SystemOperations::new(&mut self.conn);
+        system_ops.normalize_severities(system_id)
+    }
+
     pub fn update_system_last_accessed(&mut self, system_id: &str) -> Result<(), DatabaseError> {
         let mut system_ops = SystemOperations::new(&mut self.conn);
         system_ops.update_system_last_accessed(system_id)
     }
 
 
+    // App Settings
+    pub fn get_setting(&self, key: &str, system_id: Option<&str>) -> Result<Option<String>, DatabaseError> {
+        let settings_queries = SettingsQueries::new(&self.conn);
+        settings_queries.get_setting(&settings::scoped_key(key, system_id))
+    }
+
+    pub fn set_setting(&mut self, key: &str, value: &str, system_id: Option<&str>) -> Result<(), DatabaseError> {
+        let mut settings_ops = SettingsOperations::new(&mut self.conn);
+        settings_ops.set_setting(&settings::scoped_key(key, system_id), value)
+    }
+
+    pub fn delete_setting(&mut self, key: &str, system_id: Option<&str>) -> Result<(), DatabaseError> {
+        let mut settings_ops = SettingsOperations::new(&mut self.conn);
+        settings_ops.delete_setting(&settings::scoped_key(key, system_id))
+    }
+
+    pub fn get_setting_as<T: serde::de::DeserializeOwned>(&self, key: &str, system_id: Option<&str>) -> Result<Option<T>, DatabaseError> {
+        let settings_queries = SettingsQueries::new(&self.conn);
+        settings_queries.get_setting_as(&settings::scoped_key(key, system_id))
+    }
+
+    pub fn set_setting_as<T: serde::Serialize>(&mut self, key: &str, value: &T, system_id: Option<&str>) -> Result<(), DatabaseError> {
+        let mut settings_ops = SettingsOperations::new(&mut self.conn);
+        settings_ops.set_setting_as(&settings::scoped_key(key, system_id), value)
+    }
+
+    pub fn get_active_system_id(&self) -> Result<String, DatabaseError> {
+        let settings_queries = SettingsQueries::new(&self.conn);
+        settings_queries.get_active_system_id()
+    }
+
     // Group Operations (mutable)
     pub fn create_group(&mut self, group: &SystemGroup) -> Result<(), DatabaseError> {
         let group_ops = GroupOperations::new(&mut self.conn);
@@ -158,10 +275,40 @@ impl Database {
         group_ops.delete_group_poam(id)
     }
 
+    pub fn link_group_poam_to_system_poams(&mut self, group_poam_id: i64, refs: &[crate::models::SystemPOAMRef]) -> Result<usize, DatabaseError> {
+        let mut group_ops = GroupOperations::new(&mut self.conn);
+        group_ops.link_group_poam_to_system_poams(group_poam_id, refs)
+    }
+
+    pub fn unlink_group_poam_from_system_poam(&mut self, group_poam_id: i64, system_id: &str, poam_id: i64) -> Result<(), DatabaseError> {
+        let mut group_ops = GroupOperations::new(&mut self.conn);
+        group_ops.unlink_group_poam_from_system_poam(group_poam_id, system_id, poam_id)
+    }
+
+    pub fn get_group_poam_rollup(&self, group_poam_id: i64) -> Result<crate::models::GroupPOAMRollup, DatabaseError> {
+        let group_queries = GroupQueries::new(&self.conn);
+        group_queries.get_group_poam_rollup(group_poam_id)
+    }
+
+    pub fn create_group_milestone(&mut self, group_poam_id: i64, milestone: &crate::models::Milestone) -> Result<(), DatabaseError> {
+        let mut group_ops = GroupOperations::new(&mut self.conn);
+        group_ops.create_group_milestone(group_poam_id, milestone)
+    }
+
+    pub fn update_group_milestone_status(&mut self, milestone_id: &str, group_poam_id: i64, status: &str) -> Result<(), DatabaseError> {
+        let mut group_ops = GroupOperations::new(&mut self.conn);
+        group_ops.update_group_milestone_status(milestone_id, group_poam_id, status)
+    }
+
+    pub fn delete_group_milestone(&mut self, milestone_id: &str, group_poam_id: i64) -> Result<(), DatabaseError> {
+        let mut group_ops = GroupOperations::new(&mut self.conn);
+        group_ops.delete_group_milestone(milestone_id, group_poam_id)
+    }
+
     // POAM Operations
-    pub fn import_poam_data(&mut self, data: &POAMData, system_id: &str) -> Result<(), DatabaseError> {
+    pub fn import_poam_data(&mut self, data: &POAMData, system_id: &str, mode: crate::models::ImportMode) -> Result<(), DatabaseError> {
         let mut poam_ops = POAMOperations::new(&mut self.conn);
-        poam_ops.import_poam_data(data, system_id)
+        poam_ops.import_poam_data(data, system_id, mode)
     }
 
     pub fn create_poam(&mut self, poam: &POAM, system_id: &str) -> Result<(), DatabaseError> {
@@ -169,6 +316,11 @@ impl Database {
         poam_ops.create_poam(poam, system_id)
     }
 
+    pub fn create_poams(&mut self, poams: &mut [POAM], system_id: &str) -> Result<(), DatabaseError> {
+        let mut poam_ops = POAMOperations::new(&mut self.conn);
+        poam_ops.create_poams(poams, system_id)
+    }
+
 
     // POAM Queries (read-only)
     pub fn get_all_poams(&self, system_id: &str) -> Result<Vec<POAM>, DatabaseError> {
@@ -181,21 +333,66 @@ impl Database {
         poam_queries.get_poam_by_id(id, system_id)
     }
 
+    pub fn get_poam_history(&self, poam_id: i64, system_id: &str) -> Result<Vec<crate::models::POAMFieldChange>, DatabaseError> {
+        let poam_queries = POAMQueries::new(&self.conn);
+        poam_queries.get_poam_history(poam_id, system_id)
+    }
+
+    pub fn get_system_field_history(&self, system_id: &str, from: Option<&str>, to: Option<&str>) -> Result<Vec<crate::models::POAMFieldChange>, DatabaseError> {
+        let poam_queries = POAMQueries::new(&self.conn);
+        poam_queries.get_system_field_history(system_id, from, to)
+    }
+
+    pub fn find_duplicate_milestone_ids(&self, system_id: &str) -> Result<Vec<crate::models::DuplicateMilestoneId>, DatabaseError> {
+        let poam_queries = POAMQueries::new(&self.conn);
+        poam_queries.find_duplicate_milestone_ids(system_id)
+    }
+
+    pub fn get_poams_by_control(&self, control_id: &str, system_id: &str) -> Result<Vec<POAM>, DatabaseError> {
+        let poam_queries = POAMQueries::new(&self.conn);
+        poam_queries.get_poams_by_control(control_id, system_id)
+    }
+
     pub fn update_poam(&mut self, poam: &POAM, system_id: &str) -> Result<(), DatabaseError> {
         let mut ops = POAMOperations::new(&mut self.conn);
         ops.update_poam(poam, system_id)
     }
 
+    pub fn complete_milestones(&mut self, refs: &[crate::models::MilestoneRef], system_id: &str) -> Result<Vec<crate::models::POAMMilestoneProgress>, DatabaseError> {
+        let mut ops = POAMOperations::new(&mut self.conn);
+        ops.complete_milestones(refs, system_id)
+    }
+
     pub fn update_milestone_status(&mut self, milestone_id: &str, poam_id: i64, status: &str, system_id: &str) -> Result<(), DatabaseError> {
         let mut ops = POAMOperations::new(&mut self.conn);
         ops.update_milestone_status(milestone_id, poam_id, status, system_id)
     }
 
+    pub fn set_milestone_dependencies(&mut self, milestone_id: &str, poam_id: i64, depends_on: &[String], system_id: &str) -> Result<(), DatabaseError> {
+        let mut ops = POAMOperations::new(&mut self.conn);
+        ops.set_milestone_dependencies(milestone_id, poam_id, depends_on, system_id)
+    }
+
     pub fn delete_poam(&mut self, poam_id: i64, system_id: &str) -> Result<(), DatabaseError> {
         let mut ops = POAMOperations::new(&mut self.conn);
         ops.delete_poam(poam_id, system_id)
     }
 
+    pub fn bulk_update_poams(&mut self, poam_ids: &[i64], changes: &crate::models::POAMBulkChanges, system_id: &str) -> Result<usize, DatabaseError> {
+        let mut poam_ops = POAMOperations::new(&mut self.conn);
+        poam_ops.bulk_update_poams(poam_ids, changes, system_id)
+    }
+
+    pub fn regenerate_milestone_ids(&mut self, system_id: &str) -> Result<usize, DatabaseError> {
+        let mut ops = POAMOperations::new(&mut self.conn);
+        ops.regenerate_milestone_ids(system_id)
+    }
+
+    pub fn delete_poams(&mut self, poam_ids: &[i64], system_id: &str) -> Result<usize, DatabaseError> {
+        let mut ops = POAMOperations::new(&mut self.conn);
+        ops.delete_poams(poam_ids, system_id)
+    }
+
     pub fn clear_database(&mut self) -> Result<(), DatabaseError> {
         let mut poam_ops = POAMOperations::new(&mut self.conn);
         poam_ops.clear_database()
@@ -207,6 +404,11 @@ impl Database {
         note_queries.get_all_notes(system_id)
     }
 
+    pub fn get_note_stats(&self, system_id: &str) -> Result<crate::models::NoteStats, DatabaseError> {
+        let note_queries = NoteQueries::new(&self.conn);
+        note_queries.get_note_stats(system_id)
+    }
+
     pub fn get_notes_by_poam(&self, poam_id: i64, system_id: &str) -> Result<Vec<Note>, DatabaseError> {
         let note_queries = NoteQueries::new(&self.conn);
         note_queries.get_notes_by_poam(poam_id, system_id)
@@ -227,7 +429,43 @@ impl Database {
         note_ops.delete_note(note_id, system_id)
     }
 
+    pub fn set_tag_on_notes(&mut self, note_ids: &[String], tag: &str, add: bool, system_id: &str) -> Result<usize, DatabaseError> {
+        let mut note_ops = NoteOperations::new(&mut self.conn);
+        note_ops.set_tag_on_notes(note_ids, tag, add, system_id)
+    }
+
     // STIG Mapping Operations - delegated to STIGMappingOperations/STIGMappingQueries
+    pub fn add_poam_reference(&mut self, reference: &crate::models::POAMReference, system_id: &str) -> Result<(), DatabaseError> {
+        let mut reference_ops = POAMReferenceOperations::new(&mut self.conn);
+        reference_ops.add_poam_reference(reference, system_id)
+    }
+
+    pub fn get_poam_references(&self, poam_id: i64, system_id: &str) -> Result<Vec<crate::models::POAMReference>, DatabaseError> {
+        let reference_queries = POAMReferenceQueries::new(&self.conn);
+        reference_queries.get_poam_references(poam_id, system_id)
+    }
+
+    pub fn get_poam_references_for_system(&self, system_id: &str) -> Result<Vec<crate::models::POAMReference>, DatabaseError> {
+        let reference_queries = POAMReferenceQueries::new(&self.conn);
+        reference_queries.get_poam_references_for_system(system_id)
+    }
+
+    pub fn delete_poam_reference(&mut self, id: &str, system_id: &str) -> Result<(), DatabaseError> {
+        let mut reference_ops = POAMReferenceOperations::new(&mut self.conn);
+        reference_ops.delete_poam_reference(id, system_id)
+    }
+
+    // Posture Operations - delegated to PostureOperations/PostureQueries
+    pub fn record_system_posture(&mut self, posture: &crate::models::SystemPosture) -> Result<(), DatabaseError> {
+        let mut posture_ops = PostureOperations::new(&mut self.conn);
+        posture_ops.record_system_posture(posture)
+    }
+
+    pub fn get_posture_history(&self, system_id: &str, limit: i64) -> Result<Vec<crate::models::SystemPosture>, DatabaseError> {
+        let posture_queries = PostureQueries::new(&self.conn);
+        posture_queries.get_posture_history(system_id, limit)
+    }
+
     pub fn save_stig_mapping(&mut self, mapping: &STIGMappingData, system_id: &str) -> Result<(), DatabaseError> {
         let mut stig_ops = STIGMappingOperations::new(&mut self.conn);
         stig_ops.save_stig_mapping(mapping, system_id)
@@ -243,6 +481,16 @@ impl Database {
         stig_queries.get_stig_mapping_by_id(id, system_id)
     }
 
+    pub fn search_stig_vulnerabilities(&self, system_id: &str, query: &str) -> Result<Vec<crate::models::StigVulnerabilityMatch>, DatabaseError> {
+        let stig_queries = STIGMappingQueries::new(&self.conn);
+        stig_queries.search_stig_vulnerabilities(system_id, query)
+    }
+
+    pub fn update_stig_mapping_metadata(&mut self, id: &str, name: &str, description: Option<&str>, system_id: &str) -> Result<(), DatabaseError> {
+        let mut stig_ops = STIGMappingOperations::new(&mut self.conn);
+        stig_ops.update_stig_mapping_metadata(id, name, description, system_id)
+    }
+
     pub fn delete_stig_mapping(&mut self, id: &str, system_id: &str) -> Result<(), DatabaseError> {
         let mut stig_ops = STIGMappingOperations::new(&mut self.conn);
         stig_ops.delete_stig_mapping(id, system_id)
@@ -264,6 +512,16 @@ impl Database {
         stp_queries.get_all_security_test_plans(system_id)
     }
 
+    pub fn get_referenced_evidence_paths(&self) -> Result<std::collections::HashSet<String>, DatabaseError> {
+        let stp_queries = SecurityTestPlanQueries::new(&self.conn);
+        stp_queries.get_referenced_evidence_paths()
+    }
+
+    pub fn get_test_case_evidence_dirs(&self) -> Result<std::collections::HashSet<(String, String)>, DatabaseError> {
+        let stp_queries = SecurityTestPlanQueries::new(&self.conn);
+        stp_queries.get_test_case_evidence_dirs()
+    }
+
     pub fn get_security_test_plan_by_id(&self, id: &str, system_id: &str) -> Result<Option<SecurityTestPlan>, DatabaseError> {
         let stp_queries = SecurityTestPlanQueries::new(&self.conn);
         stp_queries.get_security_test_plan_by_id(id, system_id)
@@ -274,11 +532,48 @@ impl Database {
         stp_ops.delete_security_test_plan(id, system_id)
     }
 
+    pub fn rekey_evidence_files(&mut self, blob_paths: &std::collections::HashMap<String, String>) -> Result<usize, DatabaseError> {
+        let mut stp_ops = SecurityTestPlanOperations::new(&mut self.conn);
+        stp_ops.rekey_evidence_files(blob_paths)
+    }
+
+    pub fn get_security_test_plans_filtered(
+        &self,
+        system_id: &str,
+        status: Option<&str>,
+        poam_id: Option<i64>,
+        stig_mapping_id: Option<&str>,
+    ) -> Result<Vec<SecurityTestPlan>, DatabaseError> {
+        let stp_queries = SecurityTestPlanQueries::new(&self.conn);
+        stp_queries.get_security_test_plans_filtered(system_id, status, poam_id, stig_mapping_id)
+    }
+
     pub fn get_test_plans_by_poam(&self, poam_id: i64, system_id: &str) -> Result<Vec<SecurityTestPlan>, DatabaseError> {
         let stp_queries = SecurityTestPlanQueries::new(&self.conn);
         stp_queries.get_test_plans_by_poam(poam_id, system_id)
     }
 
+    // Test Case Template Operations
+    pub fn save_test_case_template(&mut self, template: &TestCaseTemplate) -> Result<(), DatabaseError> {
+        let mut template_ops = TestCaseTemplateOperations::new(&mut self.conn);
+        template_ops.save_test_case_template(template)
+    }
+
+    pub fn get_test_case_templates(&self, system_id: Option<&str>) -> Result<Vec<TestCaseTemplate>, DatabaseError> {
+        let template_queries = TestCaseTemplateQueries::new(&self.conn);
+        template_queries.get_test_case_templates(system_id)
+    }
+
+    pub fn get_test_case_templates_by_ids(&self, ids: &[String], system_id: &str) -> Result<Vec<TestCaseTemplate>, DatabaseError> {
+        let template_queries = TestCaseTemplateQueries::new(&self.conn);
+        template_queries.get_test_case_templates_by_ids(ids, system_id)
+    }
+
+    pub fn delete_test_case_template(&mut self, id: &str, system_id: Option<&str>) -> Result<bool, DatabaseError> {
+        let mut template_ops = TestCaseTemplateOperations::new(&mut self.conn);
+        template_ops.delete_test_case_template(id, system_id)
+    }
+
     // STP Prep List Operations
     pub fn save_stp_prep_list(&mut self, prep_list: &StpPrepList, system_id: &str) -> Result<(), DatabaseError> {
         let mut stp_ops = SecurityTestPlanOperations::new(&mut self.conn);
@@ -318,6 +613,18 @@ impl Database {
         assoc_ops.create_control_poam_association(control_id, poam_id, system_id, created_by, notes)
     }
 
+    pub fn create_control_poam_associations(
+        &mut self,
+        control_id: &str,
+        poam_ids: &[i64],
+        system_id: &str,
+        created_by: Option<&str>,
+        notes: Option<&str>
+    ) -> Result<Vec<String>, DatabaseError> {
+        let mut assoc_ops = ControlPOAMAssociationOperations::new(&mut self.conn);
+        assoc_ops.create_control_poam_associations(control_id, poam_ids, system_id, created_by, notes)
+    }
+
     pub fn delete_control_poam_association(
         &mut self,
         association_id: &str,
@@ -336,6 +643,11 @@ impl Database {
         assoc_queries.get_control_poam_associations_by_control(control_id, system_id)
     }
 
+    pub fn get_associated_control_ids(&self, system_id: &str) -> Result<Vec<String>, DatabaseError> {
+        let assoc_queries = ControlPOAMAssociationQueries::new(&self.conn);
+        assoc_queries.get_associated_control_ids(system_id)
+    }
+
     pub fn get_control_poam_associations_by_poam(
         &self,
         poam_id: i64,
@@ -351,6 +663,11 @@ impl Database {
         baseline_queries.get_baseline_controls(system_id)
     }
 
+    pub fn find_control_across_systems(&self, control_id: &str) -> Result<Vec<crate::models::ControlSystemUsage>, DatabaseError> {
+        let baseline_queries = BaselineControlQueries::new(&self.conn);
+        baseline_queries.find_control_across_systems(control_id)
+    }
+
     pub fn add_baseline_control(&mut self, control: &BaselineControl) -> Result<(), DatabaseError> {
         let mut baseline_ops = BaselineControlOperations::new(&mut self.conn);
         baseline_ops.add_baseline_control(control)
@@ -361,6 +678,11 @@ impl Database {
         baseline_ops.update_baseline_control(control)
     }
 
+    pub fn add_missing_baseline_controls(&mut self, system_id: &str, controls: &[BaselineControl]) -> Result<(usize, usize), DatabaseError> {
+        let mut baseline_ops = BaselineControlOperations::new(&mut self.conn);
+        baseline_ops.add_missing_baseline_controls(system_id, controls)
+    }
+
     pub fn remove_baseline_control(&mut self, control_id: &str, system_id: &str) -> Result<(), DatabaseError> {
         let mut baseline_ops = BaselineControlOperations::new(&mut self.conn);
         baseline_ops.remove_baseline_control(control_id, system_id)
@@ -439,6 +761,11 @@ impl Database {
         ops.save_findings(findings, system_id)
     }
 
+    pub fn purge_nessus_scan_version(&mut self, system_id: &str, scan_name: &str, version: i64) -> Result<(usize, usize, usize), DatabaseError> {
+        let mut ops = nessus::NessusOperations::new(&mut self.conn);
+        ops.purge_scan_version(system_id, scan_name, version)
+    }
+
     pub fn get_nessus_scans(&self, system_id: &str) -> Result<Vec<nessus::NessusScanMeta>, DatabaseError> {
         let queries = nessus::NessusQueries::new(&self.conn);
         queries.get_scans(system_id)
@@ -449,6 +776,34 @@ impl Database {
         queries.get_findings_by_scan(scan_id, system_id)
     }
 
+    pub fn get_nessus_findings_paged(
+        &self,
+        scan_id: &str,
+        system_id: &str,
+        offset: i64,
+        limit: i64,
+        severity_ranks: Option<&[i32]>,
+        host_filter: Option<&str>,
+    ) -> Result<nessus::NessusFindingsPage, DatabaseError> {
+        let queries = nessus::NessusQueries::new(&self.conn);
+        queries.get_findings_paged(scan_id, system_id, offset, limit, severity_ranks, host_filter)
+    }
+
+    pub fn get_consolidated_nessus_findings(&self, scan_ids: &[String], system_id: &str) -> Result<Vec<nessus::ConsolidatedFinding>, DatabaseError> {
+        let queries = nessus::NessusQueries::new(&self.conn);
+        queries.get_consolidated_findings(scan_ids, system_id)
+    }
+
+    pub fn get_nessus_findings_with_dispositions(&self, scan_id: &str, system_id: &str) -> Result<Vec<nessus::DispositionedFinding>, DatabaseError> {
+        let queries = nessus::NessusQueries::new(&self.conn);
+        queries.get_findings_with_dispositions(scan_id, system_id)
+    }
+
+    pub fn set_nessus_finding_disposition(&mut self, system_id: &str, key: &nessus::FindingKey, disposition: nessus::FindingDisposition, justification: Option<&str>) -> Result<(), DatabaseError> {
+        let queries = nessus::NessusQueries::new(&self.conn);
+        queries.set_finding_disposition(system_id, key, disposition, justification)
+    }
+
     pub fn save_nessus_prep_list(&mut self, prep: &nessus::NessusPrepList, system_id: &str) -> Result<(), DatabaseError> {
         let queries = nessus::NessusQueries::new(&self.conn);
         queries.save_prep_list(prep, system_id)
@@ -526,3 +881,54 @@ impl Database {
         POAMOperations::delete_database_file(app_handle)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{milestone, poam, system};
+    use super::Database;
+    use rusqlite::Connection;
+
+    fn open(path: &std::path::Path) -> Database {
+        Database::from_connection(Connection::open(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn file_databases_use_wal_with_a_busy_timeout() {
+        let temp = tempfile::tempdir().unwrap();
+        let db = open(&temp.path().join("poam_tracker.db"));
+        let mode: String = db.conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+        let timeout: i64 = db.conn.query_row("PRAGMA busy_timeout", [], |row| row.get(0)).unwrap();
+        assert_eq!(mode, "wal");
+        assert_eq!(timeout, 5000);
+    }
+
+    #[test]
+    fn export_snapshot_is_consistent_while_another_connection_writes() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("poam_tracker.db");
+        let mut exporter = open(&path);
+        exporter.create_system(&system("sys")).unwrap();
+        exporter.create_poam(&super::POAM { milestones: vec![milestone("m1")], ..poam(1) }, "sys").unwrap();
+
+        let snapshot = exporter.begin_read_snapshot().unwrap();
+        let before = exporter.get_all_poams("sys").unwrap();
+
+        // A second connection edits the same POAM and adds another while the export is open
+        let mut editor = open(&path);
+        editor.update_poam(&super::POAM { milestones: vec![milestone("m1"), milestone("m2")], ..poam(1) }, "sys").unwrap();
+        editor.create_poam(&poam(2), "sys").unwrap();
+        assert_eq!(editor.get_all_poams("sys").unwrap().len(), 2, "other readers see the new data");
+
+        // Later reads in the export still see the state from when the snapshot started
+        let during = exporter.get_all_poams("sys").unwrap();
+        assert_eq!(during.len(), before.len());
+        assert_eq!(during[0].milestones.len(), 1);
+        let poam_rows: i64 = exporter.conn.query_row("SELECT COUNT(*) FROM poams", [], |row| row.get(0)).unwrap();
+        assert_eq!(poam_rows, 1);
+
+        drop(snapshot);
+        let after = exporter.get_all_poams("sys").unwrap();
+        assert_eq!(after.len(), 2);
+        assert_eq!(after.iter().find(|p| p.id == 1).unwrap().milestones.len(), 2);
+    }
+}
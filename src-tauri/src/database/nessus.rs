@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use super::utils::DatabaseError;
@@ -32,6 +33,133 @@ pub struct NessusFinding {
     pub raw_json: serde_json::Value,
 }
 
+fn finding_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<NessusFinding> {
+    let raw_json: String = row.get(14)?;
+    let raw_json: serde_json::Value = serde_json::from_str(&raw_json).unwrap_or(serde_json::json!({}));
+    Ok(NessusFinding {
+        id: row.get(0)?,
+        scan_id: row.get(1)?,
+        plugin_id: row.get(2)?,
+        plugin_name: row.get(3)?,
+        severity: row.get(4)?,
+        risk_factor: row.get(5)?,
+        cve: row.get(6)?,
+        cvss_base_score: row.get(7)?,
+        host: row.get(8)?,
+        port: row.get(9)?,
+        protocol: row.get(10)?,
+        synopsis: row.get(11)?,
+        description: row.get(12)?,
+        solution: row.get(13)?,
+        raw_json,
+    })
+}
+
+/// Rank a single severity value: 4 = Critical down to 0 = Info, or None if unrecognized.
+pub fn severity_value_rank(value: &str) -> Option<i32> {
+    match value.trim().to_lowercase().as_str() {
+        "critical" => Some(4),
+        "high" => Some(3),
+        "medium" => Some(2),
+        "low" => Some(1),
+        "info" | "informational" | "none" => Some(0),
+        other => other.parse::<i32>().ok(),
+    }
+}
+
+// SQL equivalent of severity_value_rank for one column; NULL when unrecognized
+fn severity_rank_sql(column: &str) -> String {
+    format!(
+        "CASE lower(trim({c}))
+            WHEN 'critical' THEN 4 WHEN 'high' THEN 3 WHEN 'medium' THEN 2 WHEN 'low' THEN 1
+            WHEN 'info' THEN 0 WHEN 'informational' THEN 0 WHEN 'none' THEN 0
+            ELSE CASE WHEN trim({c}) <> '' AND trim({c}) NOT GLOB '*[^0-9]*' THEN CAST(trim({c}) AS INTEGER) END
+         END",
+        c = column
+    )
+}
+
+impl NessusFinding {
+    /// Numeric rank: 4 = Critical down to 0 = Info. Nessus stores severity as a number;
+    /// named values and the risk factor are accepted as fallbacks.
+    pub fn severity_rank(&self) -> i32 {
+        self.severity.as_deref().and_then(severity_value_rank)
+            .or_else(|| self.risk_factor.as_deref().and_then(severity_value_rank))
+            .unwrap_or(0)
+    }
+}
+
+/// One page of a scan's findings, most severe first.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NessusFindingsPage {
+    pub findings: Vec<NessusFinding>,
+    pub total_count: i64,
+    pub offset: i64,
+    pub limit: i64,
+}
+
+/// A finding merged across scans, keeping the most severe copy.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConsolidatedFinding {
+    #[serde(flatten)]
+    pub finding: NessusFinding,
+    pub source_scan_ids: Vec<String>,
+}
+
+/// Identifies a finding across scans and re-imports.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FindingKey {
+    pub plugin_id: Option<i64>,
+    pub host: Option<String>,
+    pub port: Option<i64>,
+    pub protocol: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingDisposition {
+    Open,
+    AcceptedRisk,
+    FalsePositive,
+    Remediated,
+}
+
+impl FindingDisposition {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FindingDisposition::Open => "open",
+            FindingDisposition::AcceptedRisk => "accepted_risk",
+            FindingDisposition::FalsePositive => "false_positive",
+            FindingDisposition::Remediated => "remediated",
+        }
+    }
+}
+
+impl std::str::FromStr for FindingDisposition {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "open" => Ok(FindingDisposition::Open),
+            "accepted_risk" => Ok(FindingDisposition::AcceptedRisk),
+            "false_positive" => Ok(FindingDisposition::FalsePositive),
+            "remediated" => Ok(FindingDisposition::Remediated),
+            other => Err(format!(
+                "Unknown disposition '{}'; expected open, accepted_risk, false_positive or remediated",
+                other
+            )),
+        }
+    }
+}
+
+/// A scan finding with the disposition recorded for its key, "open" when none is set.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DispositionedFinding {
+    #[serde(flatten)]
+    pub finding: NessusFinding,
+    pub disposition: String,
+    pub justification: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NessusPrepList {
     pub id: String,
@@ -127,6 +255,35 @@ impl<'a> NessusOperations<'a> {
         Ok(())
     }
 
+    // Deletes every scan named `scan_name` at `version`, with its findings, then moves later
+    // versions of that scan down by one so numbering stays contiguous. Foreign keys aren't
+    // enforced on this connection, so findings and prep list references are handled explicitly.
+    // Returns (scans removed, findings removed, later versions renumbered).
+    pub fn purge_scan_version(&mut self, system_id: &str, scan_name: &str, version: i64) -> Result<(usize, usize, usize), DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let scan_ids: Vec<String> = {
+            let mut stmt = tx.prepare("SELECT id FROM nessus_scans WHERE system_id = ?1 AND name = ?2 AND version = ?3")?;
+            let rows = stmt.query_map(params![system_id, scan_name, version], |row| row.get(0))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        if scan_ids.is_empty() {
+            return Err(DatabaseError::NotFound(format!("Scan '{}' version {} not found", scan_name, version)));
+        }
+
+        let mut findings_removed = 0;
+        for scan_id in &scan_ids {
+            findings_removed += tx.execute("DELETE FROM nessus_findings WHERE scan_id = ?1 AND system_id = ?2", params![scan_id, system_id])?;
+            tx.execute("UPDATE nessus_prep_lists SET source_scan_id = NULL WHERE source_scan_id = ?1 AND system_id = ?2", params![scan_id, system_id])?;
+            tx.execute("DELETE FROM nessus_scans WHERE id = ?1 AND system_id = ?2", params![scan_id, system_id])?;
+        }
+        let renumbered = tx.execute(
+            "UPDATE nessus_scans SET version = version - 1 WHERE system_id = ?1 AND name = ?2 AND version > ?3",
+            params![system_id, scan_name, version],
+        )?;
+        tx.commit()?;
+        Ok((scan_ids.len(), findings_removed, renumbered))
+    }
+
 }
 
 impl<'a> NessusQueries<'a> {
@@ -159,25 +316,94 @@ impl<'a> NessusQueries<'a> {
             "SELECT id, scan_id, plugin_id, plugin_name, severity, risk_factor, cve, cvss_base_score, host, port, protocol, synopsis, description, solution, raw_json
              FROM nessus_findings WHERE scan_id = ?1 AND system_id = ?2"
         )?;
+        let rows = stmt.query_map(params![scan_id, system_id], finding_from_row)?;
+        let mut findings = Vec::new();
+        for r in rows { findings.push(r?); }
+        Ok(findings)
+    }
+
+    /// `severity_ranks` keeps findings whose normalized rank is in the list; `host_filter`
+    /// is a case-insensitive substring match on the host.
+    pub fn get_findings_paged(
+        &self,
+        scan_id: &str,
+        system_id: &str,
+        offset: i64,
+        limit: i64,
+        severity_ranks: Option<&[i32]>,
+        host_filter: Option<&str>,
+    ) -> Result<NessusFindingsPage, DatabaseError> {
+        let rank_expr = format!(
+            "COALESCE({}, {}, 0)",
+            severity_rank_sql("severity"),
+            severity_rank_sql("risk_factor")
+        );
+
+        let mut where_clause = "scan_id = ?1 AND system_id = ?2".to_string();
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = vec![
+            Box::new(scan_id.to_string()),
+            Box::new(system_id.to_string()),
+        ];
+        if let Some(ranks) = severity_ranks {
+            if ranks.is_empty() {
+                where_clause.push_str(" AND 0");
+            } else {
+                let placeholders: Vec<String> = ranks.iter().map(|rank| {
+                    values.push(Box::new(*rank));
+                    format!("?{}", values.len())
+                }).collect();
+                where_clause.push_str(&format!(" AND {} IN ({})", rank_expr, placeholders.join(", ")));
+            }
+        }
+        if let Some(host) = host_filter.map(str::trim).filter(|h| !h.is_empty()) {
+            let pattern = format!("%{}%", host.to_lowercase().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+            values.push(Box::new(pattern));
+            where_clause.push_str(&format!(" AND lower(COALESCE(host, '')) LIKE ?{} ESCAPE '\\'", values.len()));
+        }
+
+        let total_count: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM nessus_findings WHERE {}", where_clause),
+            rusqlite::params_from_iter(values.iter()),
+            |row| row.get(0),
+        )?;
+
+        values.push(Box::new(limit));
+        let limit_param = values.len();
+        values.push(Box::new(offset));
+        let offset_param = values.len();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, scan_id, plugin_id, plugin_name, severity, risk_factor, cve, cvss_base_score, host, port, protocol, synopsis, description, solution, raw_json
+             FROM nessus_findings WHERE {}
+             ORDER BY {} DESC, host, port, plugin_id, id
+             LIMIT ?{} OFFSET ?{}",
+            where_clause, rank_expr, limit_param, offset_param
+        ))?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(values.iter()), finding_from_row)?;
+        let mut findings = Vec::new();
+        for r in rows { findings.push(r?); }
+
+        Ok(NessusFindingsPage { findings, total_count, offset, limit })
+    }
+
+    pub fn get_findings_with_dispositions(&self, scan_id: &str, system_id: &str) -> Result<Vec<DispositionedFinding>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT f.id, f.scan_id, f.plugin_id, f.plugin_name, f.severity, f.risk_factor, f.cve, f.cvss_base_score, f.host, f.port, f.protocol, f.synopsis, f.description, f.solution, f.raw_json,
+                    d.disposition, d.justification
+             FROM nessus_findings f
+             LEFT JOIN nessus_finding_dispositions d
+               ON d.system_id = f.system_id
+              AND d.plugin_id = COALESCE(f.plugin_id, 0)
+              AND d.host = COALESCE(f.host, '')
+              AND d.port = COALESCE(f.port, 0)
+              AND d.protocol = COALESCE(f.protocol, '')
+             WHERE f.scan_id = ?1 AND f.system_id = ?2"
+        )?;
         let rows = stmt.query_map(params![scan_id, system_id], |row| {
-            let raw_json: String = row.get(14)?;
-            let raw_json: serde_json::Value = serde_json::from_str(&raw_json).unwrap_or(serde_json::json!({}));
-            Ok(NessusFinding {
-                id: row.get(0)?,
-                scan_id: row.get(1)?,
-                plugin_id: row.get(2)?,
-                plugin_name: row.get(3)?,
-                severity: row.get(4)?,
-                risk_factor: row.get(5)?,
-                cve: row.get(6)?,
-                cvss_base_score: row.get(7)?,
-                host: row.get(8)?,
-                port: row.get(9)?,
-                protocol: row.get(10)?,
-                synopsis: row.get(11)?,
-                description: row.get(12)?,
-                solution: row.get(13)?,
-                raw_json,
+            let disposition: Option<String> = row.get(15)?;
+            Ok(DispositionedFinding {
+                finding: finding_from_row(row)?,
+                disposition: disposition.unwrap_or_else(|| FindingDisposition::Open.as_str().to_string()),
+                justification: row.get(16)?,
             })
         })?;
         let mut findings = Vec::new();
@@ -185,7 +411,96 @@ impl<'a> NessusQueries<'a> {
         Ok(findings)
     }
 
+    pub fn get_consolidated_findings(&self, scan_ids: &[String], system_id: &str) -> Result<Vec<ConsolidatedFinding>, DatabaseError> {
+        let mut consolidated: Vec<ConsolidatedFinding> = Vec::new();
+        let mut index: HashMap<(Option<i64>, Option<String>, Option<i64>, Option<String>), usize> = HashMap::new();
+        let mut seen_scans = HashSet::new();
+
+        for scan_id in scan_ids {
+            if !seen_scans.insert(scan_id.as_str()) {
+                continue;
+            }
+            let belongs: i64 = self.conn.query_row(
+                "SELECT COUNT(*) FROM nessus_scans WHERE id = ?1 AND system_id = ?2",
+                params![scan_id, system_id],
+                |row| row.get(0),
+            )?;
+            if belongs == 0 {
+                return Err(DatabaseError::NotFound(format!("Nessus scan {} not found in system {}", scan_id, system_id)));
+            }
+
+            for finding in self.get_findings_by_scan(scan_id, system_id)? {
+                let key = (finding.plugin_id, finding.host.clone(), finding.port, finding.protocol.clone());
+                match index.get(&key) {
+                    Some(&i) => {
+                        let entry = &mut consolidated[i];
+                        if !entry.source_scan_ids.contains(scan_id) {
+                            entry.source_scan_ids.push(scan_id.clone());
+                        }
+                        let current = (entry.finding.severity_rank(), entry.finding.cvss_base_score.unwrap_or(0.0));
+                        let candidate = (finding.severity_rank(), finding.cvss_base_score.unwrap_or(0.0));
+                        if candidate > current {
+                            entry.finding = finding;
+                        }
+                    }
+                    None => {
+                        index.insert(key, consolidated.len());
+                        consolidated.push(ConsolidatedFinding { finding, source_scan_ids: vec![scan_id.clone()] });
+                    }
+                }
+            }
+        }
+
+        consolidated.sort_by(|a, b| {
+            b.finding.severity_rank().cmp(&a.finding.severity_rank())
+                .then_with(|| a.finding.host.cmp(&b.finding.host))
+                .then_with(|| a.finding.port.cmp(&b.finding.port))
+                .then_with(|| a.finding.plugin_id.cmp(&b.finding.plugin_id))
+        });
+        Ok(consolidated)
+    }
+
+    pub fn set_finding_disposition(&self, system_id: &str, key: &FindingKey, disposition: FindingDisposition, justification: Option<&str>) -> Result<(), DatabaseError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO nessus_finding_dispositions (system_id, plugin_id, host, port, protocol, disposition, justification, updated_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT (system_id, plugin_id, host, port, protocol)
+             DO UPDATE SET disposition = excluded.disposition, justification = excluded.justification, updated_date = excluded.updated_date",
+            params![
+                system_id,
+                key.plugin_id.unwrap_or(0),
+                key.host.as_deref().unwrap_or(""),
+                key.port.unwrap_or(0),
+                key.protocol.as_deref().unwrap_or(""),
+                disposition.as_str(),
+                justification,
+                now,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // Prep list names must be unique per source scan; the list itself may keep its name on re-save
+    fn ensure_unique_prep_list_name(&self, prep: &NessusPrepList, system_id: &str) -> Result<(), DatabaseError> {
+        let duplicates: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM nessus_prep_lists
+             WHERE system_id = ?1 AND lower(trim(name)) = lower(trim(?2)) AND source_scan_id IS ?3 AND id != ?4",
+            params![system_id, prep.name, prep.source_scan_id, prep.id],
+            |row| row.get(0),
+        )?;
+        if duplicates > 0 {
+            return Err(DatabaseError::Conflict(format!(
+                "A prep list named '{}' already exists for this scan",
+                prep.name.trim()
+            )));
+        }
+        Ok(())
+    }
+
     pub fn save_prep_list(&self, prep: &NessusPrepList, system_id: &str) -> Result<(), DatabaseError> {
+        self.ensure_unique_prep_list_name(prep, system_id)?;
+        
         let selected_findings_json = serde_json::to_string(&prep.selected_findings).unwrap();
         let asset_info_json = serde_json::to_string(&prep.asset_info).unwrap();
         let milestones_json = prep.milestones.as_ref().map(|m| serde_json::to_string(m).unwrap());
@@ -300,6 +615,9 @@ impl<'a> NessusQueries<'a> {
     }
 
     pub fn update_prep_list(&self, prep: &NessusPrepList, system_id: &str) -> Result<(), DatabaseError> {
+        self.ensure_unique_prep_list_name(prep, system_id)?;
+        
+        let updated_date = chrono::Utc::now().to_rfc3339();
         let selected_findings_json = serde_json::to_string(&prep.selected_findings).unwrap();
         let asset_info_json = serde_json::to_string(&prep.asset_info).unwrap();
         let milestones_json = prep.milestones.as_ref().map(|m| serde_json::to_string(m).unwrap());
@@ -307,7 +625,7 @@ impl<'a> NessusQueries<'a> {
         let summary_json = prep.summary.as_ref().map(|s| serde_json::to_string(s).unwrap());
         let scan_info_json = prep.scan_info.as_ref().map(|s| serde_json::to_string(s).unwrap());
         
-        self.conn.execute(
+        let updated = self.conn.execute(
             "UPDATE nessus_prep_lists SET 
                 name = ?2, description = ?3, updated_date = ?4, source_scan_id = ?5, 
                 asset_info = ?6, selected_findings = ?7, finding_count = ?8,
@@ -317,7 +635,7 @@ impl<'a> NessusQueries<'a> {
                 prep.id,
                 prep.name,
                 prep.description,
-                prep.updated_date,
+                updated_date,
                 prep.source_scan_id,
                 asset_info_json,
                 selected_findings_json,
@@ -330,6 +648,9 @@ impl<'a> NessusQueries<'a> {
                 system_id
             ],
         )?;
+        if updated == 0 {
+            return Err(DatabaseError::NotFound(format!("Prep list {} not found in system {}", prep.id, system_id)));
+        }
         Ok(())
     }
 
@@ -343,3 +664,223 @@ impl<'a> NessusQueries<'a> {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::{FindingDisposition, FindingKey, NessusFinding, NessusPrepList, NessusScanMeta};
+    use crate::database::test_support::{db_with_systems, nessus_finding, nessus_scan};
+    use crate::database::{Database, DatabaseError};
+
+    fn prep(id: &str, name: &str, scan_id: Option<&str>) -> NessusPrepList {
+        NessusPrepList {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            created_date: "2024-01-01T00:00:00Z".to_string(),
+            updated_date: "2024-01-01T00:00:00Z".to_string(),
+            source_scan_id: scan_id.map(|s| s.to_string()),
+            asset_info: serde_json::json!({}),
+            selected_findings: serde_json::json!([]),
+            finding_count: 0,
+            milestones: None,
+            cve_analysis: None,
+            summary: None,
+            prep_status: None,
+            scan_info: None,
+        }
+    }
+
+    fn db_with_scans() -> Database {
+        let mut db = db_with_systems(&["sys-1"]);
+        for scan_id in ["scan-1", "scan-2"] {
+            db.save_nessus_scan_and_findings(&nessus_scan(scan_id), &[], "sys-1").unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn prep_list_names_are_unique_per_scan() {
+        let mut db = db_with_scans();
+        db.save_nessus_prep_list(&prep("p1", "Quarterly", Some("scan-1")), "sys-1").unwrap();
+
+        let duplicate = db.save_nessus_prep_list(&prep("p2", " quarterly ", Some("scan-1")), "sys-1");
+        assert!(matches!(duplicate, Err(DatabaseError::Conflict(_))));
+
+        // The same name is fine for another scan, and re-saving the same list is not a conflict
+        db.save_nessus_prep_list(&prep("p3", "Quarterly", Some("scan-2")), "sys-1").unwrap();
+        db.save_nessus_prep_list(&NessusPrepList { finding_count: 4, ..prep("p1", "Quarterly", Some("scan-1")) }, "sys-1").unwrap();
+        assert_eq!(db.get_all_nessus_prep_lists("sys-1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn updating_a_prep_list_bumps_its_date_and_checks_conflicts() {
+        let mut db = db_with_scans();
+        db.save_nessus_prep_list(&prep("p1", "Quarterly", Some("scan-1")), "sys-1").unwrap();
+        db.save_nessus_prep_list(&prep("p2", "Monthly", Some("scan-1")), "sys-1").unwrap();
+
+        db.update_nessus_prep_list(&NessusPrepList { prep_status: Some("ready".to_string()), ..prep("p1", "Quarterly", Some("scan-1")) }, "sys-1").unwrap();
+        let updated = db.get_nessus_prep_list_by_id("p1", "sys-1").unwrap().unwrap();
+        assert_eq!(updated.prep_status.as_deref(), Some("ready"));
+        assert!(updated.updated_date.as_str() > "2024-01-01T00:00:00Z");
+        assert_eq!(updated.created_date, "2024-01-01T00:00:00Z");
+
+        let renamed = db.update_nessus_prep_list(&prep("p2", "QUARTERLY", Some("scan-1")), "sys-1");
+        assert!(matches!(renamed, Err(DatabaseError::Conflict(_))));
+        let missing = db.update_nessus_prep_list(&prep("p9", "Other", None), "sys-1");
+        assert!(matches!(missing, Err(DatabaseError::NotFound(_))));
+    }
+
+    #[test]
+    fn consolidation_keeps_the_most_severe_copy_and_lists_every_source_scan() {
+        let mut db = db_with_scans();
+        db.create_system(&crate::database::test_support::system("sys-2")).unwrap();
+        db.save_nessus_scan_and_findings(&nessus_scan("other"), &[], "sys-2").unwrap();
+
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-1"), &[
+            NessusFinding { cvss_base_score: Some(5.0), ..nessus_finding("a1", "scan-1", 100, "web", "Medium") },
+            nessus_finding("a2", "scan-1", 200, "web", "Low"),
+        ], "sys-1").unwrap();
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-2"), &[
+            NessusFinding { cvss_base_score: Some(7.5), ..nessus_finding("b1", "scan-2", 100, "web", "Medium") },
+            // Same plugin and host on another port is a separate finding
+            NessusFinding { port: Some(8443), ..nessus_finding("b2", "scan-2", 100, "web", "High") },
+        ], "sys-1").unwrap();
+
+        let scans = vec!["scan-1".to_string(), "scan-2".to_string(), "scan-1".to_string()];
+        let consolidated = db.get_consolidated_nessus_findings(&scans, "sys-1").unwrap();
+        let summary: Vec<(&str, Vec<&str>)> = consolidated
+            .iter()
+            .map(|c| (c.finding.id.as_str(), c.source_scan_ids.iter().map(|s| s.as_str()).collect()))
+            .collect();
+        assert_eq!(summary, [
+            ("b2", vec!["scan-2"]),
+            ("b1", vec!["scan-1", "scan-2"]),
+            ("a2", vec!["scan-1"]),
+        ]);
+
+        let foreign = db.get_consolidated_nessus_findings(&["scan-1".to_string(), "other".to_string()], "sys-1");
+        assert!(matches!(foreign, Err(DatabaseError::NotFound(_))));
+    }
+
+    #[test]
+    fn dispositions_survive_a_scan_reimport() {
+        let mut db = db_with_scans();
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-1"), &[
+            nessus_finding("a1", "scan-1", 100, "web", "High"),
+            NessusFinding { port: None, protocol: None, ..nessus_finding("a2", "scan-1", 200, "web", "Low") },
+        ], "sys-1").unwrap();
+
+        let key = FindingKey { plugin_id: Some(100), host: Some("web".to_string()), port: Some(443), protocol: Some("tcp".to_string()) };
+        db.set_nessus_finding_disposition("sys-1", &key, FindingDisposition::FalsePositive, None).unwrap();
+        db.set_nessus_finding_disposition("sys-1", &key, FindingDisposition::AcceptedRisk, Some("compensating control")).unwrap();
+        let portless = FindingKey { plugin_id: Some(200), host: Some("web".to_string()), port: None, protocol: None };
+        db.set_nessus_finding_disposition("sys-1", &portless, FindingDisposition::Remediated, None).unwrap();
+
+        // The re-import gets new finding ids under a new scan
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-2"), &[
+            nessus_finding("b1", "scan-2", 100, "web", "High"),
+            NessusFinding { port: None, protocol: None, ..nessus_finding("b2", "scan-2", 200, "web", "Low") },
+            nessus_finding("b3", "scan-2", 100, "db", "High"),
+        ], "sys-1").unwrap();
+
+        let mut dispositions: Vec<(String, String, Option<String>)> = db
+            .get_nessus_findings_with_dispositions("scan-2", "sys-1")
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.finding.id, f.disposition, f.justification))
+            .collect();
+        dispositions.sort();
+        assert_eq!(dispositions, [
+            ("b1".to_string(), "accepted_risk".to_string(), Some("compensating control".to_string())),
+            ("b2".to_string(), "remediated".to_string(), None),
+            ("b3".to_string(), "open".to_string(), None),
+        ]);
+    }
+
+    #[test]
+    fn disposition_values_are_validated() {
+        assert_eq!(" Accepted_Risk ".parse::<FindingDisposition>(), Ok(FindingDisposition::AcceptedRisk));
+        assert!("ignored".parse::<FindingDisposition>().is_err());
+    }
+
+    fn db_with_paged_findings() -> Database {
+        let mut db = db_with_scans();
+        let findings = vec![
+            nessus_finding("f1", "scan-1", 101, "10.0.0.1", "4"),
+            nessus_finding("f2", "scan-1", 102, "10.0.0.2", "High"),
+            nessus_finding("f3", "scan-1", 103, "10.0.0.1", "2"),
+            NessusFinding { severity: None, risk_factor: Some("High".to_string()), ..nessus_finding("f4", "scan-1", 104, "10.0.0.10", "") },
+            nessus_finding("f5", "scan-1", 105, "10.0.0.1", "0"),
+            nessus_finding("other-scan", "scan-2", 101, "10.0.0.1", "4"),
+        ];
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-1"), &findings[..5], "sys-1").unwrap();
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-2"), &findings[5..], "sys-1").unwrap();
+        db
+    }
+
+    fn page_ids(page: &super::NessusFindingsPage) -> Vec<&str> {
+        page.findings.iter().map(|f| f.id.as_str()).collect()
+    }
+
+    #[test]
+    fn findings_pages_cover_the_scan_in_severity_order() {
+        let db = db_with_paged_findings();
+        let page = |offset| db.get_nessus_findings_paged("scan-1", "sys-1", offset, 2, None, None).unwrap();
+
+        let first = page(0);
+        assert_eq!(first.total_count, 5);
+        // Equal ranks fall back to host order, and f4's rank comes from its risk factor
+        assert_eq!(page_ids(&first), ["f1", "f4"]);
+        assert_eq!(page_ids(&page(2)), ["f2", "f3"]);
+        let last = page(4);
+        assert_eq!((page_ids(&last), last.total_count), (vec!["f5"], 5));
+        assert!(page(5).findings.is_empty());
+        assert!(db.get_nessus_findings_paged("scan-1", "other", 0, 10, None, None).unwrap().findings.is_empty());
+    }
+
+    #[test]
+    fn severity_and_host_filters_combine() {
+        let db = db_with_paged_findings();
+
+        let high_or_critical = db.get_nessus_findings_paged("scan-1", "sys-1", 0, 10, Some(&[3, 4]), None).unwrap();
+        assert_eq!((page_ids(&high_or_critical), high_or_critical.total_count), (vec!["f1", "f4", "f2"], 3));
+
+        let on_host = db.get_nessus_findings_paged("scan-1", "sys-1", 0, 10, Some(&[3, 4]), Some(" 10.0.0.1")).unwrap();
+        // A substring match, so 10.0.0.10 is included
+        assert_eq!((page_ids(&on_host), on_host.total_count), (vec!["f1", "f4"], 2));
+
+        let paged = db.get_nessus_findings_paged("scan-1", "sys-1", 1, 1, Some(&[3, 4]), Some("10.0.0.1")).unwrap();
+        assert_eq!((page_ids(&paged), paged.total_count), (vec!["f4"], 2));
+
+        assert_eq!(db.get_nessus_findings_paged("scan-1", "sys-1", 0, 10, Some(&[]), None).unwrap().total_count, 0);
+    }
+
+    #[test]
+    fn purging_a_middle_version_renumbers_later_versions_of_that_scan_only() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        let weekly = |id: &str, version: i32| NessusScanMeta { name: "Weekly".to_string(), version, ..nessus_scan(id) };
+        db.save_nessus_scan_and_findings(&weekly("w1", 1), &[nessus_finding("f1", "w1", 100, "web", "High")], "sys-1").unwrap();
+        db.save_nessus_scan_and_findings(&weekly("w2", 2), &[
+            nessus_finding("f2", "w2", 100, "web", "High"),
+            nessus_finding("f3", "w2", 200, "db", "Low"),
+        ], "sys-1").unwrap();
+        db.save_nessus_scan_and_findings(&weekly("w3", 3), &[nessus_finding("f4", "w3", 100, "web", "High")], "sys-1").unwrap();
+        db.save_nessus_scan_and_findings(&NessusScanMeta { version: 3, ..nessus_scan("monthly") }, &[], "sys-1").unwrap();
+        db.save_nessus_scan_and_findings(&weekly("other-w3", 3), &[], "sys-2").unwrap();
+        db.save_nessus_prep_list(&prep("p1", "From v2", Some("w2")), "sys-1").unwrap();
+
+        assert_eq!(db.purge_nessus_scan_version("sys-1", "Weekly", 2).unwrap(), (1, 2, 1));
+
+        let mut versions: Vec<(String, i32)> = db.get_nessus_scans("sys-1").unwrap().into_iter().map(|s| (s.id, s.version)).collect();
+        versions.sort();
+        assert_eq!(versions, [("monthly".to_string(), 3), ("w1".to_string(), 1), ("w3".to_string(), 2)]);
+        assert!(db.get_nessus_findings_by_scan("w2", "sys-1").unwrap().is_empty());
+        assert_eq!(db.get_nessus_findings_by_scan("w1", "sys-1").unwrap().len(), 1);
+        assert_eq!(db.get_nessus_findings_by_scan("w3", "sys-1").unwrap().len(), 1);
+        assert_eq!(db.get_nessus_prep_list_by_id("p1", "sys-1").unwrap().unwrap().source_scan_id, None);
+        assert_eq!(db.get_nessus_scans("sys-2").unwrap()[0].version, 3);
+
+        let missing = db.purge_nessus_scan_version("sys-1", "Weekly", 3);
+        assert!(matches!(missing, Err(DatabaseError::NotFound(_))));
+    }
+}
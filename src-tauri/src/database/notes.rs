@@ -1,5 +1,5 @@
-use crate::models::Note;
-use rusqlite::{params, Connection};
+use crate::models::{Note, NoteFolderStats, NoteStats, TagCount};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde_json;
 use std::collections::HashMap;
 use super::utils::DatabaseError;
@@ -160,6 +160,50 @@ impl<'a> NoteOperations<'a> {
         
         Ok(())
     }
+
+    // Adds or removes `tag` on each listed note in one transaction and returns how many notes
+    // actually changed. Adding a tag a note already has, or removing one it lacks, is a no-op.
+    pub fn set_tag_on_notes(&mut self, note_ids: &[String], tag: &str, add: bool, system_id: &str) -> Result<usize, DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let mut modified = 0;
+        for note_id in note_ids {
+            let tags_str: Option<Option<String>> = tx.query_row(
+                "SELECT tags FROM notes WHERE id = ?1 AND system_id = ?2",
+                params![note_id, system_id],
+                |row| row.get(0),
+            ).optional()?;
+            let Some(tags_str) = tags_str else {
+                println!("Note {} not found in system {}, skipping", note_id, system_id);
+                continue;
+            };
+
+            // Unreadable tag JSON is treated like no tags, the same as when notes are loaded
+            let mut tags: Vec<String> = tags_str
+                .filter(|json| !json.trim().is_empty())
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+            let before = tags.len();
+            if add {
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+            } else {
+                tags.retain(|t| t != tag);
+            }
+            if tags.len() == before {
+                continue;
+            }
+
+            let tags_json = if tags.is_empty() { None } else { Some(serde_json::to_string(&tags)?) };
+            tx.execute(
+                "UPDATE notes SET tags = ?1 WHERE id = ?2 AND system_id = ?3",
+                params![tags_json, note_id, system_id],
+            )?;
+            modified += 1;
+        }
+        tx.commit()?;
+        Ok(modified)
+    }
 }
 
 impl<'a> NoteQueries<'a> {
@@ -247,6 +291,51 @@ impl<'a> NoteQueries<'a> {
         Ok(notes)
     }
 
+    pub fn get_note_stats(&self, system_id: &str) -> Result<NoteStats, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT COALESCE(NULLIF(TRIM(folder), ''), '(unfiled)') AS bucket, COUNT(*), MAX(date)
+             FROM notes
+             WHERE system_id = ?1
+             GROUP BY bucket
+             ORDER BY bucket"
+        )?;
+        let folders = stmt.query_map(params![system_id], |row| {
+            Ok(NoteFolderStats {
+                folder: row.get(0)?,
+                note_count: row.get(1)?,
+                last_note_date: row.get(2)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+
+        // Tags are a JSON array per note, so they are counted here rather than in SQL
+        let mut stmt = self.conn.prepare("SELECT tags FROM notes WHERE system_id = ?1 AND tags IS NOT NULL")?;
+        let tag_columns = stmt.query_map(params![system_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut counts: std::collections::BTreeMap<String, i32> = std::collections::BTreeMap::new();
+        for json_str in tag_columns {
+            let tags: Vec<String> = match serde_json::from_str(&json_str) {
+                Ok(tags) => tags,
+                Err(e) => {
+                    println!("Error parsing tags JSON: {}", e);
+                    continue;
+                }
+            };
+            let mut seen = std::collections::HashSet::new();
+            for tag in tags {
+                let tag = tag.trim().to_string();
+                if !tag.is_empty() && seen.insert(tag.to_lowercase()) {
+                    *counts.entry(tag).or_insert(0) += 1;
+                }
+            }
+        }
+
+        Ok(NoteStats {
+            total_notes: folders.iter().map(|f| f.note_count).sum(),
+            folders,
+            tags: counts.into_iter().map(|(tag, count)| TagCount { tag, count }).collect(),
+        })
+    }
+
     pub fn get_notes_by_poam(&self, poam_id: i64, system_id: &str) -> Result<Vec<Note>, DatabaseError> {
         // Get all notes associated with the given POAM
         let mut stmt = self.conn.prepare(
@@ -327,3 +416,88 @@ impl<'a> NoteQueries<'a> {
         Ok(associations)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::db_with_systems;
+    use crate::models::Note;
+
+    fn note(id: &str, folder: Option<&str>, date: &str, tags: &[&str]) -> Note {
+        Note {
+            id: id.to_string(),
+            title: format!("Note {}", id),
+            content: String::new(),
+            date: date.to_string(),
+            poam_ids: None,
+            poam_titles: None,
+            folder: folder.map(str::to_string),
+            tags: (!tags.is_empty()).then(|| tags.iter().map(|t| t.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn stats_group_folders_and_count_tags_once_per_note() {
+        let mut db = db_with_systems(&["sys", "other"]);
+        db.create_note(&note("1", Some("Audits"), "2024-03-01", &["review", "Review ", "pii"]), "sys").unwrap();
+        db.create_note(&note("2", Some("Audits"), "2024-05-10", &["review"]), "sys").unwrap();
+        db.create_note(&note("3", None, "2024-01-15", &[]), "sys").unwrap();
+        db.create_note(&note("4", Some("  "), "2024-02-20", &["pii"]), "sys").unwrap();
+        db.create_note(&note("5", Some("Audits"), "2025-01-01", &["review"]), "other").unwrap();
+
+        let stats = db.get_note_stats("sys").unwrap();
+        assert_eq!(stats.total_notes, 4);
+        let folders: Vec<(&str, i32, Option<&str>)> = stats.folders.iter()
+            .map(|f| (f.folder.as_str(), f.note_count, f.last_note_date.as_deref()))
+            .collect();
+        // Blank folders are filed with the unfiled notes
+        assert_eq!(folders, [
+            ("(unfiled)", 2, Some("2024-02-20")),
+            ("Audits", 2, Some("2024-05-10")),
+        ]);
+        let tags: Vec<(&str, i32)> = stats.tags.iter().map(|t| (t.tag.as_str(), t.count)).collect();
+        assert_eq!(tags, [("pii", 2), ("review", 2)]);
+
+        let empty = db.get_note_stats("missing").unwrap();
+        assert_eq!(empty.total_notes, 0);
+        assert!(empty.folders.is_empty() && empty.tags.is_empty());
+    }
+
+    fn tags_of(db: &crate::database::Database, id: &str) -> Option<Vec<String>> {
+        db.get_all_notes("sys").unwrap().into_iter().find(|n| n.id == id).unwrap().tags
+    }
+
+    #[test]
+    fn adding_a_tag_skips_notes_that_already_have_it() {
+        let mut db = db_with_systems(&["sys"]);
+        db.create_note(&note("1", None, "2024-01-01", &["review"]), "sys").unwrap();
+        db.create_note(&note("2", None, "2024-01-02", &[]), "sys").unwrap();
+        db.create_note(&note("3", None, "2024-01-03", &["pii"]), "sys").unwrap();
+        let ids: Vec<String> = ["1", "2", "3", "missing"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(db.set_tag_on_notes(&ids, "review", true, "sys").unwrap(), 2);
+        assert_eq!(tags_of(&db, "1"), Some(vec!["review".to_string()]));
+        assert_eq!(tags_of(&db, "2"), Some(vec!["review".to_string()]));
+        assert_eq!(tags_of(&db, "3"), Some(vec!["pii".to_string(), "review".to_string()]));
+
+        // Running it again changes nothing
+        assert_eq!(db.set_tag_on_notes(&ids, "review", true, "sys").unwrap(), 0);
+        assert_eq!(tags_of(&db, "1"), Some(vec!["review".to_string()]));
+    }
+
+    #[test]
+    fn removing_a_tag_ignores_notes_without_it() {
+        let mut db = db_with_systems(&["sys", "other"]);
+        db.create_note(&note("1", None, "2024-01-01", &["review", "pii"]), "sys").unwrap();
+        db.create_note(&note("2", None, "2024-01-02", &["review"]), "sys").unwrap();
+        db.create_note(&note("3", None, "2024-01-03", &[]), "sys").unwrap();
+        db.create_note(&note("4", None, "2024-01-04", &["review"]), "other").unwrap();
+        let ids: Vec<String> = ["1", "2", "3", "4"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(db.set_tag_on_notes(&ids, "review", false, "sys").unwrap(), 2);
+        assert_eq!(tags_of(&db, "1"), Some(vec!["pii".to_string()]));
+        assert_eq!(tags_of(&db, "2"), None, "a note left without tags stores none");
+        assert_eq!(tags_of(&db, "3"), None);
+        // Notes in another system are not touched
+        assert_eq!(db.get_all_notes("other").unwrap()[0].tags, Some(vec!["review".to_string()]));
+    }
+}
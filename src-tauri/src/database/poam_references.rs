@@ -0,0 +1,93 @@
+use crate::models::POAMReference;
+use rusqlite::{params, Connection};
+use super::utils::DatabaseError;
+
+pub struct POAMReferenceOperations<'a> {
+    conn: &'a mut Connection,
+}
+
+pub struct POAMReferenceQueries<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> POAMReferenceOperations<'a> {
+    pub fn new(conn: &'a mut Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn add_poam_reference(&mut self, reference: &POAMReference, system_id: &str) -> Result<(), DatabaseError> {
+        let poam_exists: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM poams WHERE id = ?1 AND system_id = ?2",
+            params![reference.poam_id, system_id],
+            |row| row.get(0),
+        )?;
+        if poam_exists == 0 {
+            return Err(DatabaseError::NotFound(format!("POAM {} not found in system {}", reference.poam_id, system_id)));
+        }
+
+        self.conn.execute(
+            "INSERT INTO poam_references (id, poam_id, label, url, kind, created_date, system_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                reference.id,
+                reference.poam_id,
+                reference.label,
+                reference.url,
+                reference.kind,
+                reference.created_date,
+                system_id
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_poam_reference(&mut self, id: &str, system_id: &str) -> Result<(), DatabaseError> {
+        let deleted = self.conn.execute(
+            "DELETE FROM poam_references WHERE id = ?1 AND system_id = ?2",
+            params![id, system_id],
+        )?;
+        if deleted == 0 {
+            return Err(DatabaseError::NotFound(format!("Reference {} not found in system {}", id, system_id)));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> POAMReferenceQueries<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn get_poam_references(&self, poam_id: i64, system_id: &str) -> Result<Vec<POAMReference>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, poam_id, label, url, kind, created_date FROM poam_references
+             WHERE poam_id = ?1 AND system_id = ?2 ORDER BY created_date"
+        )?;
+        let rows = stmt.query_map(params![poam_id, system_id], reference_from_row)?;
+        let mut references = Vec::new();
+        for r in rows { references.push(r?); }
+        Ok(references)
+    }
+
+    pub fn get_poam_references_for_system(&self, system_id: &str) -> Result<Vec<POAMReference>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, poam_id, label, url, kind, created_date FROM poam_references
+             WHERE system_id = ?1 ORDER BY poam_id, created_date"
+        )?;
+        let rows = stmt.query_map(params![system_id], reference_from_row)?;
+        let mut references = Vec::new();
+        for r in rows { references.push(r?); }
+        Ok(references)
+    }
+}
+
+fn reference_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<POAMReference> {
+    Ok(POAMReference {
+        id: row.get(0)?,
+        poam_id: row.get(1)?,
+        label: row.get(2)?,
+        url: row.get(3)?,
+        kind: row.get(4)?,
+        created_date: row.get(5)?,
+    })
+}
@@ -1,5 +1,5 @@
-use crate::models::{Milestone, POAM, POAMData};
-use rusqlite::{params, Connection};
+use crate::models::{derive_priority, order_milestones_by_dependencies, DuplicateMilestoneId, ImportMode, Milestone, MilestoneRef, POAM, POAMBulkChanges, POAMData, POAMFieldChange, POAMMilestoneProgress, Priority, RiskLevel};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde_json;
 use std::fs;
 use tauri::{AppHandle, Manager};
@@ -13,30 +13,231 @@ pub struct POAMQueries<'a> {
     conn: &'a Connection,
 }
 
+fn ensure_not_owned_elsewhere<T: rusqlite::ToSql + std::fmt::Display>(tx: &rusqlite::Transaction<'_>, table: &str, label: &str, id: T, system_id: &str) -> Result<(), DatabaseError> {
+    let owner: Option<String> = tx.query_row(
+        &format!("SELECT system_id FROM {} WHERE id = ?1", table),
+        params![id],
+        |row| row.get(0),
+    ).optional()?;
+    match owner {
+        Some(owner) if owner != system_id => Err(DatabaseError::Conflict(format!(
+            "{} {} already exists in system {}", label, id, owner
+        ))),
+        _ => Ok(()),
+    }
+}
+
+// POAM columns whose previous values are kept in poam_field_history when they change
+const TRACKED_POAM_FIELDS: [&str; 3] = ["status", "end_date", "responsible_party"];
+
+fn tracked_field_values(tx: &rusqlite::Transaction<'_>, poam_id: i64, system_id: &str) -> Result<Option<[Option<String>; 3]>, DatabaseError> {
+    Ok(tx.query_row(
+        "SELECT status, end_date, responsible_party FROM poams WHERE id = ?1 AND system_id = ?2",
+        params![poam_id, system_id],
+        |row| Ok([row.get(0)?, row.get(1)?, row.get(2)?]),
+    ).optional()?)
+}
+
+// Compares the stored values against `previous` and logs one history row per changed field
+fn record_field_changes(tx: &rusqlite::Transaction<'_>, poam_id: i64, system_id: &str, previous: &[Option<String>; 3]) -> Result<usize, DatabaseError> {
+    let Some(current) = tracked_field_values(tx, poam_id, system_id)? else {
+        return Ok(0);
+    };
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut recorded = 0;
+    for ((field, old_value), new_value) in TRACKED_POAM_FIELDS.iter().zip(previous).zip(&current) {
+        if old_value != new_value {
+            tx.execute(
+                "INSERT INTO poam_field_history (poam_id, system_id, field, old_value, new_value, changed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![poam_id, system_id, field, old_value, new_value, now],
+            )?;
+            recorded += 1;
+        }
+    }
+    Ok(recorded)
+}
+
+// Milestone dependencies are stored as a JSON array of milestone ids; none is stored as NULL
+fn depends_on_json(milestone: &Milestone) -> Option<String> {
+    milestone.depends_on.as_ref()
+        .filter(|ids| !ids.is_empty())
+        .map(|ids| serde_json::to_string(ids).unwrap_or_default())
+}
+
+fn depends_on_from_json(json: Option<String>) -> Option<Vec<String>> {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+}
+
+// Ratings are stored in their canonical spelling. Legacy values outside the vocabulary are stored
+// as Low with a warning; ratings a user enters are checked by the commands before they get here.
+fn canonical_priority(value: &str) -> String {
+    Priority::from_legacy(value).to_string()
+}
+
+fn canonical_risk_level(value: &str) -> String {
+    RiskLevel::from_legacy(value).to_string()
+}
+
+// Bulk changes only come from the user, so unknown ratings are rejected
+fn checked_priority(value: &str) -> Result<String, DatabaseError> {
+    value.parse::<Priority>().map(|p| p.to_string()).map_err(DatabaseError::Invalid)
+}
+
+fn checked_risk_level(value: &str) -> Result<String, DatabaseError> {
+    value.parse::<RiskLevel>().map(|r| r.to_string()).map_err(DatabaseError::Invalid)
+}
+
+// Milestone ids are a global primary key but come from the frontend and from backups, so
+// an id already used elsewhere gets a fresh UUID; dependencies within the POAM follow it.
+// Dependencies must name milestones of the same POAM and have a valid order, otherwise nothing
+// is inserted. Callers delete the POAM's own milestones first.
+fn insert_milestones(tx: &rusqlite::Transaction<'_>, poam_id: i64, milestones: &[Milestone]) -> Result<(), DatabaseError> {
+    let mut ids: Vec<String> = Vec::with_capacity(milestones.len());
+    let mut renamed = std::collections::HashMap::new();
+    for milestone in milestones {
+        let repeated = ids.contains(&milestone.id);
+        let taken: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM milestones WHERE id = ?1)",
+            params![milestone.id],
+            |row| row.get(0)
+        )?;
+
+        if repeated || taken {
+            let new_id = uuid::Uuid::new_v4().to_string();
+            println!("Milestone id {} is already in use; assigned {} for POAM {}", milestone.id, new_id, poam_id);
+            // A repeated id inside the batch keeps pointing at its first occurrence
+            if !repeated {
+                renamed.insert(milestone.id.clone(), new_id.clone());
+            }
+            ids.push(new_id);
+        } else {
+            ids.push(milestone.id.clone());
+        }
+    }
+
+    let stored_milestones: Vec<Milestone> = milestones.iter().zip(&ids)
+        .map(|(milestone, id)| Milestone {
+            id: id.clone(),
+            depends_on: milestone.depends_on.as_ref().map(|deps| {
+                deps.iter().map(|dep| renamed.get(dep).unwrap_or(dep).clone()).collect()
+            }),
+            ..milestone.clone()
+        })
+        .collect();
+    order_milestones_by_dependencies(&stored_milestones).map_err(DatabaseError::Invalid)?;
+
+    for stored in &stored_milestones {
+        tx.execute(
+            "INSERT INTO milestones (id, poam_id, title, due_date, status, description, depends_on) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                stored.id,
+                poam_id,
+                stored.title,
+                normalize_date_format(&stored.due_date),
+                stored.status,
+                stored.description,
+                depends_on_json(stored)
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_poam(tx: &rusqlite::Transaction<'_>, poam: &POAM, system_id: &str) -> Result<(), DatabaseError> {
+    // Normalize date formats and rating values for consistent storage
+    let start_date = normalize_date_format(&poam.start_date);
+    let end_date = normalize_date_format(&poam.end_date);
+    
+    // POAMs generated from findings often arrive without a priority; derive it from severity
+    let severity = poam.severity.as_deref().or(poam.raw_severity.as_deref()).filter(|s| !s.trim().is_empty());
+    let priority = match severity {
+        Some(severity) if poam.priority.trim().is_empty() => {
+            derive_priority(severity, poam.residual_risk.as_deref())
+        }
+        _ => canonical_priority(&poam.priority),
+    };
+    let risk_level = canonical_risk_level(&poam.risk_level);
+    
+    // Insert the POAM
+    tx.execute(
+        "INSERT INTO poams (id, title, description, start_date, end_date, status, priority, risk_level, system_id,
+                            resources, source_identifying_vulnerability, raw_severity, severity,
+                            relevance_of_threat, likelihood, impact, residual_risk, mitigations, devices_affected,
+                            source_stig_mapping_id, selected_vulnerabilities, responsible_party) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
+        params![
+            poam.id,
+            poam.title,
+            poam.description,
+            start_date,
+            end_date,
+            poam.status,
+            priority,
+            risk_level,
+            system_id,
+            poam.resources,
+            poam.source_identifying_vulnerability,
+            poam.raw_severity,
+            poam.severity,
+            poam.relevance_of_threat,
+            poam.likelihood,
+            poam.impact,
+            poam.residual_risk,
+            poam.mitigations,
+            poam.devices_affected,
+            poam.source_stig_mapping_id,
+            poam.selected_vulnerabilities.as_ref().map(|v| serde_json::to_string(v).unwrap_or_default()),
+            poam.responsible_party
+        ],
+    )?;
+    
+    insert_milestones(tx, poam.id, &poam.milestones)
+}
+
 impl<'a> POAMOperations<'a> {
     pub fn new(conn: &'a mut Connection) -> Self {
         Self { conn }
     }
 
-    pub fn import_poam_data(&mut self, data: &POAMData, system_id: &str) -> Result<(), DatabaseError> {
+    pub fn import_poam_data(&mut self, data: &POAMData, system_id: &str, mode: ImportMode) -> Result<(), DatabaseError> {
         // Start a transaction
         let tx = self.conn.transaction()?;
         
-        // Clear existing data for this system only
-        tx.execute("DELETE FROM milestones WHERE poam_id IN (SELECT id FROM poams WHERE system_id = ?1)", params![system_id])?;
-        tx.execute("DELETE FROM note_poam_associations WHERE note_id IN (SELECT id FROM notes WHERE system_id = ?1)", params![system_id])?;
-        tx.execute("DELETE FROM notes WHERE system_id = ?1", params![system_id])?;
-        tx.execute("DELETE FROM poams WHERE system_id = ?1", params![system_id])?;
+        if mode == ImportMode::Replace {
+            // Clear existing data for this system only
+            tx.execute("DELETE FROM milestones WHERE poam_id IN (SELECT id FROM poams WHERE system_id = ?1)", params![system_id])?;
+            tx.execute("DELETE FROM note_poam_associations WHERE note_id IN (SELECT id FROM notes WHERE system_id = ?1)", params![system_id])?;
+            tx.execute("DELETE FROM notes WHERE system_id = ?1", params![system_id])?;
+            tx.execute("DELETE FROM poam_references WHERE system_id = ?1", params![system_id])?;
+            tx.execute("DELETE FROM poams WHERE system_id = ?1", params![system_id])?;
+        }
+        
+        // Ids are global, so refuse to overwrite rows that belong to another system
+        for poam in &data.poams {
+            ensure_not_owned_elsewhere(&tx, "poams", "POAM", poam.id, system_id)?;
+        }
+        for note in &data.notes {
+            ensure_not_owned_elsewhere(&tx, "notes", "Note", &note.id, system_id)?;
+        }
         
-        // Insert POAMs
+        // Insert POAMs; on merge an existing POAM keeps its extended fields and gets the imported milestones
         for poam in &data.poams {
             // Normalize date formats for consistent storage
             let start_date = normalize_date_format(&poam.start_date);
             let end_date = normalize_date_format(&poam.end_date);
+            let priority = canonical_priority(&poam.priority);
+            let risk_level = canonical_risk_level(&poam.risk_level);
             
+            tx.execute("DELETE FROM milestones WHERE poam_id = ?1", params![poam.id])?;
             tx.execute(
                 "INSERT INTO poams (id, title, description, start_date, end_date, status, priority, risk_level, system_id) 
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                 ON CONFLICT (id) DO UPDATE SET
+                    title = excluded.title, description = excluded.description,
+                    start_date = excluded.start_date, end_date = excluded.end_date,
+                    status = excluded.status, priority = excluded.priority, risk_level = excluded.risk_level",
                 params![
                     poam.id,
                     poam.title,
@@ -44,30 +245,13 @@ impl<'a> POAMOperations<'a> {
                     start_date,
                     end_date,
                     poam.status,
-                    poam.priority,
-                    poam.risk_level,
+                    priority,
+                    risk_level,
                     system_id
                 ],
             )?;
             
-            // Insert milestones
-            for milestone in &poam.milestones {
-                // Normalize date format for consistent storage
-                let due_date = normalize_date_format(&milestone.due_date);
-                
-                tx.execute(
-                    "INSERT INTO milestones (id, poam_id, title, due_date, status, description) 
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    params![
-                        milestone.id,
-                        poam.id,
-                        milestone.title,
-                        due_date,
-                        milestone.status,
-                        milestone.description
-                    ],
-                )?;
-            }
+            insert_milestones(&tx, poam.id, &poam.milestones)?;
         }
         
         // Insert Notes
@@ -78,9 +262,13 @@ impl<'a> POAMOperations<'a> {
                 None => None
             };
             
+            tx.execute("DELETE FROM note_poam_associations WHERE note_id = ?1", params![note.id])?;
             tx.execute(
                 "INSERT INTO notes (id, title, content, date, folder, tags, system_id) 
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT (id) DO UPDATE SET
+                    title = excluded.title, content = excluded.content, date = excluded.date,
+                    folder = excluded.folder, tags = excluded.tags",
                 params![
                     note.id,
                     note.title,
@@ -96,7 +284,7 @@ impl<'a> POAMOperations<'a> {
             if let Some(poam_ids) = &note.poam_ids {
                 for poam_id in poam_ids {
                     tx.execute(
-                        "INSERT INTO note_poam_associations (note_id, poam_id) 
+                        "INSERT OR IGNORE INTO note_poam_associations (note_id, poam_id) 
                          VALUES (?1, ?2)",
                         params![note.id, poam_id],
                     )?;
@@ -115,60 +303,7 @@ impl<'a> POAMOperations<'a> {
         
         // Start a transaction
         let tx = self.conn.transaction()?;
-        
-        // Normalize date formats for consistent storage
-        let start_date = normalize_date_format(&poam.start_date);
-        let end_date = normalize_date_format(&poam.end_date);
-        
-        // Insert the POAM
-        tx.execute(
-            "INSERT INTO poams (id, title, description, start_date, end_date, status, priority, risk_level, system_id,
-                                resources, source_identifying_vulnerability, raw_severity, severity,
-                                relevance_of_threat, likelihood, impact, residual_risk, mitigations, devices_affected,
-                                source_stig_mapping_id, selected_vulnerabilities) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
-            params![
-                poam.id,
-                poam.title,
-                poam.description,
-                start_date,
-                end_date,
-                poam.status,
-                poam.priority,
-                poam.risk_level,
-                system_id,
-                poam.resources,
-                poam.source_identifying_vulnerability,
-                poam.raw_severity,
-                poam.severity,
-                poam.relevance_of_threat,
-                poam.likelihood,
-                poam.impact,
-                poam.residual_risk,
-                poam.mitigations,
-                poam.devices_affected,
-                poam.source_stig_mapping_id,
-                poam.selected_vulnerabilities.as_ref().map(|v| serde_json::to_string(v).unwrap_or_default())
-            ],
-        )?;
-        
-        // Insert milestones
-        for milestone in &poam.milestones {
-            let due_date = normalize_date_format(&milestone.due_date);
-            
-            tx.execute(
-                "INSERT INTO milestones (id, poam_id, title, due_date, status, description) 
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                params![
-                    milestone.id,
-                    poam.id,
-                    milestone.title,
-                    due_date,
-                    milestone.status,
-                    milestone.description
-                ],
-            )?;
-        }
+        insert_poam(&tx, poam, system_id)?;
         
         // Commit the transaction
         tx.commit()?;
@@ -177,6 +312,21 @@ impl<'a> POAMOperations<'a> {
         Ok(())
     }
 
+    // Inserts all POAMs in one transaction under fresh ids, which are written back into `poams`.
+    // Ids are unique across all systems, so numbering continues from the global maximum.
+    pub fn create_poams(&mut self, poams: &mut [POAM], system_id: &str) -> Result<(), DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let mut next_id: i64 = tx.query_row("SELECT COALESCE(MAX(id), 0) + 1 FROM poams", [], |row| row.get(0))?;
+        for poam in poams.iter_mut() {
+            poam.id = next_id;
+            next_id += 1;
+            insert_poam(&tx, poam, system_id)?;
+        }
+        tx.commit()?;
+        println!("Created {} POAMs in system {}", poams.len(), system_id);
+        Ok(())
+    }
+
     pub fn update_poam(&mut self, poam: &POAM, system_id: &str) -> Result<(), DatabaseError> {
         println!("Updating POAM: id={}, title={}, milestones count={} in system: {}", 
             poam.id, poam.title, poam.milestones.len(), system_id);
@@ -187,6 +337,9 @@ impl<'a> POAMOperations<'a> {
         // Update the POAM
         let start_date = normalize_date_format(&poam.start_date);
         let end_date = normalize_date_format(&poam.end_date);
+        let priority = canonical_priority(&poam.priority);
+        let risk_level = canonical_risk_level(&poam.risk_level);
+        let previous = tracked_field_values(&tx, poam.id, system_id)?;
         
         tx.execute(
             "UPDATE poams 
@@ -194,7 +347,8 @@ impl<'a> POAMOperations<'a> {
                  status = ?5, priority = ?6, risk_level = ?7,
                  resources = ?8, source_identifying_vulnerability = ?9, raw_severity = ?10, severity = ?11,
                  relevance_of_threat = ?12, likelihood = ?13, impact = ?14, residual_risk = ?15,
-                 mitigations = ?16, devices_affected = ?17, source_stig_mapping_id = ?18, selected_vulnerabilities = ?19
+                 mitigations = ?16, devices_affected = ?17, source_stig_mapping_id = ?18, selected_vulnerabilities = ?19,
+                 responsible_party = ?22
              WHERE id = ?20 AND system_id = ?21",
             params![
                 poam.title,
@@ -202,8 +356,8 @@ impl<'a> POAMOperations<'a> {
                 start_date,
                 end_date,
                 poam.status,
-                poam.priority,
-                poam.risk_level,
+                priority,
+                risk_level,
                 poam.resources,
                 poam.source_identifying_vulnerability,
                 poam.raw_severity,
@@ -217,9 +371,32 @@ impl<'a> POAMOperations<'a> {
                 poam.source_stig_mapping_id,
                 poam.selected_vulnerabilities.as_ref().map(|v| serde_json::to_string(v).unwrap_or_default()),
                 poam.id,
-                system_id
+                system_id,
+                poam.responsible_party
             ],
         )?;
+        if let Some(previous) = previous {
+            record_field_changes(&tx, poam.id, system_id, &previous)?;
+        }
+        
+        // A milestone sent without dependencies keeps its stored ones, and milestones left out of
+        // the update are dropped from the dependencies of those that remain
+        let stored_dependencies: std::collections::HashMap<String, Option<Vec<String>>> = {
+            let mut stmt = tx.prepare("SELECT id, depends_on FROM milestones WHERE poam_id = ?1")?;
+            let rows = stmt.query_map(params![poam.id], |row| Ok((row.get(0)?, depends_on_from_json(row.get(1)?))))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let removed: Vec<&String> = stored_dependencies.keys()
+            .filter(|id| !poam.milestones.iter().any(|m| &m.id == *id))
+            .collect();
+        let milestones: Vec<Milestone> = poam.milestones.iter()
+            .map(|milestone| Milestone {
+                depends_on: milestone.depends_on.clone()
+                    .or_else(|| stored_dependencies.get(&milestone.id).cloned().flatten())
+                    .map(|deps| deps.into_iter().filter(|dep| !removed.contains(&dep)).collect()),
+                ..milestone.clone()
+            })
+            .collect();
         
         // Delete existing milestones for this POAM
         tx.execute(
@@ -228,22 +405,7 @@ impl<'a> POAMOperations<'a> {
         )?;
         
         // Insert new milestones
-        for milestone in &poam.milestones {
-            let due_date = normalize_date_format(&milestone.due_date);
-            
-            tx.execute(
-                "INSERT INTO milestones (id, poam_id, title, due_date, status, description) 
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                params![
-                    milestone.id,
-                    poam.id,
-                    milestone.title,
-                    due_date,
-                    milestone.status,
-                    milestone.description
-                ],
-            )?;
-        }
+        insert_milestones(&tx, poam.id, &milestones)?;
         
         // Commit the transaction
         tx.commit()?;
@@ -252,6 +414,102 @@ impl<'a> POAMOperations<'a> {
         Ok(())
     }
 
+    // Applies the provided fields to each POAM with the same normalization as update_poam.
+    // POAMs outside the system are skipped; returns how many were updated.
+    pub fn bulk_update_poams(&mut self, poam_ids: &[i64], changes: &POAMBulkChanges, system_id: &str) -> Result<usize, DatabaseError> {
+        let text_fields: [(&str, Option<String>); 13] = [
+            ("status", changes.status.clone()),
+            ("priority", changes.priority.as_deref().map(checked_priority).transpose()?),
+            ("risk_level", changes.risk_level.as_deref().map(checked_risk_level).transpose()?),
+            ("start_date", changes.start_date.as_deref().map(normalize_date_format)),
+            ("end_date", changes.end_date.as_deref().map(normalize_date_format)),
+            ("resources", changes.resources.clone()),
+            ("severity", changes.severity.clone()),
+            ("relevance_of_threat", changes.relevance_of_threat.clone()),
+            ("likelihood", changes.likelihood.clone()),
+            ("impact", changes.impact.clone()),
+            ("residual_risk", changes.residual_risk.clone()),
+            ("mitigations", changes.mitigations.clone()),
+            ("devices_affected", changes.devices_affected.clone()),
+        ];
+        let (columns, values): (Vec<&str>, Vec<String>) = text_fields.into_iter()
+            .filter_map(|(column, value)| value.map(|v| (column, v)))
+            .unzip();
+
+        if columns.is_empty() || poam_ids.is_empty() {
+            return Ok(0);
+        }
+        println!("Bulk updating {} on {} POAMs in system: {}", columns.join(", "), poam_ids.len(), system_id);
+
+        let assignments: Vec<String> = columns.iter().enumerate()
+            .map(|(i, column)| format!("{} = ?{}", column, i + 1))
+            .collect();
+        let sql = format!(
+            "UPDATE poams SET {} WHERE id = ?{} AND system_id = ?{}",
+            assignments.join(", "),
+            columns.len() + 1,
+            columns.len() + 2
+        );
+
+        let tx = self.conn.transaction()?;
+        let mut updated = 0;
+        {
+            let mut stmt = tx.prepare(&sql)?;
+            for poam_id in poam_ids {
+                let previous = tracked_field_values(&tx, *poam_id, system_id)?;
+                let mut bound: Vec<&dyn rusqlite::ToSql> = values.iter().map(|v| v as &dyn rusqlite::ToSql).collect();
+                bound.push(poam_id);
+                bound.push(&system_id);
+                updated += stmt.execute(bound.as_slice())?;
+                if let Some(previous) = previous {
+                    record_field_changes(&tx, *poam_id, system_id, &previous)?;
+                }
+            }
+        }
+        tx.commit()?;
+
+        println!("Bulk update changed {} of {} requested POAMs", updated, poam_ids.len());
+        Ok(updated)
+    }
+
+    // Refuses dependencies that would leave the POAM's milestones without a valid order
+    pub fn set_milestone_dependencies(&mut self, milestone_id: &str, poam_id: i64, depends_on: &[String], system_id: &str) -> Result<(), DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let mut milestones: Vec<Milestone> = {
+            let mut stmt = tx.prepare(
+                "SELECT id, title, due_date, status, description, depends_on FROM milestones
+                 WHERE poam_id = ?1 AND poam_id IN (SELECT id FROM poams WHERE system_id = ?2)
+                 ORDER BY due_date"
+            )?;
+            let rows = stmt.query_map(params![poam_id, system_id], |row| {
+                Ok(Milestone {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    due_date: row.get(2)?,
+                    status: row.get(3)?,
+                    description: row.get(4)?,
+                    depends_on: depends_on_from_json(row.get(5)?),
+                })
+            })?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let Some(milestone) = milestones.iter_mut().find(|m| m.id == milestone_id) else {
+            return Err(DatabaseError::NotFound(format!(
+                "Milestone {} not found for POAM {} in system {}", milestone_id, poam_id, system_id
+            )));
+        };
+        milestone.depends_on = Some(depends_on.to_vec());
+        let depends_on_json = depends_on_json(milestone);
+        order_milestones_by_dependencies(&milestones).map_err(DatabaseError::Invalid)?;
+
+        tx.execute(
+            "UPDATE milestones SET depends_on = ?1 WHERE id = ?2 AND poam_id = ?3",
+            params![depends_on_json, milestone_id, poam_id],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn update_milestone_status(&mut self, milestone_id: &str, poam_id: i64, status: &str, system_id: &str) -> Result<(), DatabaseError> {
         println!("Updating milestone status: milestone_id={}, poam_id={}, status={}, system_id={}", milestone_id, poam_id, status, system_id);
         
@@ -286,6 +544,134 @@ impl<'a> POAMOperations<'a> {
         Ok(())
     }
 
+    // Marks every referenced milestone Completed in one transaction. A POAM whose milestones are
+    // then all complete is itself set to Completed unless it is already closed.
+    pub fn complete_milestones(&mut self, refs: &[MilestoneRef], system_id: &str) -> Result<Vec<POAMMilestoneProgress>, DatabaseError> {
+        println!("Completing {} milestones in system: {}", refs.len(), system_id);
+
+        let tx = self.conn.transaction()?;
+        let mut poam_ids: Vec<i64> = Vec::new();
+        for milestone in refs {
+            let updated = tx.execute(
+                "UPDATE milestones SET status = 'Completed'
+                 WHERE id = ?1 AND poam_id = ?2 AND poam_id IN (SELECT id FROM poams WHERE system_id = ?3)",
+                params![milestone.milestone_id, milestone.poam_id, system_id],
+            )?;
+            if updated == 0 {
+                return Err(DatabaseError::NotFound(format!(
+                    "Milestone {} not found for POAM {} in system {}",
+                    milestone.milestone_id, milestone.poam_id, system_id
+                )));
+            }
+            if !poam_ids.contains(&milestone.poam_id) {
+                poam_ids.push(milestone.poam_id);
+            }
+        }
+
+        let mut progress = Vec::with_capacity(poam_ids.len());
+        for poam_id in poam_ids {
+            let (completed, total): (i32, i32) = tx.query_row(
+                "SELECT COALESCE(SUM(CASE WHEN lower(trim(status)) = 'completed' THEN 1 ELSE 0 END), 0), COUNT(*)
+                 FROM milestones WHERE poam_id = ?1",
+                params![poam_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            let status: String = tx.query_row(
+                "SELECT status FROM poams WHERE id = ?1 AND system_id = ?2",
+                params![poam_id, system_id],
+                |row| row.get(0),
+            )?;
+
+            let closed = matches!(status.trim().to_lowercase().as_str(), "completed" | "closed" | "risk accepted");
+            let auto_completed = completed == total && !closed;
+            if auto_completed {
+                let previous = tracked_field_values(&tx, poam_id, system_id)?;
+                tx.execute(
+                    "UPDATE poams SET status = 'Completed' WHERE id = ?1 AND system_id = ?2",
+                    params![poam_id, system_id],
+                )?;
+                if let Some(previous) = previous {
+                    record_field_changes(&tx, poam_id, system_id, &previous)?;
+                }
+            }
+
+            progress.push(POAMMilestoneProgress {
+                poam_id,
+                status: if auto_completed { "Completed".to_string() } else { status },
+                completed_milestones: completed,
+                total_milestones: total,
+                auto_completed,
+            });
+        }
+        tx.commit()?;
+
+        println!(
+            "Completed {} milestones; {} POAMs auto-completed",
+            refs.len(),
+            progress.iter().filter(|p| p.auto_completed).count()
+        );
+        Ok(progress)
+    }
+
+    // Gives every milestone in the system that shares its id with an earlier row a
+    // fresh UUID. The earliest row keeps the original id so existing references stay valid,
+    // and dependencies inside a renamed milestone's POAM follow the new id.
+    pub fn regenerate_milestone_ids(&mut self, system_id: &str) -> Result<usize, DatabaseError> {
+        println!("Regenerating duplicate milestone ids in system: {}", system_id);
+
+        let tx = self.conn.transaction()?;
+
+        let duplicates: Vec<(i64, String, i64)> = {
+            let mut stmt = tx.prepare(
+                "SELECT m.rowid, m.id, m.poam_id FROM milestones m
+                 JOIN poams p ON m.poam_id = p.id
+                 WHERE p.system_id = ?1
+                   AND m.rowid > (SELECT MIN(d.rowid) FROM milestones d WHERE d.id = m.id)"
+            )?;
+            let rows = stmt.query_map(params![system_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        for (rowid, old_id, poam_id) in &duplicates {
+            let new_id = uuid::Uuid::new_v4().to_string();
+            tx.execute(
+                "UPDATE milestones SET id = ?1 WHERE rowid = ?2",
+                params![new_id, rowid],
+            )?;
+
+            // Only rewrite dependencies when the original id now lives in another POAM
+            let original_in_poam: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM milestones WHERE id = ?1 AND poam_id = ?2)",
+                params![old_id, poam_id],
+                |row| row.get(0)
+            )?;
+            if !original_in_poam {
+                let dependents: Vec<(i64, Option<String>)> = {
+                    let mut stmt = tx.prepare("SELECT rowid, depends_on FROM milestones WHERE poam_id = ?1 AND depends_on IS NOT NULL")?;
+                    let rows = stmt.query_map(params![poam_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                    rows.collect::<Result<Vec<_>, _>>()?
+                };
+                for (dependent_rowid, json) in dependents {
+                    let Some(deps) = depends_on_from_json(json) else { continue };
+                    if !deps.contains(old_id) {
+                        continue;
+                    }
+                    let deps: Vec<String> = deps.into_iter().map(|dep| if &dep == old_id { new_id.clone() } else { dep }).collect();
+                    tx.execute(
+                        "UPDATE milestones SET depends_on = ?1 WHERE rowid = ?2",
+                        params![serde_json::to_string(&deps)?, dependent_rowid],
+                    )?;
+                }
+            }
+            println!("Milestone {} (row {}) reassigned id {}", old_id, rowid, new_id);
+        }
+
+        tx.commit()?;
+
+        println!("Regenerated {} milestone ids", duplicates.len());
+        Ok(duplicates.len())
+    }
+
     pub fn delete_poam(&mut self, poam_id: i64, system_id: &str) -> Result<(), DatabaseError> {
         println!("Deleting POAM: id={} in system: {}", poam_id, system_id);
         
@@ -329,6 +715,14 @@ impl<'a> POAMOperations<'a> {
         if control_associations_deleted > 0 {
             println!("Deleted {} control associations for POAM {}", control_associations_deleted, poam_id);
         }
+        tx.execute(
+            "DELETE FROM poam_references WHERE poam_id = ?1 AND system_id = ?2",
+            params![poam_id, system_id],
+        )?;
+        tx.execute(
+            "DELETE FROM poam_field_history WHERE poam_id = ?1 AND system_id = ?2",
+            params![poam_id, system_id],
+        )?;
         
         // 4. Update any security test plans that reference this POAM
         let test_plans_updated = tx.execute(
@@ -339,7 +733,13 @@ impl<'a> POAMOperations<'a> {
             println!("Updated {} security test plans to remove POAM {} reference", test_plans_updated, poam_id);
         }
         
-        // 5. Finally, delete the POAM itself
+        // 5. Remove links from group POAM rollups
+        tx.execute(
+            "DELETE FROM group_poam_system_links WHERE poam_id = ?1 AND system_id = ?2",
+            params![poam_id, system_id],
+        )?;
+        
+        // 6. Finally, delete the POAM itself
         let poam_deleted = tx.execute(
             "DELETE FROM poams WHERE id = ?1 AND system_id = ?2",
             params![poam_id, system_id],
@@ -358,6 +758,61 @@ impl<'a> POAMOperations<'a> {
         Ok(())
     }
 
+    // Deletes several POAMs in one transaction. Ids that do not belong to the
+    // system are skipped; returns how many POAMs were actually removed.
+    pub fn delete_poams(&mut self, poam_ids: &[i64], system_id: &str) -> Result<usize, DatabaseError> {
+        println!("Bulk deleting {} POAMs in system: {}", poam_ids.len(), system_id);
+
+        let tx = self.conn.transaction()?;
+        let mut deleted = 0;
+
+        for poam_id in poam_ids {
+            let count: i64 = tx.query_row(
+                "SELECT COUNT(*) FROM poams WHERE id = ?1 AND system_id = ?2",
+                params![poam_id, system_id],
+                |row| row.get(0)
+            )?;
+            if count == 0 {
+                println!("Skipping POAM {}: not found in system {}", poam_id, system_id);
+                continue;
+            }
+
+            tx.execute("DELETE FROM note_poam_associations WHERE poam_id = ?1", params![poam_id])?;
+            tx.execute("DELETE FROM milestones WHERE poam_id = ?1", params![poam_id])?;
+            tx.execute(
+                "DELETE FROM control_poam_associations WHERE poam_id = ?1",
+                params![poam_id],
+            ).unwrap_or(0); // This table might not exist in all setups
+            tx.execute(
+                "DELETE FROM poam_references WHERE poam_id = ?1 AND system_id = ?2",
+                params![poam_id, system_id],
+            )?;
+            tx.execute(
+                "DELETE FROM poam_field_history WHERE poam_id = ?1 AND system_id = ?2",
+                params![poam_id, system_id],
+            )?;
+            tx.execute(
+                "UPDATE security_test_plans SET poam_id = NULL WHERE poam_id = ?1",
+                params![poam_id],
+            ).unwrap_or(0);
+            tx.execute(
+                "DELETE FROM group_poam_system_links WHERE poam_id = ?1 AND system_id = ?2",
+                params![poam_id, system_id],
+            )?;
+
+            deleted += tx.execute(
+                "DELETE FROM poams WHERE id = ?1 AND system_id = ?2",
+                params![poam_id, system_id],
+            )?;
+            println!("Deleted POAM {} and related data", poam_id);
+        }
+
+        tx.commit()?;
+
+        println!("Bulk delete removed {} of {} requested POAMs", deleted, poam_ids.len());
+        Ok(deleted)
+    }
+
     pub fn clear_database(&mut self) -> Result<(), DatabaseError> {
         println!("Starting database clearing process");
         
@@ -373,10 +828,13 @@ impl<'a> POAMOperations<'a> {
         let tables = vec![
             "note_poam_associations",
             "milestones", 
+            "poam_references",
+            "poam_field_history",
             "poams",
             "notes",
             "stp_prep_lists",
             "security_test_plans", 
+            "stig_vulnerabilities",
             "stig_mappings"
         ];
         
@@ -436,6 +894,10 @@ impl<'a> POAMOperations<'a> {
         // Delete the file
         match fs::remove_file(&db_path) {
             Ok(_) => {
+                // A leftover write-ahead log must not be replayed onto the next database
+                for suffix in ["-wal", "-shm"] {
+                    let _ = fs::remove_file(app_dir.join(format!("poam_tracker.db{}", suffix)));
+                }
                 println!("Database file deleted successfully: {:?}", db_path);
                 Ok(())
             },
@@ -453,19 +915,48 @@ impl<'a> POAMQueries<'a> {
         Self { conn }
     }
 
+    // Milestone ids in the system that are shared by more than one milestone row,
+    // including collisions with milestones that belong to other systems
+    pub fn find_duplicate_milestone_ids(&self, system_id: &str) -> Result<Vec<DuplicateMilestoneId>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT d.id, d.poam_id FROM milestones d
+             WHERE d.id IN (
+                 SELECT m.id FROM milestones m
+                 JOIN poams p ON m.poam_id = p.id
+                 WHERE p.system_id = ?1
+             )
+             AND d.id IN (SELECT id FROM milestones GROUP BY id HAVING COUNT(*) > 1)
+             ORDER BY d.id, d.rowid"
+        )?;
+        let rows = stmt.query_map(params![system_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+
+        let mut duplicates: Vec<DuplicateMilestoneId> = Vec::new();
+        for row in rows {
+            let (milestone_id, poam_id) = row?;
+            match duplicates.last_mut() {
+                Some(last) if last.milestone_id == milestone_id => last.poam_ids.push(poam_id),
+                _ => duplicates.push(DuplicateMilestoneId { milestone_id, poam_ids: vec![poam_id] }),
+            }
+        }
+
+        Ok(duplicates)
+    }
+
     pub fn get_all_poams(&self, system_id: &str) -> Result<Vec<POAM>, DatabaseError> {
         let mut poam_stmt = self.conn.prepare(
             "SELECT id, title, description, start_date, end_date, status, priority, risk_level,
                     resources, source_identifying_vulnerability, raw_severity, severity,
                     relevance_of_threat, likelihood, impact, residual_risk, mitigations, devices_affected,
-                    source_stig_mapping_id, selected_vulnerabilities
+                    source_stig_mapping_id, selected_vulnerabilities, responsible_party
              FROM poams 
              WHERE system_id = ?1
              ORDER BY id"
         )?;
         
         let mut milestone_stmt = self.conn.prepare(
-            "SELECT id, title, due_date, status, description
+            "SELECT id, title, due_date, status, description, depends_on
              FROM milestones
              WHERE poam_id = ?1
              ORDER BY due_date"
@@ -498,6 +989,7 @@ impl<'a> POAMQueries<'a> {
                     let vuln_json: Option<String> = row.get(19)?;
                     vuln_json.and_then(|json| serde_json::from_str(&json).ok())
                 },
+                responsible_party: row.get::<_, Option<String>>(20)?,
             })
         })?;
         
@@ -512,6 +1004,7 @@ impl<'a> POAMQueries<'a> {
                     due_date: row.get(2)?,
                     status: row.get(3)?,
                     description: row.get(4)?,
+                    depends_on: depends_on_from_json(row.get(5)?),
                 })
             })?;
             
@@ -525,18 +1018,139 @@ impl<'a> POAMQueries<'a> {
         Ok(poams)
     }
 
+    // POAMs associated with a control, loaded with two set-based queries rather than one per POAM
+    pub fn get_poams_by_control(&self, control_id: &str, system_id: &str) -> Result<Vec<POAM>, DatabaseError> {
+        let mut poam_stmt = self.conn.prepare(
+            "SELECT DISTINCT p.id, p.title, p.description, p.start_date, p.end_date, p.status, p.priority, p.risk_level,
+                    p.resources, p.source_identifying_vulnerability, p.raw_severity, p.severity,
+                    p.relevance_of_threat, p.likelihood, p.impact, p.residual_risk, p.mitigations, p.devices_affected,
+                    p.source_stig_mapping_id, p.selected_vulnerabilities, p.responsible_party
+             FROM poams p
+             JOIN control_poam_associations cpa ON cpa.poam_id = p.id AND cpa.system_id = p.system_id
+             WHERE cpa.control_id = ?1 AND p.system_id = ?2
+             ORDER BY p.id"
+        )?;
+        
+        let poam_rows = poam_stmt.query_map(params![control_id, system_id], |row| {
+            Ok(POAM {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                description: row.get(2)?,
+                start_date: row.get(3)?,
+                end_date: row.get(4)?,
+                status: row.get(5)?,
+                priority: row.get(6)?,
+                risk_level: row.get(7)?,
+                milestones: Vec::new(),
+                resources: row.get::<_, Option<String>>(8)?,
+                source_identifying_vulnerability: row.get::<_, Option<String>>(9)?,
+                raw_severity: row.get::<_, Option<String>>(10)?,
+                severity: row.get::<_, Option<String>>(11)?,
+                relevance_of_threat: row.get::<_, Option<String>>(12)?,
+                likelihood: row.get::<_, Option<String>>(13)?,
+                impact: row.get::<_, Option<String>>(14)?,
+                residual_risk: row.get::<_, Option<String>>(15)?,
+                mitigations: row.get::<_, Option<String>>(16)?,
+                devices_affected: row.get::<_, Option<String>>(17)?,
+                source_stig_mapping_id: row.get::<_, Option<String>>(18)?,
+                selected_vulnerabilities: {
+                    let vuln_json: Option<String> = row.get(19)?;
+                    vuln_json.and_then(|json| serde_json::from_str(&json).ok())
+                },
+                responsible_party: row.get::<_, Option<String>>(20)?,
+            })
+        })?;
+        let mut poams = poam_rows.collect::<Result<Vec<_>, _>>()?;
+        
+        let mut milestone_stmt = self.conn.prepare(
+            "SELECT m.poam_id, m.id, m.title, m.due_date, m.status, m.description, m.depends_on
+             FROM milestones m
+             WHERE m.poam_id IN (
+                 SELECT cpa.poam_id FROM control_poam_associations cpa
+                 WHERE cpa.control_id = ?1 AND cpa.system_id = ?2
+             )
+             ORDER BY m.due_date"
+        )?;
+        let milestone_rows = milestone_stmt.query_map(params![control_id, system_id], |row| {
+            Ok((row.get::<_, i64>(0)?, Milestone {
+                id: row.get(1)?,
+                title: row.get(2)?,
+                due_date: row.get(3)?,
+                status: row.get(4)?,
+                description: row.get(5)?,
+                depends_on: depends_on_from_json(row.get(6)?),
+            }))
+        })?;
+        
+        let mut milestones_by_poam: std::collections::HashMap<i64, Vec<Milestone>> = std::collections::HashMap::new();
+        for milestone_result in milestone_rows {
+            let (poam_id, milestone) = milestone_result?;
+            milestones_by_poam.entry(poam_id).or_default().push(milestone);
+        }
+        for poam in &mut poams {
+            poam.milestones = milestones_by_poam.remove(&poam.id).unwrap_or_default();
+        }
+        
+        Ok(poams)
+    }
+
+    // Change log for a POAM's tracked fields, oldest first
+    pub fn get_poam_history(&self, poam_id: i64, system_id: &str) -> Result<Vec<POAMFieldChange>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, poam_id, field, old_value, new_value, changed_at
+             FROM poam_field_history
+             WHERE poam_id = ?1 AND system_id = ?2
+             ORDER BY changed_at, id"
+        )?;
+        let rows = stmt.query_map(params![poam_id, system_id], |row| {
+            Ok(POAMFieldChange {
+                id: row.get(0)?,
+                poam_id: row.get(1)?,
+                field: row.get(2)?,
+                old_value: row.get(3)?,
+                new_value: row.get(4)?,
+                changed_at: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    // Field changes across a system's POAMs, oldest first. `from` and `to` are inclusive and
+    // compared as prefixes, so "2024-03-31" covers the whole day.
+    pub fn get_system_field_history(&self, system_id: &str, from: Option<&str>, to: Option<&str>) -> Result<Vec<POAMFieldChange>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, poam_id, field, old_value, new_value, changed_at
+             FROM poam_field_history
+             WHERE system_id = ?1
+               AND (?2 IS NULL OR substr(changed_at, 1, length(?2)) >= ?2)
+               AND (?3 IS NULL OR substr(changed_at, 1, length(?3)) <= ?3)
+             ORDER BY changed_at, id"
+        )?;
+        let rows = stmt.query_map(params![system_id, from, to], |row| {
+            Ok(POAMFieldChange {
+                id: row.get(0)?,
+                poam_id: row.get(1)?,
+                field: row.get(2)?,
+                old_value: row.get(3)?,
+                new_value: row.get(4)?,
+                changed_at: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
     pub fn get_poam_by_id(&self, id: i64, system_id: &str) -> Result<Option<POAM>, DatabaseError> {
         let mut poam_stmt = self.conn.prepare(
             "SELECT id, title, description, start_date, end_date, status, priority, risk_level,
                     resources, source_identifying_vulnerability, raw_severity, severity,
                     relevance_of_threat, likelihood, impact, residual_risk, mitigations, devices_affected,
-                    source_stig_mapping_id, selected_vulnerabilities
+                    source_stig_mapping_id, selected_vulnerabilities, responsible_party
              FROM poams 
              WHERE id = ?1 AND system_id = ?2"
         )?;
         
         let mut milestone_stmt = self.conn.prepare(
-            "SELECT id, title, due_date, status, description
+            "SELECT id, title, due_date, status, description, depends_on
              FROM milestones
              WHERE poam_id = ?1
              ORDER BY due_date"
@@ -569,6 +1183,7 @@ impl<'a> POAMQueries<'a> {
                     let vuln_json: Option<String> = row.get(19)?;
                     vuln_json.and_then(|json| serde_json::from_str(&json).ok())
                 },
+                responsible_party: row.get::<_, Option<String>>(20)?,
             })
         });
         
@@ -581,6 +1196,7 @@ impl<'a> POAMQueries<'a> {
                         due_date: row.get(2)?,
                         status: row.get(3)?,
                         description: row.get(4)?,
+                        depends_on: depends_on_from_json(row.get(5)?),
                     })
                 })?;
                 
@@ -595,3 +1211,511 @@ impl<'a> POAMQueries<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::{db_with_systems, milestone, poam, test_plan};
+    use crate::database::{Database, DatabaseError};
+    use crate::models::{ImportMode, Note};
+
+    fn count(db: &Database, sql: &str, poam_id: i64) -> i64 {
+        db.conn.query_row(sql, [poam_id], |row| row.get(0)).unwrap()
+    }
+
+    fn seed_poams(db: &mut Database, system_id: &str, ids: &[i64]) {
+        for id in ids {
+            let mut p = poam(*id);
+            p.milestones = vec![milestone(&format!("m-{}", id))];
+            db.create_poam(&p, system_id).unwrap();
+        }
+    }
+
+    #[test]
+    fn bulk_delete_removes_only_the_requested_subset() {
+        let mut db = db_with_systems(&["sys-1"]);
+        seed_poams(&mut db, "sys-1", &[1, 2, 3]);
+
+        assert_eq!(db.delete_poams(&[1, 3], "sys-1").unwrap(), 2);
+
+        let remaining: Vec<i64> = db.get_all_poams("sys-1").unwrap().iter().map(|p| p.id).collect();
+        assert_eq!(remaining, vec![2]);
+    }
+
+    #[test]
+    fn bulk_delete_ignores_ids_from_another_system() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        seed_poams(&mut db, "sys-1", &[1]);
+        seed_poams(&mut db, "sys-2", &[2]);
+
+        assert_eq!(db.delete_poams(&[1, 2, 99], "sys-1").unwrap(), 1);
+        assert_eq!(db.get_all_poams("sys-2").unwrap().len(), 1);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM milestones WHERE poam_id = ?1", 2), 1);
+    }
+
+    #[test]
+    fn bulk_delete_cleans_up_dependent_rows() {
+        let mut db = db_with_systems(&["sys-1"]);
+        seed_poams(&mut db, "sys-1", &[1, 2]);
+
+        let note = Note {
+            id: "note-1".to_string(),
+            title: "Findings".to_string(),
+            content: String::new(),
+            date: "2024-01-01".to_string(),
+            poam_ids: Some(vec![1, 2]),
+            poam_titles: None,
+            folder: None,
+            tags: None,
+        };
+        db.create_note(&note, "sys-1").unwrap();
+        db.create_control_poam_association("AC-2", 1, "sys-1", None, None).unwrap();
+        let mut plan = test_plan("plan-1", Vec::new());
+        plan.poam_id = Some(1);
+        db.save_security_test_plan(&plan, "sys-1").unwrap();
+
+        db.delete_poams(&[1], "sys-1").unwrap();
+
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM milestones WHERE poam_id = ?1", 1), 0);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM note_poam_associations WHERE poam_id = ?1", 1), 0);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM control_poam_associations WHERE poam_id = ?1", 1), 0);
+        // The note and test plan survive; only their links to POAM 1 go away
+        assert_eq!(db.get_all_notes("sys-1").unwrap()[0].poam_ids.as_deref(), Some(&[2][..]));
+        assert_eq!(db.get_security_test_plan_by_id("plan-1", "sys-1").unwrap().unwrap().poam_id, None);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM milestones WHERE poam_id = ?1", 2), 1);
+    }
+
+    fn milestone_ids(db: &Database, poam_id: i64) -> Vec<String> {
+        let mut stmt = db.conn.prepare("SELECT id FROM milestones WHERE poam_id = ?1 ORDER BY due_date").unwrap();
+        stmt.query_map([poam_id], |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn restored_milestone_ids_that_collide_get_fresh_ids() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        seed_poams(&mut db, "sys-1", &[1]);
+
+        // A restore into another system brings the same milestone ids along
+        let mut first = milestone("m-1");
+        first.due_date = "2024-02-01".to_string();
+        let mut second = milestone("m-2");
+        second.depends_on = Some(vec!["m-1".to_string()]);
+        let restored = crate::models::POAM { milestones: vec![first, second], ..poam(2) };
+        db.create_poam(&restored, "sys-2").unwrap();
+
+        let ids = milestone_ids(&db, 2);
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], "m-1");
+        assert_eq!(ids[1], "m-2");
+        let loaded = db.get_all_poams("sys-2").unwrap().remove(0);
+        assert_eq!(loaded.milestones[1].depends_on, Some(vec![ids[0].clone()]));
+
+        // Status updates are scoped by POAM, so only the intended milestone changes
+        db.update_milestone_status("m-1", 1, "Completed", "sys-1").unwrap();
+        assert_eq!(db.get_all_poams("sys-1").unwrap()[0].milestones[0].status, "Completed");
+        assert_eq!(loaded.milestones[0].status, "Not Started");
+        assert!(db.update_milestone_status("m-1", 2, "Completed", "sys-2").is_err());
+    }
+
+    #[test]
+    fn imported_milestone_ids_that_collide_get_fresh_ids() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        seed_poams(&mut db, "sys-1", &[1]);
+
+        let data = crate::models::POAMData {
+            poams: vec![crate::models::POAM { milestones: vec![milestone("m-1"), milestone("m-1")], ..poam(2) }],
+            notes: Vec::new(),
+            stig_mappings: None,
+        };
+        db.import_poam_data(&data, "sys-2", ImportMode::Replace).unwrap();
+
+        let ids = milestone_ids(&db, 2);
+        assert_eq!(ids.len(), 2);
+        assert!(ids.iter().all(|id| id != "m-1"));
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(milestone_ids(&db, 1), vec!["m-1".to_string()]);
+
+        // Re-saving a POAM with its own ids keeps them
+        let saved = db.get_all_poams("sys-1").unwrap().remove(0);
+        db.update_poam(&saved, "sys-1").unwrap();
+        assert_eq!(milestone_ids(&db, 1), vec!["m-1".to_string()]);
+    }
+
+    #[test]
+    fn ratings_are_stored_canonically_and_legacy_values_default_to_low() {
+        let mut db = db_with_systems(&["sys-1"]);
+        let mut p = poam(1);
+        p.priority = "high".to_string();
+        p.risk_level = "moderate".to_string();
+        db.create_poam(&p, "sys-1").unwrap();
+
+        let stored = db.get_all_poams("sys-1").unwrap().remove(0);
+        assert_eq!((stored.priority.as_str(), stored.risk_level.as_str()), ("High", "Moderate"));
+
+        // A restored or imported POAM with unknown ratings is still written
+        let mut legacy = poam(2);
+        legacy.priority = "urgent".to_string();
+        legacy.risk_level = String::new();
+        db.create_poam(&legacy, "sys-1").unwrap();
+        let stored_legacy = db.get_poam_by_id(2, "sys-1").unwrap().unwrap();
+        assert_eq!((stored_legacy.priority.as_str(), stored_legacy.risk_level.as_str()), ("Low", "Low"));
+
+        let changes = crate::models::POAMBulkChanges { risk_level: Some("Severe".to_string()), ..Default::default() };
+        assert!(db.bulk_update_poams(&[1], &changes, "sys-1").is_err());
+        assert_eq!(db.get_all_poams("sys-1").unwrap()[0].risk_level, "Moderate");
+    }
+
+    #[test]
+    fn blank_priority_is_derived_from_severity_but_explicit_priority_is_kept() {
+        let mut db = db_with_systems(&["sys-1"]);
+
+        let mut derived = poam(1);
+        derived.priority = String::new();
+        derived.severity = Some("CAT I".to_string());
+        db.create_poam(&derived, "sys-1").unwrap();
+
+        let mut escalated = poam(2);
+        escalated.priority = " ".to_string();
+        escalated.raw_severity = Some("low".to_string());
+        escalated.residual_risk = Some("Very High".to_string());
+        db.create_poam(&escalated, "sys-1").unwrap();
+
+        let mut explicit = poam(3);
+        explicit.priority = "low".to_string();
+        explicit.severity = Some("High".to_string());
+        db.create_poam(&explicit, "sys-1").unwrap();
+
+        let priorities: Vec<String> = db.get_all_poams("sys-1").unwrap().into_iter().map(|p| p.priority).collect();
+        assert_eq!(priorities, ["High", "Critical", "Low"]);
+    }
+
+    fn note(id: &str, poam_ids: Vec<i64>) -> Note {
+        Note {
+            id: id.to_string(),
+            title: format!("Note {}", id),
+            content: String::new(),
+            date: "2024-01-01".to_string(),
+            poam_ids: Some(poam_ids),
+            poam_titles: None,
+            folder: None,
+            tags: None,
+        }
+    }
+
+    #[test]
+    fn merge_import_upserts_by_id_and_keeps_unrelated_data() {
+        let mut db = db_with_systems(&["sys-1"]);
+        db.create_poam(&crate::models::POAM { mitigations: Some("Firewall rule".to_string()), ..poam(1) }, "sys-1").unwrap();
+        db.create_poam(&poam(2), "sys-1").unwrap();
+        db.create_note(&note("kept", vec![2]), "sys-1").unwrap();
+
+        let data = crate::models::POAMData {
+            poams: vec![
+                crate::models::POAM { title: "Renamed".to_string(), milestones: vec![milestone("m-new")], ..poam(1) },
+                poam(3),
+            ],
+            notes: vec![note("added", vec![1, 3])],
+            stig_mappings: None,
+        };
+        db.import_poam_data(&data, "sys-1", ImportMode::Merge).unwrap();
+
+        let poams = db.get_all_poams("sys-1").unwrap();
+        assert_eq!(poams.iter().map(|p| p.id).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(poams[0].title, "Renamed");
+        assert_eq!(poams[0].mitigations.as_deref(), Some("Firewall rule"));
+        assert_eq!(milestone_ids(&db, 1), vec!["m-new".to_string()]);
+        let mut note_ids: Vec<String> = db.get_all_notes("sys-1").unwrap().into_iter().map(|n| n.id).collect();
+        note_ids.sort();
+        assert_eq!(note_ids, ["added", "kept"]);
+
+        // Replace clears everything the payload does not carry
+        db.import_poam_data(&crate::models::POAMData { poams: vec![poam(3)], notes: Vec::new(), stig_mappings: None }, "sys-1", ImportMode::Replace).unwrap();
+        assert_eq!(db.get_all_poams("sys-1").unwrap().iter().map(|p| p.id).collect::<Vec<_>>(), [3]);
+        assert!(db.get_all_notes("sys-1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn merge_import_refuses_ids_owned_by_another_system() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        seed_poams(&mut db, "sys-1", &[1]);
+
+        let data = crate::models::POAMData { poams: vec![poam(1)], notes: Vec::new(), stig_mappings: None };
+        assert!(matches!(db.import_poam_data(&data, "sys-2", ImportMode::Merge), Err(DatabaseError::Conflict(_))));
+        assert_eq!(db.get_all_poams("sys-1").unwrap()[0].title, "POAM 1");
+        assert_eq!(serde_json::from_str::<ImportMode>("\"merge\"").unwrap(), ImportMode::Merge);
+        assert_eq!(ImportMode::default(), ImportMode::Replace);
+    }
+
+    #[test]
+    fn bulk_update_changes_only_the_given_fields_on_the_selected_poams() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        for id in 1..=3 {
+            let poam = crate::models::POAM { mitigations: Some(format!("Mitigation {}", id)), milestones: vec![milestone(&format!("m-{}", id))], ..poam(id) };
+            db.create_poam(&poam, "sys-1").unwrap();
+        }
+        seed_poams(&mut db, "sys-2", &[4]);
+
+        let changes = crate::models::POAMBulkChanges {
+            status: Some("In Progress".to_string()),
+            end_date: Some("2024-12-31T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(db.bulk_update_poams(&[1, 2, 4], &changes, "sys-1").unwrap(), 2);
+
+        let poams = db.get_all_poams("sys-1").unwrap();
+        for poam in &poams[..2] {
+            assert_eq!((poam.status.as_str(), poam.end_date.as_str()), ("In Progress", "2024-12-31"));
+            assert_eq!(poam.mitigations, Some(format!("Mitigation {}", poam.id)));
+            assert_eq!((poam.priority.as_str(), poam.risk_level.as_str(), poam.title.clone()), ("Medium", "Moderate", format!("POAM {}", poam.id)));
+            assert_eq!(poam.milestones.len(), 1);
+        }
+        assert_eq!((poams[2].status.as_str(), poams[2].end_date.as_str()), ("Open", "2024-06-30"));
+        assert_eq!(db.get_all_poams("sys-2").unwrap()[0].status, "Open");
+
+        let fields: Vec<String> = db.get_poam_history(1, "sys-1").unwrap().into_iter().map(|c| c.field).collect();
+        assert!(fields.contains(&"status".to_string()) && fields.contains(&"end_date".to_string()));
+        assert_eq!(db.bulk_update_poams(&[1], &Default::default(), "sys-1").unwrap(), 0);
+    }
+
+    #[test]
+    fn end_date_change_writes_one_history_row() {
+        let mut db = db_with_systems(&["sys-1"]);
+        db.create_poam(&poam(1), "sys-1").unwrap();
+
+        let mut updated = db.get_poam_by_id(1, "sys-1").unwrap().unwrap();
+        updated.end_date = "2024-09-30".to_string();
+        db.update_poam(&updated, "sys-1").unwrap();
+
+        let history = db.get_poam_history(1, "sys-1").unwrap();
+        let changes: Vec<(&str, Option<&str>, Option<&str>)> = history.iter()
+            .map(|c| (c.field.as_str(), c.old_value.as_deref(), c.new_value.as_deref()))
+            .collect();
+        assert_eq!(changes, [("end_date", Some("2024-06-30"), Some("2024-09-30"))]);
+        assert!(db.get_poam_history(1, "sys-2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn saving_unchanged_tracked_fields_writes_no_history() {
+        let mut db = db_with_systems(&["sys-1"]);
+        db.create_poam(&crate::models::POAM { responsible_party: Some("ISSO".to_string()), ..poam(1) }, "sys-1").unwrap();
+
+        // Untracked fields may change without leaving a trace
+        let mut updated = db.get_poam_by_id(1, "sys-1").unwrap().unwrap();
+        updated.title = "Renamed".to_string();
+        db.update_poam(&updated, "sys-1").unwrap();
+        db.update_poam(&updated, "sys-1").unwrap();
+        assert!(db.get_poam_history(1, "sys-1").unwrap().is_empty());
+
+        updated.status = "In Progress".to_string();
+        updated.responsible_party = Some("ISSM".to_string());
+        db.update_poam(&updated, "sys-1").unwrap();
+        let mut fields: Vec<String> = db.get_poam_history(1, "sys-1").unwrap().into_iter().map(|c| c.field).collect();
+        fields.sort();
+        assert_eq!(fields, ["responsible_party", "status"]);
+
+        db.delete_poam(1, "sys-1").unwrap();
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM poam_field_history WHERE poam_id = ?1", 1), 0);
+    }
+
+    fn milestone_ref(poam_id: i64, milestone_id: &str) -> crate::models::MilestoneRef {
+        crate::models::MilestoneRef { poam_id, milestone_id: milestone_id.to_string() }
+    }
+
+    fn seed_two_milestones(db: &mut Database) {
+        let mut p = poam(1);
+        p.milestones = vec![
+            crate::models::Milestone { status: "Completed".to_string(), ..milestone("m-1") },
+            milestone("m-2"),
+        ];
+        db.create_poam(&p, "sys").unwrap();
+    }
+
+    #[test]
+    fn completing_the_last_milestone_auto_completes_the_poam() {
+        let mut db = db_with_systems(&["sys"]);
+        seed_two_milestones(&mut db);
+
+        let progress = db.complete_milestones(&[milestone_ref(1, "m-2")], "sys").unwrap();
+        assert_eq!(progress.len(), 1);
+        assert!(progress[0].auto_completed);
+        assert_eq!((progress[0].completed_milestones, progress[0].total_milestones), (2, 2));
+        assert_eq!(progress[0].status, "Completed");
+        assert_eq!(db.get_poam_by_id(1, "sys").unwrap().unwrap().status, "Completed");
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM poam_field_history WHERE poam_id = ?1 AND field = 'status'", 1), 1);
+    }
+
+    #[test]
+    fn completing_milestones_leaves_unfinished_and_closed_poams_alone() {
+        let mut db = db_with_systems(&["sys"]);
+        db.create_poam(&crate::models::POAM { milestones: vec![milestone("u-1"), milestone("u-2")], ..poam(1) }, "sys").unwrap();
+        db.create_poam(&crate::models::POAM { status: "Risk Accepted".to_string(), milestones: vec![milestone("r-1")], ..poam(2) }, "sys").unwrap();
+
+        let progress = db.complete_milestones(&[milestone_ref(1, "u-1"), milestone_ref(2, "r-1")], "sys").unwrap();
+        assert!(progress.iter().all(|p| !p.auto_completed));
+        assert_eq!((progress[0].completed_milestones, progress[0].total_milestones), (1, 2));
+        let statuses: Vec<&str> = progress.iter().map(|p| p.status.as_str()).collect();
+        assert_eq!(statuses, ["Open", "Risk Accepted"]);
+        assert_eq!(db.get_poam_by_id(1, "sys").unwrap().unwrap().status, "Open");
+        assert_eq!(db.get_poam_by_id(2, "sys").unwrap().unwrap().milestones[0].status, "Completed");
+    }
+
+    #[test]
+    fn completing_an_unknown_milestone_rolls_back_the_batch() {
+        let mut db = db_with_systems(&["sys"]);
+        seed_two_milestones(&mut db);
+
+        let result = db.complete_milestones(&[milestone_ref(1, "m-2"), milestone_ref(1, "missing")], "sys");
+        assert!(matches!(result, Err(DatabaseError::NotFound(_))));
+        let stored = db.get_poam_by_id(1, "sys").unwrap().unwrap();
+        assert_eq!(stored.milestones.iter().find(|m| m.id == "m-2").unwrap().status, "Not Started");
+        assert_eq!(stored.status, "Open");
+    }
+
+    fn dependent(id: &str, due_date: &str, depends_on: &[&str]) -> crate::models::Milestone {
+        crate::models::Milestone {
+            due_date: due_date.to_string(),
+            depends_on: if depends_on.is_empty() { None } else { Some(depends_on.iter().map(|dep| dep.to_string()).collect()) },
+            ..milestone(id)
+        }
+    }
+
+    fn dependencies(db: &Database, poam_id: i64) -> Vec<(String, Option<Vec<String>>)> {
+        let poam = db.get_poam_by_id(poam_id, "sys-1").unwrap().unwrap();
+        poam.milestones.into_iter().map(|m| (m.id, m.depends_on)).collect()
+    }
+
+    #[test]
+    fn milestone_dependencies_order_a_dag_and_survive_updates() {
+        let mut db = db_with_systems(&["sys-1"]);
+        let plan = crate::models::POAM {
+            milestones: vec![
+                dependent("m-a", "2024-03-01", &[]),
+                dependent("m-b", "2024-02-01", &["m-a"]),
+                dependent("m-c", "2024-01-01", &["m-a", "m-b"]),
+                dependent("m-d", "2024-04-01", &[]),
+            ],
+            ..poam(1)
+        };
+        db.create_poam(&plan, "sys-1").unwrap();
+
+        let stored = db.get_poam_by_id(1, "sys-1").unwrap().unwrap();
+        let ordered = crate::models::order_milestones_by_dependencies(&stored.milestones).unwrap();
+        let ids: Vec<&str> = ordered.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["m-a", "m-b", "m-c", "m-d"]);
+
+        // A payload without dependencies keeps the stored ones
+        let mut edited = stored.clone();
+        edited.title = "Renamed".to_string();
+        for milestone in &mut edited.milestones {
+            milestone.depends_on = None;
+        }
+        db.update_poam(&edited, "sys-1").unwrap();
+        let after = db.get_poam_by_id(1, "sys-1").unwrap().unwrap();
+        assert_eq!(after.milestones.iter().find(|m| m.id == "m-c").unwrap().depends_on, Some(vec!["m-a".to_string(), "m-b".to_string()]));
+
+        // Deleting a milestone removes it from the dependencies of the rest
+        let mut trimmed = after.clone();
+        trimmed.milestones.retain(|m| m.id != "m-a");
+        for milestone in &mut trimmed.milestones {
+            milestone.depends_on = None;
+        }
+        db.update_poam(&trimmed, "sys-1").unwrap();
+        assert_eq!(dependencies(&db, 1), [
+            ("m-c".to_string(), Some(vec!["m-b".to_string()])),
+            ("m-b".to_string(), None),
+            ("m-d".to_string(), None),
+        ]);
+
+        // An explicit empty list clears them
+        let mut cleared = db.get_poam_by_id(1, "sys-1").unwrap().unwrap();
+        cleared.milestones[0].depends_on = Some(Vec::new());
+        db.update_poam(&cleared, "sys-1").unwrap();
+        assert!(dependencies(&db, 1).iter().all(|(_, deps)| deps.is_none()));
+    }
+
+    #[test]
+    fn cyclic_milestone_dependencies_are_rejected_on_every_write_path() {
+        let mut db = db_with_systems(&["sys-1"]);
+        let plan = crate::models::POAM {
+            milestones: vec![
+                dependent("m-a", "2024-01-01", &[]),
+                dependent("m-b", "2024-02-01", &["m-a"]),
+                dependent("m-c", "2024-03-01", &["m-b"]),
+            ],
+            ..poam(1)
+        };
+        db.create_poam(&plan, "sys-1").unwrap();
+        let before = dependencies(&db, 1);
+
+        let closed = db.set_milestone_dependencies("m-a", 1, &["m-c".to_string()], "sys-1");
+        match closed {
+            Err(DatabaseError::Invalid(message)) => assert!(message.contains("cycle"), "{}", message),
+            other => panic!("expected a cycle error, got {:?}", other),
+        }
+        assert_eq!(dependencies(&db, 1), before);
+        db.set_milestone_dependencies("m-c", 1, &["m-a".to_string()], "sys-1").unwrap();
+        assert!(matches!(
+            db.set_milestone_dependencies("m-z", 1, &[], "sys-1"),
+            Err(DatabaseError::NotFound(_))
+        ));
+
+        let mut cyclic = db.get_poam_by_id(1, "sys-1").unwrap().unwrap();
+        cyclic.title = "Not saved".to_string();
+        cyclic.milestones[0].depends_on = Some(vec!["m-c".to_string()]);
+        assert!(matches!(db.update_poam(&cyclic, "sys-1"), Err(DatabaseError::Invalid(_))));
+        assert_eq!(db.get_poam_by_id(1, "sys-1").unwrap().unwrap().title, "POAM 1");
+
+        let data = crate::models::POAMData {
+            poams: vec![crate::models::POAM {
+                milestones: vec![dependent("i-1", "2024-01-01", &["i-2"]), dependent("i-2", "2024-02-01", &["i-1"])],
+                ..poam(2)
+            }],
+            notes: Vec::new(),
+            stig_mappings: None,
+        };
+        assert!(matches!(db.import_poam_data(&data, "sys-1", ImportMode::Merge), Err(DatabaseError::Invalid(_))));
+        assert!(db.get_poam_by_id(2, "sys-1").unwrap().is_none());
+
+        let unknown = crate::models::POAM { milestones: vec![dependent("u-1", "2024-01-01", &["missing"])], ..poam(3) };
+        assert!(matches!(db.create_poam(&unknown, "sys-1"), Err(DatabaseError::Invalid(_))));
+    }
+
+    // Databases written before milestones.id was enforced as a key can hold the same id twice
+    fn seed_legacy_duplicate_milestones(db: &mut Database) {
+        seed_poams(db, "sys-1", &[1]);
+        seed_poams(db, "sys-2", &[2]);
+        db.conn.execute_batch(
+            "DROP TABLE milestones;
+             CREATE TABLE milestones (id TEXT, poam_id INTEGER NOT NULL, title TEXT NOT NULL, due_date TEXT NOT NULL,
+                                      status TEXT NOT NULL, description TEXT NOT NULL, depends_on TEXT);
+             INSERT INTO milestones VALUES ('m-1', 1, 'First', '2024-01-01', 'Not Started', '', NULL);
+             INSERT INTO milestones VALUES ('m-1', 2, 'First', '2024-01-01', 'Not Started', '', NULL);
+             INSERT INTO milestones VALUES ('m-2', 2, 'Second', '2024-02-01', 'Not Started', '', '[\"m-1\"]');"
+        ).unwrap();
+    }
+
+    #[test]
+    fn duplicate_milestone_ids_are_found_and_regenerated() {
+        let mut db = db_with_systems(&["sys-1", "sys-2"]);
+        seed_legacy_duplicate_milestones(&mut db);
+
+        let duplicates = db.find_duplicate_milestone_ids("sys-2").unwrap();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].milestone_id, "m-1");
+        assert_eq!(duplicates[0].poam_ids, vec![1, 2]);
+
+        // The targeted update only touches the milestone in the given POAM
+        db.update_milestone_status("m-1", 2, "Completed", "sys-2").unwrap();
+        assert_eq!(db.get_all_poams("sys-1").unwrap()[0].milestones[0].status, "Not Started");
+        assert_eq!(db.get_all_poams("sys-2").unwrap()[0].milestones[0].status, "Completed");
+
+        assert_eq!(db.regenerate_milestone_ids("sys-2").unwrap(), 1);
+        assert!(db.find_duplicate_milestone_ids("sys-2").unwrap().is_empty());
+        assert_eq!(milestone_ids(&db, 1), vec!["m-1".to_string()]);
+
+        let ids = milestone_ids(&db, 2);
+        assert_ne!(ids[0], "m-1");
+        let restored = db.get_all_poams("sys-2").unwrap().remove(0);
+        assert_eq!(restored.milestones[1].depends_on, Some(vec![ids[0].clone()]));
+        assert_eq!(db.regenerate_milestone_ids("sys-2").unwrap(), 0);
+    }
+}
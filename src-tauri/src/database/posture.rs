@@ -0,0 +1,56 @@
+use crate::models::SystemPosture;
+use rusqlite::{params, Connection};
+use super::utils::DatabaseError;
+
+pub struct PostureOperations<'a> {
+    conn: &'a mut Connection,
+}
+
+pub struct PostureQueries<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> PostureOperations<'a> {
+    pub fn new(conn: &'a mut Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn record_system_posture(&mut self, posture: &SystemPosture) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "INSERT INTO posture_history (system_id, score, components, computed_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                posture.system_id,
+                posture.score,
+                serde_json::to_string(&posture.components)?,
+                posture.computed_at
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+impl<'a> PostureQueries<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Most recent scores first
+    pub fn get_posture_history(&self, system_id: &str, limit: i64) -> Result<Vec<SystemPosture>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT system_id, score, components, computed_at FROM posture_history
+             WHERE system_id = ?1 ORDER BY computed_at DESC, id DESC LIMIT ?2"
+        )?;
+        let rows = stmt.query_map(params![system_id, limit], |row| {
+            let components: String = row.get(2)?;
+            Ok(SystemPosture {
+                system_id: row.get(0)?,
+                score: row.get(1)?,
+                components: serde_json::from_str(&components).unwrap_or_default(),
+                computed_at: row.get(3)?,
+            })
+        })?;
+        let mut history = Vec::new();
+        for r in rows { history.push(r?); }
+        Ok(history)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::{SecurityTestPlan, StpPrepList};
+use crate::models::{SecurityTestPlan, StpPrepList, TestCase};
 use rusqlite::{params, Connection};
 use serde_json;
 use super::utils::DatabaseError;
@@ -49,6 +49,62 @@ impl<'a> SecurityTestPlanOperations<'a> {
         Ok(())
     }
 
+    // Points evidence references at their content-addressed copies across system and group test
+    // plans in one transaction. `blob_paths` maps an old stored path to its blob path; the old file
+    // name is kept in evidence_file_names for display. Returns the number of references rewritten.
+    pub fn rekey_evidence_files(&mut self, blob_paths: &std::collections::HashMap<String, String>) -> Result<usize, DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let mut rewritten = 0;
+        for table in ["security_test_plans", "group_security_test_plans"] {
+            let rows: Vec<(String, String)> = {
+                let mut stmt = tx.prepare(&format!("SELECT id, test_cases FROM {}", table))?;
+                let rows = stmt.query_map(params![], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                rows.collect::<Result<Vec<_>, _>>()?
+            };
+
+            for (id, test_cases_json) in rows {
+                let mut test_cases: Vec<TestCase> = serde_json::from_str(&test_cases_json)?;
+                let mut changed = false;
+                for test_case in &mut test_cases {
+                    let Some(evidence_files) = &mut test_case.evidence_files else { continue };
+                    let mut updated: Vec<String> = Vec::with_capacity(evidence_files.len());
+                    for path in evidence_files.drain(..) {
+                        let Some(blob_path) = blob_paths.get(&path.replace('\\', "/")) else {
+                            if !updated.contains(&path) {
+                                updated.push(path);
+                            }
+                            continue;
+                        };
+                        let original_name = std::path::Path::new(&path.replace('\\', "/"))
+                            .file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_else(|| path.clone());
+                        test_case.evidence_file_names
+                            .get_or_insert_with(Default::default)
+                            .entry(blob_path.clone())
+                            .or_insert(original_name);
+                        // Identical files attached twice to one test case collapse to one reference
+                        if !updated.contains(blob_path) {
+                            updated.push(blob_path.clone());
+                        }
+                        rewritten += 1;
+                        changed = true;
+                    }
+                    *evidence_files = updated;
+                }
+
+                if changed {
+                    tx.execute(
+                        &format!("UPDATE {} SET test_cases = ?1 WHERE id = ?2", table),
+                        params![serde_json::to_string(&test_cases)?, id],
+                    )?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(rewritten)
+    }
+
     // STP Prep List Operations
     pub fn save_stp_prep_list(&mut self, prep_list: &StpPrepList, system_id: &str) -> Result<(), DatabaseError> {
         let stig_info_json = serde_json::to_string(&prep_list.stig_info).unwrap();
@@ -124,6 +180,43 @@ impl<'a> SecurityTestPlanQueries<'a> {
         Ok(plans)
     }
 
+    // Every evidence path referenced by a system or group test case, whatever the system's state.
+    // Read as raw JSON so a plan that no longer matches the model still protects its files.
+    pub fn get_referenced_evidence_paths(&self) -> Result<std::collections::HashSet<String>, DatabaseError> {
+        let mut referenced = std::collections::HashSet::new();
+        for table in ["security_test_plans", "group_security_test_plans"] {
+            let mut stmt = self.conn.prepare(&format!("SELECT test_cases FROM {}", table))?;
+            let rows = stmt.query_map(params![], |row| row.get::<_, String>(0))?;
+            for row in rows {
+                let test_cases: serde_json::Value = serde_json::from_str(&row?)?;
+                let files = test_cases.as_array().into_iter().flatten()
+                    .filter_map(|tc| tc.get("evidence_files").and_then(|f| f.as_array()))
+                    .flatten()
+                    .filter_map(|f| f.as_str());
+                referenced.extend(files.map(|f| f.replace('\\', "/")));
+            }
+        }
+        Ok(referenced)
+    }
+
+    // (plan id, test case id) of every system and group test case, i.e. the evidence
+    // directories the app may have written
+    pub fn get_test_case_evidence_dirs(&self) -> Result<std::collections::HashSet<(String, String)>, DatabaseError> {
+        let mut dirs = std::collections::HashSet::new();
+        for table in ["security_test_plans", "group_security_test_plans"] {
+            let mut stmt = self.conn.prepare(&format!("SELECT id, test_cases FROM {}", table))?;
+            let rows = stmt.query_map(params![], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+            for row in rows {
+                let (plan_id, test_cases) = row?;
+                let test_cases: serde_json::Value = serde_json::from_str(&test_cases)?;
+                let case_ids = test_cases.as_array().into_iter().flatten()
+                    .filter_map(|tc| tc.get("id").and_then(|id| id.as_str()));
+                dirs.extend(case_ids.map(|case_id| (plan_id.clone(), case_id.to_string())));
+            }
+        }
+        Ok(dirs)
+    }
+
     pub fn get_security_test_plan_by_id(&self, id: &str, system_id: &str) -> Result<Option<SecurityTestPlan>, DatabaseError> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, description, created_date, updated_date, status, poam_id, stig_mapping_id, test_cases, overall_score
@@ -189,6 +282,50 @@ impl<'a> SecurityTestPlanQueries<'a> {
         Ok(plans)
     }
 
+    // Each filter is optional; a NULL parameter disables its clause
+    pub fn get_security_test_plans_filtered(
+        &self,
+        system_id: &str,
+        status: Option<&str>,
+        poam_id: Option<i64>,
+        stig_mapping_id: Option<&str>,
+    ) -> Result<Vec<SecurityTestPlan>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, description, created_date, updated_date, status, poam_id, stig_mapping_id, test_cases, overall_score
+             FROM security_test_plans
+             WHERE system_id = ?1
+               AND (?2 IS NULL OR status = ?2)
+               AND (?3 IS NULL OR poam_id = ?3)
+               AND (?4 IS NULL OR stig_mapping_id = ?4)
+             ORDER BY updated_date DESC"
+        )?;
+        
+        let mut plans = Vec::new();
+        let rows = stmt.query_map(params![system_id, status, poam_id, stig_mapping_id], |row| {
+            let test_cases_json: String = row.get(8)?;
+            let test_cases = serde_json::from_str(&test_cases_json).unwrap_or_default();
+            
+            Ok(SecurityTestPlan {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                created_date: row.get(3)?,
+                updated_date: row.get(4)?,
+                status: row.get(5)?,
+                poam_id: row.get(6)?,
+                stig_mapping_id: row.get(7)?,
+                test_cases,
+                overall_score: row.get(9)?,
+            })
+        })?;
+        
+        for row in rows {
+            plans.push(row?);
+        }
+        
+        Ok(plans)
+    }
+
     // STP Prep List Queries
     pub fn get_all_stp_prep_lists(&self, system_id: &str) -> Result<Vec<StpPrepList>, DatabaseError> {
         let mut stmt = self.conn.prepare(
@@ -305,3 +442,69 @@ impl<'a> SecurityTestPlanQueries<'a> {
         Ok(prep_lists)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::{db_with_systems, poam, test_plan};
+    use crate::database::Database;
+
+    fn seed() -> Database {
+        let mut db = db_with_systems(&["sys", "other"]);
+        db.create_poam(&poam(1), "sys").unwrap();
+        db.create_poam(&poam(2), "sys").unwrap();
+
+        let plans = [
+            ("draft-1", "Draft", Some(1), "2024-01-01"),
+            ("active-1", "In Progress", Some(1), "2024-03-01"),
+            ("active-2", "In Progress", Some(2), "2024-02-01"),
+            ("active-none", "In Progress", None, "2024-04-01"),
+        ];
+        for (id, status, poam_id, updated) in plans {
+            let mut plan = test_plan(id, Vec::new());
+            plan.status = status.to_string();
+            plan.poam_id = poam_id;
+            plan.updated_date = updated.to_string();
+            db.save_security_test_plan(&plan, "sys").unwrap();
+        }
+
+        let mut elsewhere = test_plan("other-plan", Vec::new());
+        elsewhere.status = "In Progress".to_string();
+        db.save_security_test_plan(&elsewhere, "other").unwrap();
+        db
+    }
+
+    fn ids(db: &Database, status: Option<&str>, poam_id: Option<i64>, stig_mapping_id: Option<&str>) -> Vec<String> {
+        db.get_security_test_plans_filtered("sys", status, poam_id, stig_mapping_id)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.id)
+            .collect()
+    }
+
+    #[test]
+    fn no_filters_returns_all_plans_newest_first() {
+        let db = seed();
+        assert_eq!(ids(&db, None, None, None), vec!["active-none", "active-1", "active-2", "draft-1"]);
+    }
+
+    #[test]
+    fn filters_by_status_alone() {
+        let db = seed();
+        assert_eq!(ids(&db, Some("In Progress"), None, None), vec!["active-none", "active-1", "active-2"]);
+        assert_eq!(ids(&db, Some("Draft"), None, None), vec!["draft-1"]);
+    }
+
+    #[test]
+    fn filters_by_poam_alone() {
+        let db = seed();
+        assert_eq!(ids(&db, None, Some(1), None), vec!["active-1", "draft-1"]);
+    }
+
+    #[test]
+    fn combines_filters() {
+        let db = seed();
+        assert_eq!(ids(&db, Some("In Progress"), Some(1), None), vec!["active-1"]);
+        assert!(ids(&db, Some("Draft"), Some(2), None).is_empty());
+        assert!(ids(&db, Some("In Progress"), None, Some("missing-mapping")).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use rusqlite::{params, Connection};
+use serde::{de::DeserializeOwned, Serialize};
+use super::utils::DatabaseError;
+
+// Key under which the backend remembers the currently selected system
+pub const ACTIVE_SYSTEM_KEY: &str = "active_system_id";
+
+// Settings scoped to a system are stored under a composite "system:<id>:<key>" key
+pub fn scoped_key(key: &str, system_id: Option<&str>) -> String {
+    match system_id {
+        Some(id) => format!("system:{}:{}", id, key),
+        None => key.to_string(),
+    }
+}
+
+pub struct SettingsOperations<'a> {
+    conn: &'a mut Connection,
+}
+
+pub struct SettingsQueries<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SettingsOperations<'a> {
+    pub fn new(conn: &'a mut Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn set_setting(&mut self, key: &str, value: &str) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "INSERT INTO app_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_setting_as<T: Serialize>(&mut self, key: &str, value: &T) -> Result<(), DatabaseError> {
+        let json = serde_json::to_string(value)?;
+        self.set_setting(key, &json)
+    }
+
+    pub fn delete_setting(&mut self, key: &str) -> Result<(), DatabaseError> {
+        self.conn.execute("DELETE FROM app_settings WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+}
+
+impl<'a> SettingsQueries<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn get_setting(&self, key: &str) -> Result<Option<String>, DatabaseError> {
+        let value = self.conn.query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![key],
+            |row| row.get::<_, String>(0),
+        );
+
+        match value {
+            Ok(v) => Ok(Some(v)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(DatabaseError::Sqlite(e)),
+        }
+    }
+
+    pub fn get_setting_as<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, DatabaseError> {
+        match self.get_setting(key)? {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    // Stored selection if it still exists, otherwise the most recently accessed system, otherwise "default"
+    pub fn get_active_system_id(&self) -> Result<String, DatabaseError> {
+        if let Some(stored) = self.get_setting(ACTIVE_SYSTEM_KEY)? {
+            let exists = self.conn.query_row(
+                "SELECT COUNT(*) FROM systems WHERE id = ?1 AND is_active = 1",
+                params![stored],
+                |row| row.get::<_, i64>(0),
+            )? > 0;
+
+            if exists {
+                return Ok(stored);
+            }
+            println!("Stored active system {} no longer exists, falling back", stored);
+        }
+
+        let most_recent = self.conn.query_row(
+            "SELECT id FROM systems WHERE is_active = 1 AND last_accessed IS NOT NULL
+             ORDER BY last_accessed DESC LIMIT 1",
+            params![],
+            |row| row.get::<_, String>(0),
+        );
+
+        match most_recent {
+            Ok(id) => Ok(id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok("default".to_string()),
+            Err(e) => Err(DatabaseError::Sqlite(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scoped_key, ACTIVE_SYSTEM_KEY};
+    use crate::database::test_support::{memory_db, system};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct RiskWeights {
+        critical: f64,
+        high: f64,
+        labels: Vec<String>,
+    }
+
+    #[test]
+    fn set_get_and_overwrite() {
+        let mut db = memory_db();
+        db.set_setting("auto_lock_minutes", "15", None).unwrap();
+        assert_eq!(db.get_setting("auto_lock_minutes", None).unwrap().as_deref(), Some("15"));
+
+        db.set_setting("auto_lock_minutes", "30", None).unwrap();
+        assert_eq!(db.get_setting("auto_lock_minutes", None).unwrap().as_deref(), Some("30"));
+    }
+
+    #[test]
+    fn missing_key_is_none() {
+        let db = memory_db();
+        assert_eq!(db.get_setting("never_set", None).unwrap(), None);
+        assert_eq!(db.get_setting_as::<RiskWeights>("never_set", None).unwrap(), None);
+    }
+
+    #[test]
+    fn typed_struct_round_trips() {
+        let mut db = memory_db();
+        let weights = RiskWeights { critical: 10.0, high: 5.5, labels: vec!["CAT I".to_string()] };
+        db.set_setting_as("risk_weights", &weights, None).unwrap();
+        assert_eq!(db.get_setting_as::<RiskWeights>("risk_weights", None).unwrap(), Some(weights));
+    }
+
+    #[test]
+    fn system_scoped_settings_are_independent() {
+        let mut db = memory_db();
+        db.set_setting("default_cci_path", "/global.xml", None).unwrap();
+        db.set_setting("default_cci_path", "/a.xml", Some("a")).unwrap();
+
+        assert_eq!(scoped_key("default_cci_path", Some("a")), "system:a:default_cci_path");
+        assert_eq!(db.get_setting("default_cci_path", Some("a")).unwrap().as_deref(), Some("/a.xml"));
+        assert_eq!(db.get_setting("default_cci_path", Some("b")).unwrap(), None);
+        assert_eq!(db.get_setting("default_cci_path", None).unwrap().as_deref(), Some("/global.xml"));
+
+        db.delete_setting("default_cci_path", Some("a")).unwrap();
+        assert_eq!(db.get_setting("default_cci_path", Some("a")).unwrap(), None);
+        assert_eq!(db.get_setting("default_cci_path", None).unwrap().as_deref(), Some("/global.xml"));
+    }
+
+    #[test]
+    fn active_system_round_trips() {
+        let mut db = memory_db();
+        db.create_system(&system("a")).unwrap();
+        db.create_system(&system("b")).unwrap();
+
+        db.set_setting(ACTIVE_SYSTEM_KEY, "b", None).unwrap();
+        assert_eq!(db.get_active_system_id().unwrap(), "b");
+
+        db.set_setting(ACTIVE_SYSTEM_KEY, "a", None).unwrap();
+        assert_eq!(db.get_active_system_id().unwrap(), "a");
+    }
+
+    #[test]
+    fn deleted_active_system_falls_back_to_most_recently_accessed() {
+        let mut db = memory_db();
+        for (id, accessed) in [("older", "2024-01-01T00:00:00Z"), ("recent", "2024-05-01T00:00:00Z"), ("gone", "2024-06-01T00:00:00Z")] {
+            db.create_system(&crate::models::System { last_accessed: Some(accessed.to_string()), ..system(id) }).unwrap();
+        }
+        db.set_setting(ACTIVE_SYSTEM_KEY, "gone", None).unwrap();
+        db.delete_system("gone").unwrap();
+
+        assert_eq!(db.get_active_system_id().unwrap(), "recent");
+    }
+
+    #[test]
+    fn falls_back_to_default_without_any_accessed_system() {
+        let mut db = memory_db();
+        assert_eq!(db.get_active_system_id().unwrap(), "default");
+
+        db.create_system(&system("never-opened")).unwrap();
+        db.set_setting(ACTIVE_SYSTEM_KEY, "missing", None).unwrap();
+        assert_eq!(db.get_active_system_id().unwrap(), "default");
+    }
+}
@@ -3,10 +3,60 @@ use std::fs;
 use tauri::{AppHandle, Manager};
 use super::utils::DatabaseError;
 
+// Remediation hints shared between database setup errors and the diagnostics report
+pub(crate) const APP_DIR_REMEDIATION: &str = "This may occur in restricted environments. \
+    Ensure the application has permission to access user data directories.";
+
+pub(crate) const DATA_DIR_REMEDIATION: &str = "This may be due to: \
+    1) Insufficient user permissions \
+    2) Group Policy restrictions \
+    3) Antivirus blocking file creation \
+    4) Disk space limitations. \
+    Please check with your system administrator if this error persists.";
+
+pub(crate) const DATABASE_OPEN_REMEDIATION: &str = "This may be due to: \
+    1) File permissions restrictions \
+    2) Antivirus blocking database access \
+    3) Group Policy preventing SQLite operations \
+    4) Corrupted database file. \
+    Try running the application as administrator or contact your IT support team.";
+
 pub struct DatabaseSetup<'a> {
     conn: &'a mut Connection,
 }
 
+type Migration = fn(&mut DatabaseSetup<'_>) -> Result<(), DatabaseError>;
+
+// Ordered schema migrations. Each step runs once, after which PRAGMA user_version is set
+// to its number. Append new steps to the end and never renumber existing ones. Steps
+// stay idempotent because databases created before versioning start at user_version 0.
+const MIGRATIONS: &[(i32, &str, Migration)] = &[
+    (1, "stig_mappings cci_mappings column", |setup| setup.migrate_stig_mapping_cci_column()),
+    (2, "poam enhanced fields", |setup| setup.migrate_poam_enhanced_fields()),
+    (3, "system_id columns", |setup| setup.migrate_to_system_schema()),
+    (4, "note POAM associations", |setup| setup.migrate_notes_schema()),
+    (5, "systems group_id column", |setup| setup.migrate_groups_schema()),
+    (6, "nessus prep list fields", |setup| setup.migrate_nessus_prep_lists_schema()),
+    (7, "group CCI mappings table", |setup| setup.create_cci_mappings_table()),
+    (8, "test case templates table", |setup| setup.create_test_case_templates_table()),
+    (9, "app settings table", |setup| setup.create_app_settings_table()),
+    (10, "nessus finding dispositions table", |setup| setup.create_nessus_finding_dispositions_table()),
+    (11, "POAM references table", |setup| setup.create_poam_references_table()),
+    (12, "STIG vulnerability index", |setup| setup.create_stig_vulnerabilities_index()),
+    (13, "baseline controls keyed per system", |setup| setup.migrate_baseline_controls_primary_key()),
+    (14, "posture history table", |setup| setup.create_posture_history_table()),
+    (15, "POAM responsible party and field history", |setup| setup.create_poam_field_history()),
+    (16, "milestone dependencies column", |setup| setup.migrate_milestone_dependencies()),
+    (17, "group baseline controls keyed per group", |setup| setup.migrate_group_baseline_controls_primary_key()),
+];
+
+/// Schema version written by the newest migration this build knows about
+pub const SCHEMA_VERSION: i32 = MIGRATIONS[MIGRATIONS.len() - 1].0;
+
+pub fn schema_version(conn: &Connection) -> Result<i32, DatabaseError> {
+    Ok(conn.query_row("PRAGMA user_version", params![], |row| row.get(0))?)
+}
+
 impl<'a> DatabaseSetup<'a> {
     pub fn new(conn: &'a mut Connection) -> Self {
         Self { conn }
@@ -17,20 +67,15 @@ impl<'a> DatabaseSetup<'a> {
         let app_dir = app_handle
             .path()
             .app_data_dir()
-            .map_err(|e| DatabaseError::AppDir(format!("Failed to get app data directory: {}. This may occur in restricted environments. Ensure the application has permission to access user data directories.", e)))?;
+            .map_err(|e| DatabaseError::AppDir(format!("Failed to get app data directory: {}. {}", e, APP_DIR_REMEDIATION)))?;
         
         // Create the directory if it doesn't exist
         fs::create_dir_all(&app_dir).map_err(|e| {
             let detailed_error = format!(
-                "Failed to create data directory at '{}': {}. \
-                This may be due to: \
-                1) Insufficient user permissions \
-                2) Group Policy restrictions \
-                3) Antivirus blocking file creation \
-                4) Disk space limitations. \
-                Please check with your system administrator if this error persists.",
+                "Failed to create data directory at '{}': {}. {}",
                 app_dir.display(),
-                e
+                e,
+                DATA_DIR_REMEDIATION
             );
             DatabaseError::AppDir(detailed_error)
         })?;
@@ -41,15 +86,10 @@ impl<'a> DatabaseSetup<'a> {
         
         let conn = Connection::open(&db_path).map_err(|e| {
             let detailed_error = format!(
-                "Failed to open database at '{}': {}. \
-                This may be due to: \
-                1) File permissions restrictions \
-                2) Antivirus blocking database access \
-                3) Group Policy preventing SQLite operations \
-                4) Corrupted database file. \
-                Try running the application as administrator or contact your IT support team.",
+                "Failed to open database at '{}': {}. {}",
                 db_path.display(),
-                e
+                e,
+                DATABASE_OPEN_REMEDIATION
             );
             DatabaseError::AppDir(detailed_error)
         })?;
@@ -58,6 +98,12 @@ impl<'a> DatabaseSetup<'a> {
     }
 
     pub fn initialize_tables(&mut self) -> Result<(), DatabaseError> {
+        // Refuse databases written by a newer build before touching anything
+        let current_version = schema_version(self.conn)?;
+        if current_version > SCHEMA_VERSION {
+            return Err(DatabaseError::SchemaTooNew { found: current_version, supported: SCHEMA_VERSION });
+        }
+
         // Create systems table first
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS systems (
@@ -147,21 +193,6 @@ impl<'a> DatabaseSetup<'a> {
             params![],
         )?;
         
-        // Check if we need to add cci_mappings column to existing table
-        let has_cci_mappings_column = self.conn.query_row(
-            "SELECT COUNT(*) FROM pragma_table_info('stig_mappings') WHERE name = 'cci_mappings'",
-            params![],
-            |row| row.get::<_, i64>(0)
-        ).unwrap_or(0) > 0;
-        
-        if !has_cci_mappings_column {
-            println!("Adding cci_mappings column to stig_mappings table");
-            self.conn.execute(
-                "ALTER TABLE stig_mappings ADD COLUMN cci_mappings TEXT",
-                params![],
-            )?;
-        }
-        
         // Security Test Plan tables
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS security_test_plans (
@@ -304,7 +335,7 @@ impl<'a> DatabaseSetup<'a> {
         // Create Baseline Controls table
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS baseline_controls (
-                id TEXT PRIMARY KEY,
+                id TEXT NOT NULL,
                 family TEXT NOT NULL,
                 title TEXT NOT NULL,
                 implementation_status TEXT NOT NULL,
@@ -312,6 +343,7 @@ impl<'a> DatabaseSetup<'a> {
                 responsible_party TEXT,
                 notes TEXT,
                 system_id TEXT NOT NULL DEFAULT 'default',
+                PRIMARY KEY (id, system_id),
                 FOREIGN KEY (system_id) REFERENCES systems (id) ON DELETE CASCADE
             )",
             params![],
@@ -320,7 +352,7 @@ impl<'a> DatabaseSetup<'a> {
         // Create Group Baseline Controls table (group-level NIST controls)
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS group_baseline_controls (
-                id TEXT PRIMARY KEY,
+                id TEXT NOT NULL,
                 family TEXT NOT NULL,
                 title TEXT NOT NULL,
                 implementation_status TEXT NOT NULL,
@@ -328,6 +360,7 @@ impl<'a> DatabaseSetup<'a> {
                 responsible_party TEXT,
                 notes TEXT,
                 group_id TEXT NOT NULL,
+                PRIMARY KEY (id, group_id),
                 FOREIGN KEY (group_id) REFERENCES system_groups (id) ON DELETE CASCADE
             )",
             params![],
@@ -422,6 +455,20 @@ impl<'a> DatabaseSetup<'a> {
             params![],
         )?;
         
+        // Links from group POAMs to the member-system POAMs they roll up
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS group_poam_system_links (
+                group_poam_id INTEGER NOT NULL,
+                system_id TEXT NOT NULL,
+                poam_id INTEGER NOT NULL,
+                linked_date TEXT NOT NULL,
+                PRIMARY KEY (group_poam_id, system_id, poam_id),
+                FOREIGN KEY (group_poam_id) REFERENCES group_poams (id) ON DELETE CASCADE,
+                FOREIGN KEY (poam_id) REFERENCES poams (id) ON DELETE CASCADE
+            )",
+            params![],
+        )?;
+        
         // Create Group Security Test Plans table
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS group_security_test_plans (
@@ -443,17 +490,49 @@ impl<'a> DatabaseSetup<'a> {
         )?;
         
         // Run migrations
-        self.migrate_poam_enhanced_fields()?;
+        self.run_migrations(current_version)?;
         self.ensure_default_system()?;
-        self.migrate_to_system_schema()?;
-        self.migrate_notes_schema()?;
-        self.migrate_groups_schema()?;
-        self.migrate_nessus_prep_lists_schema()?;
-        self.create_cci_mappings_table()?;
         
         Ok(())
     }
 
+    fn run_migrations(&mut self, current_version: i32) -> Result<(), DatabaseError> {
+        for (version, name, migrate) in MIGRATIONS {
+            if *version <= current_version {
+                continue;
+            }
+
+            println!("Applying schema migration {}: {}", version, name);
+            migrate(self)?;
+            self.conn.execute_batch(&format!("PRAGMA user_version = {}", version))?;
+        }
+
+        if current_version < SCHEMA_VERSION {
+            println!("Database schema upgraded from version {} to {}", current_version, SCHEMA_VERSION);
+        }
+
+        Ok(())
+    }
+
+    fn migrate_stig_mapping_cci_column(&mut self) -> Result<(), DatabaseError> {
+        // Check if we need to add cci_mappings column to existing table
+        let has_cci_mappings_column = self.conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('stig_mappings') WHERE name = 'cci_mappings'",
+            params![],
+            |row| row.get::<_, i64>(0)
+        ).unwrap_or(0) > 0;
+        
+        if !has_cci_mappings_column {
+            println!("Adding cci_mappings column to stig_mappings table");
+            self.conn.execute(
+                "ALTER TABLE stig_mappings ADD COLUMN cci_mappings TEXT",
+                params![],
+            )?;
+        }
+
+        Ok(())
+    }
+
     fn migrate_poam_enhanced_fields(&mut self) -> Result<(), DatabaseError> {
         // List of enhanced fields to add
         let enhanced_fields = [
@@ -724,4 +803,437 @@ impl<'a> DatabaseSetup<'a> {
 
         Ok(())
     }
+
+    fn create_test_case_templates_table(&mut self) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS test_case_templates (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                nist_control TEXT NOT NULL,
+                cci_ref TEXT NOT NULL DEFAULT '',
+                test_description TEXT NOT NULL,
+                test_procedure TEXT NOT NULL,
+                expected_result TEXT NOT NULL,
+                risk_rating TEXT NOT NULL,
+                system_id TEXT, -- NULL for templates shared by every system
+                created_date TEXT NOT NULL,
+                updated_date TEXT NOT NULL,
+                FOREIGN KEY (system_id) REFERENCES systems (id) ON DELETE CASCADE
+            )",
+            params![],
+        )?;
+
+        let existing = self.conn.query_row(
+            "SELECT COUNT(*) FROM test_case_templates",
+            params![],
+            |row| row.get::<_, i64>(0)
+        ).unwrap_or(0);
+
+        if existing == 0 {
+            self.seed_default_test_case_templates()?;
+        }
+
+        Ok(())
+    }
+
+    fn seed_default_test_case_templates(&mut self) -> Result<(), DatabaseError> {
+        // (name, control, CCI, description, procedure, expected result, risk rating)
+        let defaults = [
+            (
+                "Account management review",
+                "AC-2",
+                "CCI-000015",
+                "Verify accounts are managed through an approved process.",
+                "Export the list of active accounts and compare it against the approved access roster. Confirm disabled and terminated users have no active accounts.",
+                "Every active account maps to an approved, current user.",
+                "Medium",
+            ),
+            (
+                "Unsuccessful logon lockout",
+                "AC-7",
+                "CCI-000044",
+                "Verify the system locks accounts after consecutive failed logon attempts.",
+                "Review the account lockout policy, then attempt the configured number of invalid logons with a test account.",
+                "The account is locked after the configured threshold of failed attempts.",
+                "Medium",
+            ),
+            (
+                "Auditable events configuration",
+                "AU-2",
+                "CCI-000123",
+                "Verify the system is configured to audit the required event types.",
+                "Review the audit policy configuration and generate sample logon, privilege use and object access events.",
+                "All required event types are captured in the audit log.",
+                "Medium",
+            ),
+            (
+                "Multifactor authentication",
+                "IA-2",
+                "CCI-000765",
+                "Verify privileged and network access requires multifactor authentication.",
+                "Attempt interactive and remote logon with a privileged account using only a password.",
+                "Logon is denied unless a second authentication factor is presented.",
+                "High",
+            ),
+            (
+                "Secure configuration baseline",
+                "CM-6",
+                "CCI-000366",
+                "Verify the system is configured according to the applicable STIG or benchmark.",
+                "Run an SCAP or STIG checklist scan against the system and review any open findings.",
+                "No open CAT I findings and all CAT II/III findings are documented.",
+                "High",
+            ),
+            (
+                "Transmission confidentiality",
+                "SC-8",
+                "CCI-002418",
+                "Verify data in transit is protected with approved cryptography.",
+                "Inspect service and protocol configurations for TLS versions and cipher suites in use.",
+                "Only approved protocols (TLS 1.2 or higher) and FIPS-validated ciphers are enabled.",
+                "High",
+            ),
+        ];
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = self.conn.transaction()?;
+        for (name, control, cci, description, procedure, expected, risk) in defaults {
+            tx.execute(
+                "INSERT INTO test_case_templates
+                 (id, name, nist_control, cci_ref, test_description, test_procedure, expected_result, risk_rating, system_id, created_date, updated_date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, NULL, ?9, ?9)",
+                params![uuid::Uuid::new_v4().to_string(), name, control, cci, description, procedure, expected, risk, now],
+            )?;
+        }
+        tx.commit()?;
+
+        println!("Seeded {} default test case templates", defaults.len());
+        Ok(())
+    }
+
+    fn create_app_settings_table(&mut self) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS app_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT
+            )",
+            params![],
+        )?;
+
+        Ok(())
+    }
+
+    // Keyed on the finding dedup tuple rather than finding ids, which change on every import.
+    // Missing key parts are stored as '' / 0 so the unique index treats them as equal.
+    fn create_nessus_finding_dispositions_table(&mut self) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS nessus_finding_dispositions (
+                system_id TEXT NOT NULL,
+                plugin_id INTEGER NOT NULL DEFAULT 0,
+                host TEXT NOT NULL DEFAULT '',
+                port INTEGER NOT NULL DEFAULT 0,
+                protocol TEXT NOT NULL DEFAULT '',
+                disposition TEXT NOT NULL,
+                justification TEXT,
+                updated_date TEXT NOT NULL,
+                PRIMARY KEY (system_id, plugin_id, host, port, protocol),
+                FOREIGN KEY (system_id) REFERENCES systems (id) ON DELETE CASCADE
+            )",
+            params![],
+        )?;
+
+        Ok(())
+    }
+
+    fn create_poam_references_table(&mut self) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS poam_references (
+                id TEXT PRIMARY KEY,
+                poam_id INTEGER NOT NULL,
+                label TEXT NOT NULL,
+                url TEXT NOT NULL,
+                kind TEXT,
+                created_date TEXT NOT NULL,
+                system_id TEXT NOT NULL,
+                FOREIGN KEY (poam_id) REFERENCES poams (id) ON DELETE CASCADE,
+                FOREIGN KEY (system_id) REFERENCES systems (id) ON DELETE CASCADE
+            )",
+            params![],
+        )?;
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_poam_references_poam ON poam_references (system_id, poam_id)",
+            params![],
+        )?;
+
+        Ok(())
+    }
+
+    fn create_stig_vulnerabilities_index(&mut self) -> Result<(), DatabaseError> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS stig_vulnerabilities (
+                mapping_id TEXT NOT NULL,
+                system_id TEXT NOT NULL,
+                vuln_num TEXT NOT NULL,
+                nist_control TEXT NOT NULL,
+                rule_id TEXT,
+                rule_title TEXT,
+                group_title TEXT,
+                severity TEXT,
+                status TEXT,
+                PRIMARY KEY (mapping_id, vuln_num, nist_control),
+                FOREIGN KEY (mapping_id) REFERENCES stig_mappings (id) ON DELETE CASCADE
+            )",
+            params![],
+        )?;
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_stig_vulnerabilities_system_control ON stig_vulnerabilities (system_id, nist_control)",
+            params![],
+        )?;
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_stig_vulnerabilities_system_vuln ON stig_vulnerabilities (system_id, vuln_num)",
+            params![],
+        )?;
+
+        // Backfill from mappings saved before the index existed
+        let existing: Vec<(String, String, String)> = {
+            let mut stmt = tx.prepare("SELECT id, system_id, mapping_result FROM stig_mappings")?;
+            let rows = stmt.query_map(params![], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        for (mapping_id, system_id, mapping_result) in existing {
+            match serde_json::from_str::<crate::models::STIGMappingResult>(&mapping_result) {
+                Ok(result) => super::stig_mappings::write_vulnerability_index(&tx, &mapping_id, &result, &system_id)?,
+                Err(e) => println!("Skipping vulnerability index for STIG mapping {}: {}", mapping_id, e),
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    // Baseline controls used to be keyed on the control id alone, so the same control
+    // could only exist in one system. Rebuild the table keyed on (id, system_id).
+    fn migrate_baseline_controls_primary_key(&mut self) -> Result<(), DatabaseError> {
+        let key_columns: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('baseline_controls') WHERE pk > 0",
+            params![],
+            |row| row.get(0),
+        )?;
+        if key_columns > 1 {
+            return Ok(());
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute_batch(
+            "CREATE TABLE baseline_controls_rekeyed (
+                id TEXT NOT NULL,
+                family TEXT NOT NULL,
+                title TEXT NOT NULL,
+                implementation_status TEXT NOT NULL,
+                date_added TEXT NOT NULL,
+                responsible_party TEXT,
+                notes TEXT,
+                system_id TEXT NOT NULL DEFAULT 'default',
+                PRIMARY KEY (id, system_id),
+                FOREIGN KEY (system_id) REFERENCES systems (id) ON DELETE CASCADE
+            );
+            INSERT INTO baseline_controls_rekeyed (id, family, title, implementation_status, date_added, responsible_party, notes, system_id)
+                SELECT id, family, title, implementation_status, date_added, responsible_party, notes, system_id FROM baseline_controls;
+            DROP TABLE baseline_controls;
+            ALTER TABLE baseline_controls_rekeyed RENAME TO baseline_controls;",
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn create_posture_history_table(&mut self) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS posture_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                system_id TEXT NOT NULL,
+                score REAL NOT NULL,
+                components TEXT NOT NULL,
+                computed_at TEXT NOT NULL,
+                FOREIGN KEY (system_id) REFERENCES systems (id) ON DELETE CASCADE
+            )",
+            params![],
+        )?;
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_posture_history_system ON posture_history (system_id, computed_at)",
+            params![],
+        )?;
+
+        Ok(())
+    }
+
+    fn create_poam_field_history(&mut self) -> Result<(), DatabaseError> {
+        let has_responsible_party = self.conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('poams') WHERE name = 'responsible_party'",
+            params![],
+            |row| row.get::<_, i64>(0)
+        )? > 0;
+        if !has_responsible_party {
+            self.conn.execute("ALTER TABLE poams ADD COLUMN responsible_party TEXT", params![])?;
+        }
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS poam_field_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                poam_id INTEGER NOT NULL,
+                system_id TEXT NOT NULL,
+                field TEXT NOT NULL,
+                old_value TEXT,
+                new_value TEXT,
+                changed_at TEXT NOT NULL,
+                FOREIGN KEY (poam_id) REFERENCES poams (id) ON DELETE CASCADE
+            )",
+            params![],
+        )?;
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_poam_field_history_poam ON poam_field_history (system_id, poam_id, changed_at)",
+            params![],
+        )?;
+
+        Ok(())
+    }
+
+    // JSON array of the milestone ids a milestone waits on; NULL when it has none
+    fn migrate_milestone_dependencies(&mut self) -> Result<(), DatabaseError> {
+        let has_depends_on = self.conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('milestones') WHERE name = 'depends_on'",
+            params![],
+            |row| row.get::<_, i64>(0)
+        )? > 0;
+        if !has_depends_on {
+            self.conn.execute("ALTER TABLE milestones ADD COLUMN depends_on TEXT", params![])?;
+        }
+        Ok(())
+    }
+
+    // Same fix as for baseline_controls: the group table was keyed on the control id alone,
+    // so a control could only belong to one group. Rebuild it keyed on (id, group_id).
+    fn migrate_group_baseline_controls_primary_key(&mut self) -> Result<(), DatabaseError> {
+        let key_columns: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('group_baseline_controls') WHERE pk > 0",
+            params![],
+            |row| row.get(0),
+        )?;
+        if key_columns > 1 {
+            return Ok(());
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute_batch(
+            "CREATE TABLE group_baseline_controls_rekeyed (
+                id TEXT NOT NULL,
+                family TEXT NOT NULL,
+                title TEXT NOT NULL,
+                implementation_status TEXT NOT NULL,
+                date_added TEXT NOT NULL,
+                responsible_party TEXT,
+                notes TEXT,
+                group_id TEXT NOT NULL,
+                PRIMARY KEY (id, group_id),
+                FOREIGN KEY (group_id) REFERENCES system_groups (id) ON DELETE CASCADE
+            );
+            INSERT INTO group_baseline_controls_rekeyed (id, family, title, implementation_status, date_added, responsible_party, notes, group_id)
+                SELECT id, family, title, implementation_status, date_added, responsible_party, notes, group_id FROM group_baseline_controls;
+            DROP TABLE group_baseline_controls;
+            ALTER TABLE group_baseline_controls_rekeyed RENAME TO group_baseline_controls;",
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{schema_version, DatabaseSetup, SCHEMA_VERSION};
+    use crate::database::test_support::{group, memory_db, poam, stig_mapping, stig_vuln, system};
+    use crate::database::DatabaseError;
+
+    fn schema(conn: &rusqlite::Connection) -> Vec<String> {
+        let mut stmt = conn.prepare("SELECT sql FROM sqlite_master WHERE sql IS NOT NULL ORDER BY name").unwrap();
+        stmt.query_map([], |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn new_database_is_at_current_version_and_reinitializing_is_a_no_op() {
+        let mut db = memory_db();
+        assert_eq!(db.get_schema_version().unwrap(), SCHEMA_VERSION);
+        let before = schema(&db.conn);
+
+        DatabaseSetup::new(&mut db.conn).initialize_tables().unwrap();
+        assert_eq!(schema_version(&db.conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(schema(&db.conn), before);
+    }
+
+    #[test]
+    fn migrations_are_idempotent_when_replayed_over_an_unversioned_database() {
+        // Databases created before versioning already have some columns but report version 0
+        let mut db = memory_db();
+        db.create_system(&system("sys-1")).unwrap();
+        db.create_poam(&poam(1), "sys-1").unwrap();
+        let before = schema(&db.conn);
+
+        db.conn.execute_batch("PRAGMA user_version = 0").unwrap();
+        DatabaseSetup::new(&mut db.conn).initialize_tables().unwrap();
+
+        assert_eq!(schema_version(&db.conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(schema(&db.conn), before);
+        assert_eq!(db.get_all_poams("sys-1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn database_from_a_newer_build_is_refused() {
+        let mut db = memory_db();
+        db.conn.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION + 1)).unwrap();
+
+        let result = DatabaseSetup::new(&mut db.conn).initialize_tables();
+        assert!(matches!(
+            result,
+            Err(DatabaseError::SchemaTooNew { found, supported }) if found == SCHEMA_VERSION + 1 && supported == SCHEMA_VERSION
+        ));
+    }
+
+    #[test]
+    fn vulnerability_index_migration_backfills_existing_mappings() {
+        let mut db = memory_db();
+        db.create_system(&system("sys-1")).unwrap();
+        db.save_stig_mapping(&stig_mapping("m1", vec![("AC-2", vec![stig_vuln("V-1001", "Open", &[]), stig_vuln("V-1002", "NotAFinding", &[])])]), "sys-1").unwrap();
+
+        // A version 11 database has mappings but no index table
+        db.conn.execute_batch("DROP TABLE stig_vulnerabilities; PRAGMA user_version = 11").unwrap();
+        DatabaseSetup::new(&mut db.conn).initialize_tables().unwrap();
+
+        let rows: Vec<(String, String)> = {
+            let mut stmt = db.conn.prepare("SELECT vuln_num, status FROM stig_vulnerabilities WHERE mapping_id = 'm1' ORDER BY vuln_num").unwrap();
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().map(|r| r.unwrap()).collect()
+        };
+        assert_eq!(rows, [("V-1001".to_string(), "Open".to_string()), ("V-1002".to_string(), "NotAFinding".to_string())]);
+    }
+
+    #[test]
+    fn group_baseline_controls_migration_keys_controls_per_group() {
+        let mut db = memory_db();
+        db.create_group(&group("g-1")).unwrap();
+        db.create_group(&group("g-2")).unwrap();
+
+        // A version 16 database keys group baseline controls on the control id alone
+        db.conn.execute_batch(
+            "DROP TABLE group_baseline_controls;
+             CREATE TABLE group_baseline_controls (id TEXT PRIMARY KEY, family TEXT NOT NULL, title TEXT NOT NULL,
+                 implementation_status TEXT NOT NULL, date_added TEXT NOT NULL, responsible_party TEXT, notes TEXT, group_id TEXT NOT NULL);
+             INSERT INTO group_baseline_controls VALUES ('AC-2', 'AC', 'Account Management', 'Implemented', '2024-01-01', NULL, NULL, 'g-1');
+             PRAGMA user_version = 16",
+        ).unwrap();
+        DatabaseSetup::new(&mut db.conn).initialize_tables().unwrap();
+
+        let mut control = db.get_group_baseline_controls("g-1").unwrap().remove(0);
+        control.group_id = "g-2".to_string();
+        db.add_group_baseline_control(&control).unwrap();
+        assert_eq!(db.get_group_baseline_controls("g-1").unwrap().len(), 1);
+        assert_eq!(db.get_group_baseline_controls("g-2").unwrap().len(), 1);
+    }
 }
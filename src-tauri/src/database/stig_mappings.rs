@@ -1,4 +1,5 @@
-use crate::models::STIGMappingData;
+use crate::models::{STIGMappingData, STIGMappingResult, StigVulnerabilityMatch};
+use std::collections::HashMap;
 use rusqlite::{params, Connection};
 use serde_json;
 use super::utils::DatabaseError;
@@ -23,7 +24,8 @@ impl<'a> STIGMappingOperations<'a> {
         let cci_mappings_json = mapping.cci_mappings.as_ref()
             .map(|mappings| serde_json::to_string(mappings).unwrap());
         
-        self.conn.execute(
+        let tx = self.conn.transaction()?;
+        tx.execute(
             "INSERT OR REPLACE INTO stig_mappings 
              (id, name, description, created_date, updated_date, stig_info, asset_info, mapping_result, cci_mappings, system_id)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
@@ -40,20 +42,47 @@ impl<'a> STIGMappingOperations<'a> {
                 system_id
             ],
         )?;
+        write_vulnerability_index(&tx, &mapping.id, &mapping.mapping_result, system_id)?;
+        tx.commit()?;
         
         Ok(())
     }
 
+    // Updates only the lightweight columns so large JSON blobs are not rewritten
+    pub fn update_stig_mapping_metadata(&mut self, id: &str, name: &str, description: Option<&str>, system_id: &str) -> Result<(), DatabaseError> {
+        let updated_date = chrono::Utc::now().to_rfc3339();
+        let updated = self.conn.execute(
+            "UPDATE stig_mappings SET name = ?1, description = ?2, updated_date = ?3
+             WHERE id = ?4 AND system_id = ?5",
+            params![name, description, updated_date, id, system_id],
+        )?;
+
+        if updated == 0 {
+            return Err(DatabaseError::NotFound(format!("STIG mapping {} not found", id)));
+        }
+        Ok(())
+    }
+
     pub fn delete_stig_mapping(&mut self, id: &str, system_id: &str) -> Result<(), DatabaseError> {
-        self.conn.execute(
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM stig_vulnerabilities WHERE mapping_id = ?1 AND system_id = ?2",
+            params![id, system_id],
+        )?;
+        tx.execute(
             "DELETE FROM stig_mappings WHERE id = ?1 AND system_id = ?2",
             params![id, system_id],
         )?;
+        tx.commit()?;
         Ok(())
     }
 
     pub fn clear_stig_mappings_for_system(&mut self, system_id: &str) -> Result<(), DatabaseError> {
         // Remove all STIG mappings for a specific system
+        self.conn.execute(
+            "DELETE FROM stig_vulnerabilities WHERE system_id = ?1",
+            params![system_id],
+        )?;
         self.conn.execute(
             "DELETE FROM stig_mappings WHERE system_id = ?1",
             params![system_id],
@@ -106,6 +135,62 @@ impl<'a> STIGMappingQueries<'a> {
         Ok(mappings)
     }
 
+    // Case-insensitive substring match on vuln number, rule id, rule title or group title.
+    // Matching runs against the stig_vulnerabilities index; details come from the matched
+    // mappings' JSON. A vulnerability appears once per mapping with all of its controls.
+    pub fn search_stig_vulnerabilities(&self, system_id: &str, query: &str) -> Result<Vec<StigVulnerabilityMatch>, DatabaseError> {
+        let needle = query.trim().to_lowercase();
+        if needle.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pattern = format!("%{}%", needle.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+
+        let mut stmt = self.conn.prepare(
+            "SELECT v.mapping_id, m.name, v.vuln_num, group_concat(v.nist_control, char(31))
+             FROM stig_vulnerabilities v
+             JOIN stig_mappings m ON m.id = v.mapping_id AND m.system_id = v.system_id
+             WHERE v.system_id = ?1
+               AND (lower(v.vuln_num) LIKE ?2 ESCAPE '\\' OR lower(v.rule_id) LIKE ?2 ESCAPE '\\'
+                    OR lower(v.rule_title) LIKE ?2 ESCAPE '\\' OR lower(v.group_title) LIKE ?2 ESCAPE '\\')
+             GROUP BY v.mapping_id, v.vuln_num
+             ORDER BY m.updated_date DESC, v.vuln_num"
+        )?;
+        let rows = stmt.query_map(params![system_id, pattern], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        let mut hits = Vec::new();
+        for r in rows { hits.push(r?); }
+
+        let mut mappings: HashMap<String, Option<STIGMappingData>> = HashMap::new();
+        let mut matches = Vec::new();
+        for (mapping_id, mapping_name, vuln_num, controls) in hits {
+            if !mappings.contains_key(&mapping_id) {
+                let mapping = self.get_stig_mapping_by_id(&mapping_id, system_id)?;
+                mappings.insert(mapping_id.clone(), mapping);
+            }
+            let vulnerability = mappings[&mapping_id].as_ref().and_then(|mapping| {
+                mapping.mapping_result.mapped_controls.iter()
+                    .flat_map(|control| control.stigs.iter())
+                    .find(|vuln| vuln.vuln_num == vuln_num)
+                    .cloned()
+            });
+            if let Some(vulnerability) = vulnerability {
+                matches.push(StigVulnerabilityMatch {
+                    mapping_id,
+                    mapping_name,
+                    nist_controls: controls.split('\u{1f}').map(str::to_string).collect(),
+                    vulnerability,
+                });
+            }
+        }
+        Ok(matches)
+    }
+
     pub fn get_stig_mapping_by_id(&self, id: &str, system_id: &str) -> Result<Option<STIGMappingData>, DatabaseError> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, description, created_date, updated_date, stig_info, asset_info, mapping_result, cci_mappings
@@ -144,3 +229,146 @@ impl<'a> STIGMappingQueries<'a> {
         }
     }
 }
+
+// stig_vulnerabilities is a derived index over mapping_result; the JSON stays authoritative.
+// Rows for the mapping are replaced wholesale so the index never drifts from a re-save.
+pub(crate) fn write_vulnerability_index(conn: &Connection, mapping_id: &str, result: &STIGMappingResult, system_id: &str) -> Result<(), DatabaseError> {
+    conn.execute("DELETE FROM stig_vulnerabilities WHERE mapping_id = ?1", params![mapping_id])?;
+
+    let mut stmt = conn.prepare(
+        "INSERT OR IGNORE INTO stig_vulnerabilities
+         (mapping_id, system_id, vuln_num, nist_control, rule_id, rule_title, group_title, severity, status)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+    )?;
+    for control in &result.mapped_controls {
+        for vuln in &control.stigs {
+            stmt.execute(params![
+                mapping_id,
+                system_id,
+                vuln.vuln_num,
+                control.nist_control,
+                vuln.rule_id,
+                vuln.rule_title,
+                vuln.group_title,
+                vuln.severity,
+                vuln.status
+            ])?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::{db_with_systems, stig_mapping, stig_vuln};
+    use crate::database::DatabaseError;
+
+    fn stored_blob(db: &crate::database::Database, id: &str) -> String {
+        db.conn
+            .query_row("SELECT mapping_result FROM stig_mappings WHERE id = ?1", [id], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn indexed(db: &crate::database::Database, system_id: &str) -> Vec<(String, String, String, String)> {
+        let mut stmt = db
+            .conn
+            .prepare("SELECT mapping_id, vuln_num, nist_control, status FROM stig_vulnerabilities WHERE system_id = ?1 ORDER BY mapping_id, vuln_num, nist_control")
+            .unwrap();
+        stmt.query_map([system_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect()
+    }
+
+    fn row(mapping_id: &str, vuln_num: &str, control: &str, status: &str) -> (String, String, String, String) {
+        (mapping_id.to_string(), vuln_num.to_string(), control.to_string(), status.to_string())
+    }
+
+    #[test]
+    fn metadata_update_leaves_mapping_result_untouched() {
+        let mut db = db_with_systems(&["sys"]);
+        let mapping = stig_mapping("m1", vec![("AC-2", vec![stig_vuln("V-1001", "Open", &["CCI-000015"])])]);
+        db.save_stig_mapping(&mapping, "sys").unwrap();
+        let before = stored_blob(&db, "m1");
+
+        db.update_stig_mapping_metadata("m1", "Renamed checklist", Some("Quarterly scan"), "sys").unwrap();
+
+        let updated = db.get_stig_mapping_by_id("m1", "sys").unwrap().unwrap();
+        assert_eq!(updated.name, "Renamed checklist");
+        assert_eq!(updated.description.as_deref(), Some("Quarterly scan"));
+        assert_ne!(updated.updated_date, mapping.updated_date);
+        assert_eq!(stored_blob(&db, "m1"), before);
+    }
+
+    #[test]
+    fn metadata_update_is_scoped_to_the_system() {
+        let mut db = db_with_systems(&["sys", "other"]);
+        db.save_stig_mapping(&stig_mapping("m1", Vec::new()), "sys").unwrap();
+
+        let result = db.update_stig_mapping_metadata("m1", "Renamed", None, "other");
+        assert!(matches!(result, Err(DatabaseError::NotFound(_))));
+        assert_eq!(db.get_stig_mapping_by_id("m1", "sys").unwrap().unwrap().name, "Mapping m1");
+    }
+
+    #[test]
+    fn search_matches_ids_and_titles_and_annotates_mapping_and_controls() {
+        let mut db = db_with_systems(&["sys", "other"]);
+        let mut password = stig_vuln("V-2001", "Open", &[]);
+        password.rule_title = "Passwords must be 15 characters".to_string();
+        password.group_title = "SRG-OS-000078".to_string();
+        db.save_stig_mapping(&stig_mapping("m1", vec![
+            ("IA-5", vec![password.clone(), stig_vuln("V-2002", "NotAFinding", &[])]),
+            ("IA-5(1)", vec![password.clone()]),
+        ]), "sys").unwrap();
+        db.save_stig_mapping(&stig_mapping("m2", vec![("AC-7", vec![stig_vuln("V-3001", "Open", &[])])]), "sys").unwrap();
+        db.save_stig_mapping(&stig_mapping("m3", vec![("IA-5", vec![password])]), "other").unwrap();
+
+        let by_title = db.search_stig_vulnerabilities("sys", "  PASSWORDS ").unwrap();
+        assert_eq!(by_title.len(), 1);
+        assert_eq!((by_title[0].mapping_id.as_str(), by_title[0].mapping_name.as_str()), ("m1", "Mapping m1"));
+        let mut controls = by_title[0].nist_controls.clone();
+        controls.sort();
+        assert_eq!(controls, ["IA-5", "IA-5(1)"]);
+        assert_eq!(by_title[0].vulnerability.vuln_num, "V-2001");
+
+        let found = |query: &str| -> Vec<String> {
+            let mut hits: Vec<String> = db.search_stig_vulnerabilities("sys", query).unwrap().into_iter().map(|m| m.vulnerability.vuln_num).collect();
+            hits.sort();
+            hits
+        };
+        assert_eq!(found("v-200"), ["V-2001", "V-2002"]);
+        assert_eq!(found("sv-3001r1"), ["V-3001"]);
+        assert_eq!(found("os-000078"), ["V-2001"]);
+        // LIKE wildcards in the query are matched literally
+        assert!(found("V_2001").is_empty());
+        assert!(found("%").is_empty());
+        assert!(found("   ").is_empty());
+    }
+
+    #[test]
+    fn vulnerability_index_follows_resaves_and_deletes() {
+        let mut db = db_with_systems(&["sys"]);
+        db.save_stig_mapping(&stig_mapping("m1", vec![
+            ("AC-2", vec![stig_vuln("V-1001", "Open", &[]), stig_vuln("V-1002", "Open", &[])]),
+            ("AU-2", vec![stig_vuln("V-1001", "Open", &[])]),
+        ]), "sys").unwrap();
+        db.save_stig_mapping(&stig_mapping("m2", vec![("CM-6", vec![stig_vuln("V-2001", "Open", &[])])]), "sys").unwrap();
+        assert_eq!(indexed(&db, "sys").len(), 4);
+
+        // Re-saving with a changed status and a dropped finding replaces the mapping's rows
+        db.save_stig_mapping(&stig_mapping("m1", vec![
+            ("AC-2", vec![stig_vuln("V-1001", "NotAFinding", &[])]),
+            ("AU-2", vec![stig_vuln("V-1001", "NotAFinding", &[])]),
+        ]), "sys").unwrap();
+        assert_eq!(indexed(&db, "sys"), vec![
+            row("m1", "V-1001", "AC-2", "NotAFinding"),
+            row("m1", "V-1001", "AU-2", "NotAFinding"),
+            row("m2", "V-2001", "CM-6", "Open"),
+        ]);
+
+        db.delete_stig_mapping("m2", "sys").unwrap();
+        assert_eq!(indexed(&db, "sys").len(), 2);
+        db.clear_stig_mappings_for_system("sys").unwrap();
+        assert!(indexed(&db, "sys").is_empty());
+    }
+}
@@ -1,6 +1,9 @@
-use crate::models::{System, SystemSummary};
+use crate::models::{normalize_severity, STIGMappingResult, System, SystemDataResetOptions, SystemSummary, TagCount};
+use std::collections::BTreeMap;
 use rusqlite::{params, Connection};
 use serde_json;
+use super::nessus::severity_value_rank;
+use super::stig_mappings::write_vulnerability_index;
 use super::utils::DatabaseError;
 
 pub struct SystemOperations<'a> {
@@ -48,55 +51,34 @@ impl<'a> SystemOperations<'a> {
         Ok(())
     }
 
-    pub fn get_all_systems(&self) -> Result<Vec<SystemSummary>, DatabaseError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT s.id, s.name, s.description, s.owner, s.classification, s.tags, s.created_date, s.last_accessed, s.group_id,
-                    COUNT(DISTINCT p.id) as poam_count,
-                    COUNT(DISTINCT n.id) as notes_count,
-                    COUNT(DISTINCT sm.id) as stig_mappings_count,
-                    COUNT(DISTINCT stp.id) as test_plans_count
-             FROM systems s
-             LEFT JOIN poams p ON s.id = p.system_id
-             LEFT JOIN notes n ON s.id = n.system_id
-             LEFT JOIN stig_mappings sm ON s.id = sm.system_id
-             LEFT JOIN security_test_plans stp ON s.id = stp.system_id
-             WHERE s.is_active = 1
-             GROUP BY s.id, s.name, s.description, s.owner, s.classification, s.tags, s.created_date, s.last_accessed, s.group_id
-             ORDER BY s.last_accessed DESC, s.created_date DESC"
-        )?;
-
-        let system_iter = stmt.query_map(params![], |row| {
-            let tags_str: Option<String> = row.get(5)?;
-            let tags = if let Some(json_str) = tags_str {
-                serde_json::from_str(&json_str).unwrap_or_default()
-            } else {
-                None
-            };
-
-            Ok(SystemSummary {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                description: row.get(2)?,
-                owner: row.get(3)?,
-                classification: row.get(4)?,
-                tags,
-                created_date: row.get(6)?,
-                last_accessed: row.get(7)?,
-                group_id: row.get(8)?,
-                poam_count: row.get(9).unwrap_or(0),
-                notes_count: row.get(10).unwrap_or(0),
-                stig_mappings_count: row.get(11).unwrap_or(0),
-                test_plans_count: row.get(12).unwrap_or(0),
-            })
-        })?;
-
-        let mut systems = Vec::new();
-        for system in system_iter {
-            systems.push(system?);
+    /// Inserts the systems in one transaction. Returns, per system, whether it was created;
+    /// systems whose name is already taken (including earlier in the batch) are left out.
+    pub fn create_systems_skipping_existing(&mut self, systems: &[System]) -> Result<Vec<bool>, DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let mut created = Vec::with_capacity(systems.len());
+        for system in systems {
+            let tags_json = system.tags.as_ref().map(|tags| serde_json::to_string(tags).unwrap_or_default());
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO systems (id, name, description, created_date, updated_date, owner, classification, tags, is_active, last_accessed, group_id) 
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    system.id,
+                    system.name,
+                    system.description,
+                    system.created_date,
+                    system.updated_date,
+                    system.owner,
+                    system.classification,
+                    tags_json,
+                    system.is_active,
+                    system.last_accessed,
+                    system.group_id
+                ],
+            )?;
+            created.push(inserted > 0);
         }
-
-        println!("Retrieved {} systems", systems.len());
-        Ok(systems)
+        tx.commit()?;
+        Ok(created)
     }
 
     pub fn get_system_by_id(&self, id: &str) -> Result<Option<System>, DatabaseError> {
@@ -189,19 +171,25 @@ impl<'a> SystemOperations<'a> {
         tx.execute("DELETE FROM milestones WHERE poam_id IN (SELECT id FROM poams WHERE system_id = ?1)", params![id])?;
         tx.execute("DELETE FROM poams WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM notes WHERE system_id = ?1", params![id])?;
+        tx.execute("DELETE FROM stig_vulnerabilities WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM stig_mappings WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM security_test_plans WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM stp_prep_lists WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM control_poam_associations WHERE system_id = ?1", params![id])?;
+        tx.execute("DELETE FROM poam_references WHERE system_id = ?1", params![id])?;
+        tx.execute("DELETE FROM poam_field_history WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM baseline_controls WHERE system_id = ?1", params![id])?;
+        tx.execute("DELETE FROM posture_history WHERE system_id = ?1", params![id])?;
         
         // Delete Nessus-related data
         tx.execute("DELETE FROM nessus_findings WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM nessus_scans WHERE system_id = ?1", params![id])?;
         tx.execute("DELETE FROM nessus_prep_lists WHERE system_id = ?1", params![id])?;
+        tx.execute("DELETE FROM nessus_finding_dispositions WHERE system_id = ?1", params![id])?;
         
         // Delete group associations for this system
         tx.execute("DELETE FROM group_system_associations WHERE system_id = ?1", params![id])?;
+        tx.execute("DELETE FROM group_poam_system_links WHERE system_id = ?1", params![id])?;
         
         // Finally delete the system
         tx.execute("DELETE FROM systems WHERE id = ?1", params![id])?;
@@ -212,284 +200,288 @@ impl<'a> SystemOperations<'a> {
         Ok(())
     }
 
-    pub fn update_system_last_accessed(&mut self, system_id: &str) -> Result<(), DatabaseError> {
-        let now = chrono::Utc::now().to_rfc3339();
-        self.conn.execute(
-            "UPDATE systems SET last_accessed = ?1 WHERE id = ?2",
-            params![now, system_id],
+    // Moves everything owned by one system into another so the source can be deleted.
+    // Row ids are global primary keys, so moving them cannot collide with the target's rows;
+    // only Nessus scan versions and finding dispositions need reconciling. Returns rows moved per table.
+    pub fn reassign_system_data(&mut self, from_system_id: &str, to_system_id: &str) -> Result<BTreeMap<String, usize>, DatabaseError> {
+        if from_system_id == to_system_id {
+            return Err(DatabaseError::Conflict("Source and target system are the same".to_string()));
+        }
+
+        let tx = self.conn.transaction()?;
+        for system_id in [from_system_id, to_system_id] {
+            let exists: i64 = tx.query_row("SELECT COUNT(*) FROM systems WHERE id = ?1", params![system_id], |row| row.get(0))?;
+            if exists == 0 {
+                return Err(DatabaseError::NotFound(format!("System {} not found", system_id)));
+            }
+        }
+
+        // Continue the target's version numbering for scans that share a name
+        let source_scans: Vec<(String, String, i64)> = {
+            let mut stmt = tx.prepare("SELECT id, name, version FROM nessus_scans WHERE system_id = ?1")?;
+            let rows = stmt.query_map(params![from_system_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        for (scan_id, name, version) in &source_scans {
+            let target_max: i64 = tx.query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM nessus_scans WHERE system_id = ?1 AND name = ?2",
+                params![to_system_id, name],
+                |row| row.get(0),
+            )?;
+            if target_max > 0 {
+                tx.execute("UPDATE nessus_scans SET version = ?1 WHERE id = ?2", params![target_max + version, scan_id])?;
+            }
+        }
+
+        let tables = [
+            "poams",
+            "notes",
+            "stig_mappings",
+            "stig_vulnerabilities",
+            "security_test_plans",
+            "stp_prep_lists",
+            "control_poam_associations",
+            "poam_references",
+            "nessus_scans",
+            "nessus_findings",
+            "nessus_prep_lists",
+            "stig_files",
+            "test_case_templates",
+            "group_poam_system_links",
+            "posture_history",
+            "poam_field_history",
+        ];
+        let mut moved = BTreeMap::new();
+        for table in tables {
+            let count = tx.execute(
+                &format!("UPDATE {} SET system_id = ?1 WHERE system_id = ?2", table),
+                params![to_system_id, from_system_id],
+            )?;
+            moved.insert(table.to_string(), count);
+        }
+
+        // A disposition already recorded on the target wins over the source's for the same finding
+        let dispositions = tx.execute(
+            "UPDATE OR IGNORE nessus_finding_dispositions SET system_id = ?1 WHERE system_id = ?2",
+            params![to_system_id, from_system_id],
         )?;
-        Ok(())
-    }
-}
+        tx.execute("DELETE FROM nessus_finding_dispositions WHERE system_id = ?1", params![from_system_id])?;
+        moved.insert("nessus_finding_dispositions".to_string(), dispositions);
 
-use crate::models::{SystemExportData, POAM, Note, STIGMappingData, SecurityTestPlan, StpPrepList, BaselineControl, ControlPOAMAssociation};
-use rusqlite::Row;
-
-// Helper TryFrom implementations for models
-impl<'r> TryFrom<&'r Row<'r>> for POAM {
-    type Error = rusqlite::Error;
-    fn try_from(row: &'r Row<'r>) -> Result<Self, Self::Error> {
-        // This is a placeholder. You need to implement the actual conversion from a rusqlite Row to your POAM struct.
-        // This will depend on the columns in your 'poams' table.
-        // For now, let's assume a simple mapping.
-        Ok(POAM {
-            id: row.get("id")?,
-            title: row.get("title")?,
-            description: row.get("description")?,
-            start_date: row.get("start_date")?,
-            end_date: row.get("end_date")?,
-            status: row.get("status")?,
-            priority: row.get("priority")?,
-            risk_level: row.get("risk_level")?,
-            milestones: serde_json::from_str(&row.get::<_, String>("milestones")?).unwrap_or_default(),
-            resources: row.get("resources")?,
-            source_identifying_vulnerability: row.get("source_identifying_vulnerability")?,
-            raw_severity: row.get("raw_severity")?,
-            severity: row.get("severity")?,
-            relevance_of_threat: row.get("relevance_of_threat")?,
-            likelihood: row.get("likelihood")?,
-            impact: row.get("impact")?,
-            residual_risk: row.get("residual_risk")?,
-            mitigations: row.get("mitigations")?,
-            devices_affected: row.get("devices_affected")?,
-            source_stig_mapping_id: row.get("source_stig_mapping_id")?,
-            selected_vulnerabilities: serde_json::from_str(&row.get::<_, String>("selected_vulnerabilities")?).unwrap_or_default(),
-        })
-    }
-}
+        // Likewise the target's own entry for a baseline control is kept
+        let baseline_controls = tx.execute(
+            "UPDATE OR IGNORE baseline_controls SET system_id = ?1 WHERE system_id = ?2",
+            params![to_system_id, from_system_id],
+        )?;
+        tx.execute("DELETE FROM baseline_controls WHERE system_id = ?1", params![from_system_id])?;
+        moved.insert("baseline_controls".to_string(), baseline_controls);
 
-impl<'r> TryFrom<&'r Row<'r>> for Note {
-    type Error = rusqlite::Error;
-    fn try_from(row: &'r Row<'r>) -> Result<Self, Self::Error> {
-        Ok(Note {
-            id: row.get("id")?,
-            title: row.get("title")?,
-            content: row.get("content")?,
-            date: row.get("date")?,
-            poam_ids: serde_json::from_str(&row.get::<_, String>("poam_ids")?).unwrap_or_default(),
-            poam_titles: serde_json::from_str(&row.get::<_, String>("poam_titles")?).unwrap_or_default(),
-            folder: row.get("folder")?,
-            tags: serde_json::from_str(&row.get::<_, String>("tags")?).unwrap_or_default(),
-        })
-    }
-}
+        let now = chrono::Utc::now().to_rfc3339();
+        tx.execute("UPDATE systems SET updated_date = ?1 WHERE id IN (?2, ?3)", params![now, from_system_id, to_system_id])?;
+        tx.commit()?;
 
-impl<'r> TryFrom<&'r Row<'r>> for STIGMappingData {
-    type Error = rusqlite::Error;
-    fn try_from(row: &'r Row<'r>) -> Result<Self, Self::Error> {
-        Ok(STIGMappingData {
-            id: row.get("id")?,
-            name: row.get("name")?,
-            description: row.get("description")?,
-            created_date: row.get("created_date")?,
-            updated_date: row.get("updated_date")?,
-            stig_info: serde_json::from_str(&row.get::<_, String>("stig_info")?).unwrap_or_default(),
-            asset_info: serde_json::from_str(&row.get::<_, String>("asset_info")?).unwrap_or_default(),
-            mapping_result: serde_json::from_str(&row.get::<_, String>("mapping_result")?).unwrap_or_default(),
-            cci_mappings: serde_json::from_str(&row.get::<_, String>("cci_mappings")?).unwrap_or_default(),
-        })
+        println!("Reassigned data from system {} to {}: {:?}", from_system_id, to_system_id, moved);
+        Ok(moved)
     }
-}
 
-impl<'r> TryFrom<&'r Row<'r>> for SecurityTestPlan {
-    type Error = rusqlite::Error;
-    fn try_from(row: &'r Row<'r>) -> Result<Self, Self::Error> {
-        Ok(SecurityTestPlan {
-            id: row.get("id")?,
-            name: row.get("name")?,
-            description: row.get("description")?,
-            created_date: row.get("created_date")?,
-            updated_date: row.get("updated_date")?,
-            status: row.get("status")?,
-            poam_id: row.get("poam_id")?,
-            stig_mapping_id: row.get("stig_mapping_id")?,
-            test_cases: serde_json::from_str(&row.get::<_, String>("test_cases")?).unwrap_or_default(),
-            overall_score: row.get("overall_score")?,
-        })
-    }
-}
+    // Clears the selected categories of a system's data while keeping the systems row, its
+    // group membership and tags. Returns rows deleted per table.
+    pub fn reset_system_data(&mut self, system_id: &str, options: &SystemDataResetOptions) -> Result<BTreeMap<String, usize>, DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let exists: i64 = tx.query_row("SELECT COUNT(*) FROM systems WHERE id = ?1", params![system_id], |row| row.get(0))?;
+        if exists == 0 {
+            return Err(DatabaseError::NotFound(format!("System {} not found", system_id)));
+        }
 
-impl<'r> TryFrom<&'r Row<'r>> for StpPrepList {
-    type Error = rusqlite::Error;
-    fn try_from(row: &'r Row<'r>) -> Result<Self, Self::Error> {
-        Ok(StpPrepList {
-            id: row.get("id")?,
-            name: row.get("name")?,
-            description: row.get("description")?,
-            created_date: row.get("created_date")?,
-            updated_date: row.get("updated_date")?,
-            source_mapping_id: row.get("source_mapping_id")?,
-            stig_info: serde_json::from_str(&row.get::<_, String>("stig_info")?).unwrap_or_default(),
-            asset_info: serde_json::from_str(&row.get::<_, String>("asset_info")?).unwrap_or_default(),
-            prep_status: row.get("prep_status")?,
-            selected_controls: serde_json::from_str(&row.get::<_, String>("selected_controls")?).unwrap_or_default(),
-            control_count: row.get("control_count")?,
-        })
-    }
-}
+        let mut statements: Vec<(&str, &str)> = Vec::new();
+        if options.poams {
+            statements.extend([
+                ("note_poam_associations", "DELETE FROM note_poam_associations WHERE poam_id IN (SELECT id FROM poams WHERE system_id = ?1)"),
+                ("milestones", "DELETE FROM milestones WHERE poam_id IN (SELECT id FROM poams WHERE system_id = ?1)"),
+                ("control_poam_associations", "DELETE FROM control_poam_associations WHERE system_id = ?1"),
+                ("poam_references", "DELETE FROM poam_references WHERE system_id = ?1"),
+                ("poam_field_history", "DELETE FROM poam_field_history WHERE system_id = ?1"),
+                ("group_poam_system_links", "DELETE FROM group_poam_system_links WHERE system_id = ?1"),
+                ("poams", "DELETE FROM poams WHERE system_id = ?1"),
+            ]);
+            if !options.test_plans {
+                statements.push(("security_test_plans", "UPDATE security_test_plans SET poam_id = NULL WHERE system_id = ?1"));
+            }
+        }
+        if options.notes {
+            statements.extend([
+                ("note_poam_associations", "DELETE FROM note_poam_associations WHERE note_id IN (SELECT id FROM notes WHERE system_id = ?1)"),
+                ("notes", "DELETE FROM notes WHERE system_id = ?1"),
+            ]);
+        }
+        if options.stig_mappings {
+            statements.extend([
+                ("stig_vulnerabilities", "DELETE FROM stig_vulnerabilities WHERE system_id = ?1"),
+                ("stig_files", "DELETE FROM stig_files WHERE system_id = ?1"),
+                ("stig_mappings", "DELETE FROM stig_mappings WHERE system_id = ?1"),
+            ]);
+            if !options.test_plans {
+                statements.extend([
+                    ("security_test_plans", "UPDATE security_test_plans SET stig_mapping_id = NULL WHERE system_id = ?1"),
+                    ("stp_prep_lists", "UPDATE stp_prep_lists SET source_mapping_id = NULL WHERE system_id = ?1"),
+                ]);
+            }
+        }
+        if options.test_plans {
+            statements.extend([
+                ("security_test_plans", "DELETE FROM security_test_plans WHERE system_id = ?1"),
+                ("stp_prep_lists", "DELETE FROM stp_prep_lists WHERE system_id = ?1"),
+            ]);
+        }
+        if options.nessus {
+            statements.extend([
+                ("nessus_findings", "DELETE FROM nessus_findings WHERE system_id = ?1"),
+                ("nessus_scans", "DELETE FROM nessus_scans WHERE system_id = ?1"),
+                ("nessus_prep_lists", "DELETE FROM nessus_prep_lists WHERE system_id = ?1"),
+                ("nessus_finding_dispositions", "DELETE FROM nessus_finding_dispositions WHERE system_id = ?1"),
+            ]);
+        }
+        if options.baseline_controls {
+            statements.extend([
+                ("control_poam_associations", "DELETE FROM control_poam_associations WHERE system_id = ?1"),
+                ("baseline_controls", "DELETE FROM baseline_controls WHERE system_id = ?1"),
+            ]);
+        }
 
-impl<'r> TryFrom<&'r Row<'r>> for BaselineControl {
-    type Error = rusqlite::Error;
-    fn try_from(row: &'r Row<'r>) -> Result<Self, Self::Error> {
-        Ok(BaselineControl {
-            id: row.get("id")?,
-            family: row.get("family")?,
-            title: row.get("title")?,
-            implementation_status: row.get("implementation_status")?,
-            date_added: row.get("date_added")?,
-            responsible_party: row.get("responsible_party")?,
-            notes: row.get("notes")?,
-            system_id: row.get("system_id")?,
-        })
-    }
-}
+        let mut cleared = BTreeMap::new();
+        for (table, sql) in statements {
+            let count = tx.execute(sql, params![system_id])?;
+            *cleared.entry(table.to_string()).or_insert(0) += count;
+        }
 
-impl<'r> TryFrom<&'r Row<'r>> for ControlPOAMAssociation {
-    type Error = rusqlite::Error;
-    fn try_from(row: &'r Row<'r>) -> Result<Self, Self::Error> {
-        Ok(ControlPOAMAssociation {
-            id: row.get("id")?,
-            control_id: row.get("control_id")?,
-            poam_id: row.get("poam_id")?,
-            association_date: row.get("association_date")?,
-            created_by: row.get("created_by")?,
-            notes: row.get("notes")?,
-        })
+        let now = chrono::Utc::now().to_rfc3339();
+        tx.execute("UPDATE systems SET updated_date = ?1 WHERE id = ?2", params![now, system_id])?;
+        tx.commit()?;
+
+        println!("Reset data for system {}: {:?}", system_id, cleared);
+        Ok(cleared)
     }
-}
 
-impl<'a> SystemQueries<'a> {
-    pub fn get_system_export_data(&self, system_id: &str) -> Result<SystemExportData, DatabaseError> {
-        let system = self.get_system_by_id(system_id)?
-            .ok_or_else(|| DatabaseError::NotFound(format!("System with id {} not found", system_id)))?;
+    /// Rewrites stored severities to one spelling per source: title case on POAMs ("High"),
+    /// lowercase inside STIG mappings as checklists use ("high") and the numeric form Nessus
+    /// uses ("3"). Unrecognized values are left untouched. Returns changed rows per table.
+    pub fn normalize_severities(&mut self, system_id: &str) -> Result<BTreeMap<String, usize>, DatabaseError> {
+        let tx = self.conn.transaction()?;
+        let mut changed = BTreeMap::new();
 
-        let poams: Vec<POAM> = self.conn.prepare("SELECT * FROM poams WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| POAM::try_from(row))?
-            .filter_map(Result::ok)
-            .collect();
+        let poams: Vec<(i64, Option<String>, Option<String>)> = {
+            let mut stmt = tx.prepare("SELECT id, severity, raw_severity FROM poams WHERE system_id = ?1")?;
+            let rows = stmt.query_map(params![system_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let normalize_poam = |value: &Option<String>| -> Option<String> {
+            value.as_deref().map(|v| normalize_severity(v).map(str::to_string).unwrap_or_else(|| v.to_string()))
+        };
+        let mut poam_count = 0;
+        for (id, severity, raw_severity) in &poams {
+            let (new_severity, new_raw) = (normalize_poam(severity), normalize_poam(raw_severity));
+            if &new_severity != severity || &new_raw != raw_severity {
+                tx.execute(
+                    "UPDATE poams SET severity = ?1, raw_severity = ?2 WHERE id = ?3",
+                    params![new_severity, new_raw, id],
+                )?;
+                poam_count += 1;
+            }
+        }
+        changed.insert("poams".to_string(), poam_count);
 
-        let notes: Vec<Note> = self.conn.prepare("SELECT * FROM notes WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| Note::try_from(row))?
-            .filter_map(Result::ok)
-            .collect();
+        let mappings: Vec<(String, String)> = {
+            let mut stmt = tx.prepare("SELECT id, mapping_result FROM stig_mappings WHERE system_id = ?1")?;
+            let rows = stmt.query_map(params![system_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let mut mapping_count = 0;
+        for (id, json) in &mappings {
+            let mut result: STIGMappingResult = serde_json::from_str(json)?;
+            let mut dirty = false;
+            for vuln in result.mapped_controls.iter_mut().flat_map(|c| c.stigs.iter_mut()) {
+                if let Some(canonical) = normalize_severity(&vuln.severity).map(str::to_lowercase) {
+                    if canonical != vuln.severity {
+                        vuln.severity = canonical;
+                        dirty = true;
+                    }
+                }
+            }
+            if dirty {
+                tx.execute(
+                    "UPDATE stig_mappings SET mapping_result = ?1 WHERE id = ?2",
+                    params![serde_json::to_string(&result)?, id],
+                )?;
+                write_vulnerability_index(&tx, id, &result, system_id)?;
+                mapping_count += 1;
+            }
+        }
+        changed.insert("stig_mappings".to_string(), mapping_count);
 
-        let stig_mappings: Vec<STIGMappingData> = self.conn.prepare("SELECT * FROM stig_mappings WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| STIGMappingData::try_from(row))?
-            .filter_map(Result::ok)
-            .collect();
+        let findings: Vec<(String, String)> = {
+            let mut stmt = tx.prepare("SELECT id, severity FROM nessus_findings WHERE system_id = ?1 AND severity IS NOT NULL")?;
+            let rows = stmt.query_map(params![system_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let mut finding_count = 0;
+        for (id, severity) in &findings {
+            let canonical = normalize_severity(severity).and_then(severity_value_rank).map(|rank| rank.to_string());
+            if let Some(canonical) = canonical.filter(|c| c != severity) {
+                tx.execute("UPDATE nessus_findings SET severity = ?1 WHERE id = ?2", params![canonical, id])?;
+                finding_count += 1;
+            }
+        }
+        changed.insert("nessus_findings".to_string(), finding_count);
 
-        let test_plans: Vec<SecurityTestPlan> = self.conn.prepare("SELECT * FROM security_test_plans WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| SecurityTestPlan::try_from(row))?
-            .filter_map(Result::ok)
-            .collect();
+        tx.commit()?;
+        println!("Normalized severities for system {}: {:?}", system_id, changed);
+        Ok(changed)
+    }
 
-        let prep_lists: Vec<StpPrepList> = self.conn.prepare("SELECT * FROM stp_prep_lists WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| StpPrepList::try_from(row))?
-            .filter_map(Result::ok)
-            .collect();
+    pub fn update_system_last_accessed(&mut self, system_id: &str) -> Result<(), DatabaseError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let updated = self.conn.execute(
+            "UPDATE systems SET last_accessed = ?1 WHERE id = ?2",
+            params![now, system_id],
+        )?;
+        if updated == 0 {
+            return Err(DatabaseError::NotFound(format!("System {} not found", system_id)));
+        }
+        Ok(())
+    }
+}
 
-        let baseline_controls: Vec<BaselineControl> = self.conn.prepare("SELECT * FROM baseline_controls WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| BaselineControl::try_from(row))?
-            .filter_map(Result::ok)
-            .collect();
+use crate::models::SystemExportData;
 
-        let poam_control_associations: Vec<ControlPOAMAssociation> = self.conn.prepare("SELECT * FROM control_poam_associations WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| ControlPOAMAssociation::try_from(row))?
-            .filter_map(Result::ok)
-            .collect();
+impl<'a> SystemQueries<'a> {
+    // Reads through each entity's own queries so the payload matches what those queries return
+    pub fn get_system_export_data(&self, system_id: &str) -> Result<SystemExportData, DatabaseError> {
+        let system = self.get_system_by_id(system_id)?
+            .ok_or_else(|| DatabaseError::NotFound(format!("System with id {} not found", system_id)))?;
 
-        // Get nessus data
-        let nessus_scans: Vec<crate::database::nessus::NessusScanMeta> = self.conn.prepare("SELECT id, name, description, imported_date, version, source_file, scan_info FROM nessus_scans WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| {
-                let scan_info_str: String = row.get("scan_info")?;
-                let scan_info = serde_json::from_str(&scan_info_str).unwrap_or(serde_json::Value::Null);
-                
-                Ok(crate::database::nessus::NessusScanMeta {
-                    id: row.get("id")?,
-                    name: row.get("name")?,
-                    description: row.get("description")?,
-                    imported_date: row.get("imported_date")?,
-                    version: row.get("version")?,
-                    source_file: row.get("source_file")?,
-                    scan_info,
-                })
-            })?
-            .filter_map(Result::ok)
-            .collect();
+        let poams = super::poams::POAMQueries::new(self.conn).get_all_poams(system_id)?;
+        let notes = super::notes::NoteQueries::new(self.conn).get_all_notes(system_id)?;
+        let stig_mappings = super::stig_mappings::STIGMappingQueries::new(self.conn).get_all_stig_mappings(system_id)?;
+        let stp_queries = super::security_test_plans::SecurityTestPlanQueries::new(self.conn);
+        let test_plans = stp_queries.get_all_security_test_plans(system_id)?;
+        let prep_lists = stp_queries.get_all_stp_prep_lists(system_id)?;
+        let baseline_controls = super::baseline_controls::BaselineControlQueries::new(self.conn).get_baseline_controls(system_id)?;
+
+        let association_queries = super::control_poam_associations::ControlPOAMAssociationQueries::new(self.conn);
+        let mut poam_control_associations = Vec::new();
+        for poam in &poams {
+            poam_control_associations.extend(association_queries.get_control_poam_associations_by_poam(poam.id, system_id)?);
+        }
 
-        let nessus_findings: Vec<crate::database::nessus::NessusFinding> = self.conn.prepare("SELECT id, scan_id, plugin_id, plugin_name, severity, risk_factor, cve, cvss_base_score, host, port, protocol, synopsis, description, solution, raw_json FROM nessus_findings WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| {
-                let raw_json_str: String = row.get("raw_json")?;
-                let raw_json = serde_json::from_str(&raw_json_str).unwrap_or(serde_json::Value::Null);
-                
-                Ok(crate::database::nessus::NessusFinding {
-                    id: row.get("id")?,
-                    scan_id: row.get("scan_id")?,
-                    plugin_id: row.get("plugin_id")?,
-                    plugin_name: row.get("plugin_name")?,
-                    severity: row.get("severity")?,
-                    risk_factor: row.get("risk_factor")?,
-                    cve: row.get("cve")?,
-                    cvss_base_score: row.get("cvss_base_score")?,
-                    host: row.get("host")?,
-                    port: row.get("port")?,
-                    protocol: row.get("protocol")?,
-                    synopsis: row.get("synopsis")?,
-                    description: row.get("description")?,
-                    solution: row.get("solution")?,
-                    raw_json,
-                })
-            })?
-            .filter_map(Result::ok)
-            .collect();
+        let nessus_queries = super::nessus::NessusQueries::new(self.conn);
+        let nessus_scans = nessus_queries.get_scans(system_id)?;
+        let mut nessus_findings = Vec::new();
+        for scan in &nessus_scans {
+            nessus_findings.extend(nessus_queries.get_findings_by_scan(&scan.id, system_id)?);
+        }
+        let nessus_prep_lists = nessus_queries.get_prep_lists(system_id)?;
 
-        let nessus_prep_lists: Vec<crate::database::nessus::NessusPrepList> = self.conn.prepare("SELECT id, name, description, created_date, updated_date, source_scan_id, asset_info, selected_findings, finding_count, milestones, cve_analysis, summary, prep_status, scan_info FROM nessus_prep_lists WHERE system_id = ?1")?
-            .query_map(params![system_id], |row| {
-                let asset_info_str: String = row.get("asset_info")?;
-                let asset_info = serde_json::from_str(&asset_info_str).unwrap_or(serde_json::Value::Null);
-                
-                let selected_findings_str: String = row.get("selected_findings")?;
-                let selected_findings = serde_json::from_str(&selected_findings_str).unwrap_or(serde_json::Value::Null);
-                
-                let milestones = match row.get::<_, Option<String>>("milestones")? {
-                    Some(s) => Some(serde_json::from_str(&s).unwrap_or(serde_json::Value::Null)),
-                    None => None,
-                };
-                
-                let cve_analysis = match row.get::<_, Option<String>>("cve_analysis")? {
-                    Some(s) => Some(serde_json::from_str(&s).unwrap_or(serde_json::Value::Null)),
-                    None => None,
-                };
-                
-                let summary = match row.get::<_, Option<String>>("summary")? {
-                    Some(s) => Some(serde_json::from_str(&s).unwrap_or(serde_json::Value::Null)),
-                    None => None,
-                };
-                
-                let scan_info = match row.get::<_, Option<String>>("scan_info")? {
-                    Some(s) => Some(serde_json::from_str(&s).unwrap_or(serde_json::Value::Null)),
-                    None => None,
-                };
-                
-                Ok(crate::database::nessus::NessusPrepList {
-                    id: row.get("id")?,
-                    name: row.get("name")?,
-                    description: row.get("description")?,
-                    created_date: row.get("created_date")?,
-                    updated_date: row.get("updated_date")?,
-                    source_scan_id: row.get("source_scan_id")?,
-                    asset_info,
-                    selected_findings,
-                    finding_count: row.get("finding_count")?,
-                    milestones,
-                    cve_analysis,
-                    summary,
-                    prep_status: row.get("prep_status")?,
-                    scan_info,
-                })
-            })?
-            .filter_map(Result::ok)
-            .collect();
+        let poam_references = super::poam_references::POAMReferenceQueries::new(self.conn).get_poam_references_for_system(system_id)?;
 
         Ok(SystemExportData {
             system,
@@ -503,6 +495,8 @@ impl<'a> SystemQueries<'a> {
             nessus_scans: if nessus_scans.is_empty() { None } else { Some(nessus_scans) },
             nessus_findings: if nessus_findings.is_empty() { None } else { Some(nessus_findings) },
             nessus_prep_lists: if nessus_prep_lists.is_empty() { None } else { Some(nessus_prep_lists) },
+            poam_references: if poam_references.is_empty() { None } else { Some(poam_references) },
+            evidence_omitted: None,
             export_date: None,
             export_version: None,
         })
@@ -512,20 +506,36 @@ impl<'a> SystemQueries<'a> {
     }
 
     pub fn get_all_systems(&self) -> Result<Vec<SystemSummary>, DatabaseError> {
+        self.query_system_summaries(false)
+    }
+
+    // Systems with their item counts, ordered by name; tags are stored as a JSON array so tag
+    // filtering happens in Rust on top of this query
+    fn query_system_summaries(&self, active_only: bool) -> Result<Vec<SystemSummary>, DatabaseError> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, description, owner, classification, tags, last_accessed, created_date, group_id
-             FROM systems 
-             ORDER BY name"
+            "SELECT s.id, s.name, s.description, s.owner, s.classification, s.tags, s.created_date, s.last_accessed, s.group_id,
+                    COUNT(DISTINCT p.id) as poam_count,
+                    COUNT(DISTINCT n.id) as notes_count,
+                    COUNT(DISTINCT sm.id) as stig_mappings_count,
+                    COUNT(DISTINCT stp.id) as test_plans_count
+             FROM systems s
+             LEFT JOIN poams p ON s.id = p.system_id
+             LEFT JOIN notes n ON s.id = n.system_id
+             LEFT JOIN stig_mappings sm ON s.id = sm.system_id
+             LEFT JOIN security_test_plans stp ON s.id = stp.system_id
+             WHERE ?1 = 0 OR s.is_active = 1
+             GROUP BY s.id, s.name, s.description, s.owner, s.classification, s.tags, s.created_date, s.last_accessed, s.group_id
+             ORDER BY s.name"
         )?;
-        
-        let system_rows = stmt.query_map([], |row| {
-            let tags_json: Option<String> = row.get(5)?;
-            let tags = if let Some(json_str) = tags_json {
+
+        let system_iter = stmt.query_map(params![active_only], |row| {
+            let tags_str: Option<String> = row.get(5)?;
+            let tags = if let Some(json_str) = tags_str {
                 serde_json::from_str(&json_str).unwrap_or_default()
             } else {
                 None
             };
-            
+
             Ok(SystemSummary {
                 id: row.get(0)?,
                 name: row.get(1)?,
@@ -533,24 +543,86 @@ impl<'a> SystemQueries<'a> {
                 owner: row.get(3)?,
                 classification: row.get(4)?,
                 tags,
-                poam_count: 0, // TODO: Calculate actual counts
-                notes_count: 0,
-                stig_mappings_count: 0,
-                test_plans_count: 0,
-                last_accessed: row.get(6)?,
-                created_date: row.get(7)?,
+                created_date: row.get(6)?,
+                last_accessed: row.get(7)?,
                 group_id: row.get(8)?,
+                poam_count: row.get(9).unwrap_or(0),
+                notes_count: row.get(10).unwrap_or(0),
+                stig_mappings_count: row.get(11).unwrap_or(0),
+                test_plans_count: row.get(12).unwrap_or(0),
             })
         })?;
-        
+
         let mut systems = Vec::new();
-        for system_result in system_rows {
-            systems.push(system_result?);
+        for system in system_iter {
+            systems.push(system?);
         }
-        
+
+        Ok(systems)
+    }
+
+    // Most recently accessed active systems first; systems never opened sort last
+    pub fn get_recent_systems(&self, limit: usize) -> Result<Vec<SystemSummary>, DatabaseError> {
+        let mut systems = self.query_system_summaries(true)?;
+        systems.sort_by(|a, b| match (&a.last_accessed, &b.last_accessed) {
+            (Some(a_time), Some(b_time)) => b_time.cmp(a_time),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.name.cmp(&b.name),
+        });
+        systems.truncate(limit);
+        Ok(systems)
+    }
+
+    // Active systems last opened before `cutoff`, least recently accessed first. Never-accessed
+    // systems and unreadable timestamps count as stale and lead the list.
+    pub fn get_stale_systems(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<Vec<SystemSummary>, DatabaseError> {
+        let accessed_at = |system: &SystemSummary| system.last_accessed.as_deref()
+            .and_then(|value| chrono::DateTime::parse_from_rfc3339(value.trim()).ok())
+            .map(|accessed| accessed.with_timezone(&chrono::Utc));
+
+        let mut stale: Vec<SystemSummary> = self.query_system_summaries(true)?
+            .into_iter()
+            .filter(|system| accessed_at(system).is_none_or(|accessed| accessed < cutoff))
+            .collect();
+        stale.sort_by_key(|system| accessed_at(system));
+        Ok(stale)
+    }
+
+    pub fn get_systems_by_tag(&self, tag: &str) -> Result<Vec<SystemSummary>, DatabaseError> {
+        let wanted = tag.trim().to_lowercase();
+        let systems = self.query_system_summaries(true)?
+            .into_iter()
+            .filter(|s| {
+                s.tags
+                    .as_ref()
+                    .map(|tags| tags.iter().any(|t| t.trim().to_lowercase() == wanted))
+                    .unwrap_or(false)
+            })
+            .collect::<Vec<_>>();
+
+        println!("Found {} systems tagged '{}'", systems.len(), tag);
         Ok(systems)
     }
 
+    pub fn get_all_tags(&self) -> Result<Vec<TagCount>, DatabaseError> {
+        // Tags match case-insensitively, so they are counted that way too; the first spelling seen is reported
+        let mut counts: std::collections::BTreeMap<String, TagCount> = std::collections::BTreeMap::new();
+        for system in self.query_system_summaries(true)? {
+            // Count each tag once per system even if it is duplicated in the array
+            let mut seen = std::collections::HashSet::new();
+            for tag in system.tags.unwrap_or_default() {
+                let tag = tag.trim().to_string();
+                let key = tag.to_lowercase();
+                if !tag.is_empty() && seen.insert(key.clone()) {
+                    counts.entry(key).or_insert(TagCount { tag, count: 0 }).count += 1;
+                }
+            }
+        }
+
+        Ok(counts.into_values().collect())
+    }
+
     pub fn get_system_by_id(&self, id: &str) -> Result<Option<System>, DatabaseError> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, description, created_date, updated_date, owner, classification, tags, is_active, last_accessed, group_id 
@@ -589,3 +661,229 @@ impl<'a> SystemQueries<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::{memory_db, poam, system};
+    use crate::database::{Database, DatabaseError};
+    use crate::models::System;
+
+    fn tagged(id: &str, tags: Option<&[&str]>) -> System {
+        System { tags: tags.map(|t| t.iter().map(|s| s.to_string()).collect()), ..system(id) }
+    }
+
+    fn seed() -> Database {
+        let mut db = memory_db();
+        db.create_system(&tagged("web", Some(&["production", "PII"]))).unwrap();
+        db.create_system(&tagged("lab", Some(&["Production ", "test"]))).unwrap();
+        db.create_system(&tagged("untagged", None)).unwrap();
+        db.create_system(&tagged("empty", Some(&[]))).unwrap();
+        db.create_system(&System { is_active: false, ..tagged("retired", Some(&["production"])) }).unwrap();
+        db
+    }
+
+    fn ids(systems: Vec<crate::models::SystemSummary>) -> Vec<String> {
+        systems.into_iter().map(|s| s.id).collect()
+    }
+
+    #[test]
+    fn matches_a_single_tag_case_insensitively() {
+        let db = seed();
+        assert_eq!(ids(db.get_systems_by_tag("production").unwrap()), vec!["lab", "web"]);
+        assert_eq!(ids(db.get_systems_by_tag("pii").unwrap()), vec!["web"]);
+        assert!(db.get_systems_by_tag("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn systems_without_tags_never_match() {
+        let db = seed();
+        let all_tagged: Vec<String> = ["production", "pii", "test", ""]
+            .iter()
+            .flat_map(|t| ids(db.get_systems_by_tag(t).unwrap()))
+            .collect();
+        assert!(!all_tagged.iter().any(|id| id == "untagged" || id == "empty"));
+    }
+
+    #[test]
+    fn counts_distinct_tags_across_active_systems() {
+        let db = seed();
+        let counts: Vec<(String, i32)> = db.get_all_tags().unwrap().into_iter().map(|t| (t.tag, t.count)).collect();
+        // "Production " on the lab system and "production" on web are one tag; the retired system is ignored
+        assert_eq!(counts, vec![
+            ("PII".to_string(), 1),
+            ("Production".to_string(), 2),
+            ("test".to_string(), 1),
+        ]);
+    }
+
+    #[test]
+    fn summaries_include_item_counts() {
+        let mut db = seed();
+        db.create_poam(&poam(1), "web").unwrap();
+        db.create_poam(&poam(2), "web").unwrap();
+
+        let web = db.get_systems_by_tag("pii").unwrap().remove(0);
+        assert_eq!(web.poam_count, 2);
+        let all = db.get_all_systems().unwrap();
+        assert!(all.iter().any(|s| s.id == "retired"), "get_all_systems still lists inactive systems");
+        assert_eq!(all.iter().find(|s| s.id == "web").unwrap().poam_count, 2);
+    }
+
+    #[test]
+    fn recent_systems_follow_access_order_and_skip_inactive_ones() {
+        let mut db = seed();
+        for id in ["lab", "retired", "web", "untagged"] {
+            db.update_system_last_accessed(id).unwrap();
+        }
+
+        assert_eq!(ids(db.get_recent_systems(3).unwrap()), vec!["untagged", "web", "lab"]);
+
+        // Never-accessed systems follow the accessed ones
+        let all = db.get_recent_systems(100).unwrap();
+        assert!(all.iter().all(|s| s.id != "retired"));
+        assert_eq!(ids(all[..3].to_vec()), vec!["untagged", "web", "lab"]);
+        assert!(all[3..].iter().all(|s| s.last_accessed.is_none()));
+
+        assert!(matches!(db.update_system_last_accessed("missing"), Err(crate::database::DatabaseError::NotFound(_))));
+    }
+
+    #[test]
+    fn reassigning_moves_everything_into_the_target_and_empties_the_source() {
+        use crate::database::test_support::{db_with_systems, milestone, nessus_finding, nessus_scan, stig_mapping, stig_vuln, test_case, test_plan};
+        use crate::models::{BaselineControl, Note, POAM};
+
+        let baseline = |id: &str, system_id: &str, status: &str| BaselineControl {
+            id: id.to_string(),
+            family: id[..2].to_string(),
+            title: format!("Control {}", id),
+            implementation_status: status.to_string(),
+            date_added: "2024-01-01".to_string(),
+            responsible_party: String::new(),
+            notes: String::new(),
+            system_id: system_id.to_string(),
+        };
+
+        let mut db = db_with_systems(&["from", "to"]);
+        db.create_poam(&POAM { milestones: vec![milestone("m1")], ..poam(1) }, "from").unwrap();
+        db.create_poam(&poam(2), "to").unwrap();
+        db.create_note(&Note {
+            id: "n1".to_string(),
+            title: "Note".to_string(),
+            content: String::new(),
+            date: "2024-01-01".to_string(),
+            poam_ids: Some(vec![1]),
+            poam_titles: None,
+            folder: None,
+            tags: None,
+        }, "from").unwrap();
+        db.save_stig_mapping(&stig_mapping("map", vec![("AC-2", vec![stig_vuln("V-1", "Open", &[])])]), "from").unwrap();
+        db.save_security_test_plan(&test_plan("plan", vec![test_case("tc", "AC-2")]), "from").unwrap();
+        db.add_baseline_control(&baseline("AC-2", "from", "Not Implemented")).unwrap();
+        db.add_baseline_control(&baseline("AC-3", "from", "Implemented")).unwrap();
+        db.add_baseline_control(&baseline("AC-2", "to", "Implemented")).unwrap();
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-to"), &[], "to").unwrap();
+        db.save_nessus_scan_and_findings(&nessus_scan("scan-from"), &[nessus_finding("f1", "scan-from", 100, "10.0.0.1", "2")], "from").unwrap();
+        // Both scans are named after their id, so give the source's the target's name
+        db.conn.execute("UPDATE nessus_scans SET name = 'Scan scan-to' WHERE id = 'scan-from'", []).unwrap();
+
+        let moved = db.reassign_system_data("from", "to").unwrap();
+        assert_eq!(moved["poams"], 1);
+        assert_eq!(moved["baseline_controls"], 1);
+
+        let mut poam_ids: Vec<i64> = db.get_all_poams("to").unwrap().iter().map(|p| p.id).collect();
+        poam_ids.sort();
+        assert_eq!(poam_ids, [1, 2]);
+        assert_eq!(db.get_poam_by_id(1, "to").unwrap().unwrap().milestones.len(), 1);
+        assert_eq!(db.get_all_notes("to").unwrap()[0].poam_ids.as_deref(), Some(&[1][..]));
+        assert_eq!(db.get_all_stig_mappings("to").unwrap().len(), 1);
+        assert_eq!(db.get_all_security_test_plans("to").unwrap().len(), 1);
+
+        let mut controls: Vec<(String, String)> = db.get_baseline_controls("to").unwrap().into_iter().map(|c| (c.id, c.implementation_status)).collect();
+        controls.sort();
+        assert_eq!(controls, [("AC-2".to_string(), "Implemented".to_string()), ("AC-3".to_string(), "Implemented".to_string())]);
+
+        let mut versions: Vec<(String, i32)> = db.get_nessus_scans("to").unwrap().into_iter().map(|s| (s.id, s.version)).collect();
+        versions.sort();
+        assert_eq!(versions, [("scan-from".to_string(), 2), ("scan-to".to_string(), 1)]);
+        assert_eq!(db.get_nessus_findings_by_scan("scan-from", "to").unwrap().len(), 1);
+
+        assert!(db.get_all_poams("from").unwrap().is_empty());
+        assert!(db.get_all_notes("from").unwrap().is_empty());
+        assert!(db.get_all_stig_mappings("from").unwrap().is_empty());
+        assert!(db.get_all_security_test_plans("from").unwrap().is_empty());
+        assert!(db.get_baseline_controls("from").unwrap().is_empty());
+        assert!(db.get_nessus_scans("from").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reassigning_requires_two_distinct_existing_systems() {
+        let mut db = memory_db();
+        db.create_system(&system("from")).unwrap();
+        assert!(matches!(db.reassign_system_data("from", "from"), Err(DatabaseError::Conflict(_))));
+        assert!(matches!(db.reassign_system_data("from", "missing"), Err(DatabaseError::NotFound(_))));
+    }
+
+    #[test]
+    fn stale_systems_are_active_ones_last_opened_before_the_cutoff() {
+        let mut db = seed();
+        let cutoff = chrono::DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        for (id, accessed) in [
+            ("web", Some("2024-05-31T23:59:59Z")),
+            ("lab", Some("2024-06-01T00:00:00Z")),
+            ("empty", Some("2024-01-15T08:00:00+00:00")),
+            ("retired", Some("2023-01-01T00:00:00Z")),
+            ("untagged", None),
+            ("default", Some("2024-07-01T00:00:00Z")),
+        ] {
+            db.conn.execute("UPDATE systems SET last_accessed = ?1 WHERE id = ?2", rusqlite::params![accessed, id]).unwrap();
+        }
+
+        // Exactly at the cutoff is not stale; the inactive system is never listed
+        assert_eq!(ids(db.get_stale_systems(cutoff).unwrap()), vec!["untagged", "empty", "web"]);
+        let stale = db.get_stale_systems(cutoff).unwrap();
+        assert_eq!(stale[2].last_accessed.as_deref(), Some("2024-05-31T23:59:59Z"));
+    }
+
+    #[test]
+    fn normalizing_severities_canonicalizes_mixed_casing_once() {
+        use crate::database::test_support::{nessus_finding, nessus_scan, stig_mapping, stig_vuln};
+        use crate::models::{POAM, STIGVulnerability};
+
+        let mut db = memory_db();
+        db.create_system(&system("sys")).unwrap();
+        db.create_poam(&POAM { severity: Some("CAT I".to_string()), raw_severity: Some("high".to_string()), ..poam(1) }, "sys").unwrap();
+        db.create_poam(&POAM { severity: Some("High".to_string()), raw_severity: Some("High".to_string()), ..poam(2) }, "sys").unwrap();
+        db.create_poam(&POAM { severity: Some("unrated".to_string()), ..poam(3) }, "sys").unwrap();
+        let vuln = |num: &str, severity: &str| STIGVulnerability { severity: severity.to_string(), ..stig_vuln(num, "Open", &[]) };
+        db.save_stig_mapping(&stig_mapping("map", vec![("AC-2", vec![vuln("V-1", "CAT II"), vuln("V-2", "low")])]), "sys").unwrap();
+        db.save_nessus_scan_and_findings(&nessus_scan("scan"), &[
+            nessus_finding("f1", "scan", 100, "10.0.0.1", "3"),
+            nessus_finding("f2", "scan", 101, "10.0.0.2", "3"),
+        ], "sys").unwrap();
+        db.conn.execute("UPDATE nessus_findings SET severity = 'Critical' WHERE id = 'f1'", []).unwrap();
+
+        let changed = db.normalize_severities("sys").unwrap();
+        assert_eq!(changed["poams"], 1);
+        assert_eq!(changed["stig_mappings"], 1);
+        assert_eq!(changed["nessus_findings"], 1);
+
+        let severities = |id: i64| {
+            let p = db.get_poam_by_id(id, "sys").unwrap().unwrap();
+            (p.severity, p.raw_severity)
+        };
+        assert_eq!(severities(1), (Some("High".to_string()), Some("High".to_string())));
+        assert_eq!(severities(2), (Some("High".to_string()), Some("High".to_string())));
+        // Values outside the scale are left for the user to fix
+        assert_eq!(severities(3).0.as_deref(), Some("unrated"));
+
+        let mapping = db.get_stig_mapping_by_id("map", "sys").unwrap().unwrap();
+        let stig_severities: Vec<&str> = mapping.mapping_result.mapped_controls[0].stigs.iter().map(|v| v.severity.as_str()).collect();
+        assert_eq!(stig_severities, ["medium", "low"]);
+        let mut finding_severities: Vec<Option<String>> = db.get_nessus_findings_by_scan("scan", "sys").unwrap().into_iter().map(|f| f.severity).collect();
+        finding_severities.sort();
+        assert_eq!(finding_severities, [Some("3".to_string()), Some("4".to_string())]);
+
+        // A second pass finds nothing left to change
+        assert!(db.normalize_severities("sys").unwrap().values().all(|&n| n == 0));
+    }
+}
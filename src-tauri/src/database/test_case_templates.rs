@@ -0,0 +1,152 @@
+use crate::models::TestCaseTemplate;
+use rusqlite::{params, Connection};
+use super::utils::DatabaseError;
+
+pub struct TestCaseTemplateOperations<'a> {
+    conn: &'a mut Connection,
+}
+
+pub struct TestCaseTemplateQueries<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> TestCaseTemplateOperations<'a> {
+    pub fn new(conn: &'a mut Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn save_test_case_template(&mut self, template: &TestCaseTemplate) -> Result<(), DatabaseError> {
+        println!("Saving test case template: {}", template.name);
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO test_case_templates
+             (id, name, nist_control, cci_ref, test_description, test_procedure, expected_result, risk_rating, system_id, created_date, updated_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                template.id,
+                template.name,
+                template.nist_control,
+                template.cci_ref,
+                template.test_description,
+                template.test_procedure,
+                template.expected_result,
+                template.risk_rating,
+                template.system_id,
+                template.created_date,
+                template.updated_date
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // Scoped like get_test_case_templates: a caller can delete global templates and its own
+    // system's, never another system's. Returns whether a template was deleted.
+    pub fn delete_test_case_template(&mut self, id: &str, system_id: Option<&str>) -> Result<bool, DatabaseError> {
+        let deleted = self.conn.execute(
+            "DELETE FROM test_case_templates WHERE id = ?1 AND (system_id IS NULL OR system_id = ?2)",
+            params![id, system_id],
+        )?;
+        Ok(deleted > 0)
+    }
+}
+
+impl<'a> TestCaseTemplateQueries<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    // Returns global templates plus, when a system is given, that system's own templates
+    pub fn get_test_case_templates(&self, system_id: Option<&str>) -> Result<Vec<TestCaseTemplate>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, nist_control, cci_ref, test_description, test_procedure, expected_result, risk_rating, system_id, created_date, updated_date
+             FROM test_case_templates
+             WHERE system_id IS NULL OR system_id = ?1
+             ORDER BY nist_control, name"
+        )?;
+
+        let templates = stmt
+            .query_map(params![system_id], |row| {
+                Ok(TestCaseTemplate {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    nist_control: row.get(2)?,
+                    cci_ref: row.get(3)?,
+                    test_description: row.get(4)?,
+                    test_procedure: row.get(5)?,
+                    expected_result: row.get(6)?,
+                    risk_rating: row.get(7)?,
+                    system_id: row.get(8)?,
+                    created_date: row.get(9)?,
+                    updated_date: row.get(10)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(templates)
+    }
+
+    pub fn get_test_case_templates_by_ids(&self, ids: &[String], system_id: &str) -> Result<Vec<TestCaseTemplate>, DatabaseError> {
+        let available = self.get_test_case_templates(Some(system_id))?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| available.iter().find(|t| &t.id == id).cloned())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_support::db_with_systems;
+    use crate::models::TestCaseTemplate;
+
+    fn template(id: &str, system_id: Option<&str>) -> TestCaseTemplate {
+        TestCaseTemplate {
+            id: id.to_string(),
+            name: format!("Template {}", id),
+            nist_control: "SC-8".to_string(),
+            cci_ref: "CCI-002418".to_string(),
+            test_description: "Verify transmitted data is protected.".to_string(),
+            test_procedure: "Capture traffic between the client and server.".to_string(),
+            expected_result: "Only TLS 1.2 or newer is negotiated.".to_string(),
+            risk_rating: "High".to_string(),
+            system_id: system_id.map(str::to_string),
+            created_date: "2024-01-01T00:00:00Z".to_string(),
+            updated_date: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn new_database_is_seeded_with_global_templates() {
+        let db = db_with_systems(&[]);
+        let templates = db.get_test_case_templates(None).unwrap();
+        assert!(!templates.is_empty());
+        assert!(templates.iter().all(|t| t.system_id.is_none()));
+    }
+
+    #[test]
+    fn system_templates_are_only_visible_to_their_system() {
+        let mut db = db_with_systems(&["a", "b"]);
+        let seeded = db.get_test_case_templates(None).unwrap().len();
+        db.save_test_case_template(&template("tls", Some("a"))).unwrap();
+
+        assert_eq!(db.get_test_case_templates(Some("a")).unwrap().len(), seeded + 1);
+        assert_eq!(db.get_test_case_templates(Some("b")).unwrap().len(), seeded);
+        assert!(db.get_test_case_templates_by_ids(&["tls".to_string()], "b").unwrap().is_empty());
+    }
+
+    #[test]
+    fn delete_is_scoped_to_the_callers_system() {
+        let mut db = db_with_systems(&["a", "b"]);
+        db.save_test_case_template(&template("tls", Some("a"))).unwrap();
+        db.save_test_case_template(&template("global", None)).unwrap();
+
+        assert!(!db.delete_test_case_template("tls", Some("b")).unwrap());
+        assert!(!db.delete_test_case_template("tls", None).unwrap());
+        assert_eq!(db.get_test_case_templates_by_ids(&["tls".to_string()], "a").unwrap().len(), 1);
+
+        assert!(db.delete_test_case_template("tls", Some("a")).unwrap());
+        assert!(db.delete_test_case_template("global", Some("b")).unwrap());
+        assert!(!db.delete_test_case_template("missing", Some("a")).unwrap());
+    }
+}
@@ -0,0 +1,247 @@
+// Fixtures shared by the unit tests: an in-memory database with the full schema and
+// minimal records that tests tweak with struct update syntax.
+use rusqlite::Connection;
+
+use super::nessus::{NessusFinding, NessusScanMeta};
+use super::Database;
+use crate::models::{
+    GroupPOAM, MappedControl, Milestone, STIGMappingData, STIGMappingResult, STIGVulnerability, SecurityTestPlan, System, SystemExportData, SystemGroup, TestCase, POAM,
+};
+
+pub fn memory_db() -> Database {
+    Database::from_connection(Connection::open_in_memory().unwrap()).unwrap()
+}
+
+pub fn system(id: &str) -> System {
+    System {
+        id: id.to_string(),
+        name: format!("System {}", id),
+        description: None,
+        created_date: "2024-01-01T00:00:00Z".to_string(),
+        updated_date: "2024-01-01T00:00:00Z".to_string(),
+        owner: None,
+        classification: None,
+        tags: None,
+        is_active: true,
+        poam_count: None,
+        last_accessed: None,
+        group_id: None,
+    }
+}
+
+pub fn db_with_systems(ids: &[&str]) -> Database {
+    let mut db = memory_db();
+    for id in ids {
+        db.create_system(&system(id)).unwrap();
+    }
+    db
+}
+
+pub fn group(id: &str) -> SystemGroup {
+    SystemGroup {
+        id: id.to_string(),
+        name: format!("Group {}", id),
+        description: None,
+        color: None,
+        created_date: "2024-01-01T00:00:00Z".to_string(),
+        updated_date: "2024-01-01T00:00:00Z".to_string(),
+        created_by: None,
+        is_active: true,
+        system_count: None,
+    }
+}
+
+pub fn poam(id: i64) -> POAM {
+    POAM {
+        id,
+        title: format!("POAM {}", id),
+        description: format!("Description {}", id),
+        start_date: "2024-01-01".to_string(),
+        end_date: "2024-06-30".to_string(),
+        status: "Open".to_string(),
+        priority: "Medium".to_string(),
+        risk_level: "Moderate".to_string(),
+        milestones: Vec::new(),
+        resources: None,
+        source_identifying_vulnerability: None,
+        raw_severity: None,
+        severity: None,
+        relevance_of_threat: None,
+        likelihood: None,
+        impact: None,
+        residual_risk: None,
+        mitigations: None,
+        devices_affected: None,
+        source_stig_mapping_id: None,
+        selected_vulnerabilities: None,
+        responsible_party: None,
+    }
+}
+
+pub fn milestone(id: &str) -> Milestone {
+    Milestone {
+        id: id.to_string(),
+        title: format!("Milestone {}", id),
+        due_date: "2024-03-01".to_string(),
+        status: "Not Started".to_string(),
+        description: String::new(),
+        depends_on: None,
+    }
+}
+
+pub fn group_poam(id: i64, group_id: &str) -> GroupPOAM {
+    GroupPOAM {
+        id,
+        title: format!("Group POAM {}", id),
+        description: format!("Description {}", id),
+        start_date: "2024-01-01".to_string(),
+        end_date: "2024-06-30".to_string(),
+        status: "Open".to_string(),
+        priority: "Medium".to_string(),
+        risk_level: "Moderate".to_string(),
+        group_id: group_id.to_string(),
+        affected_systems: Vec::new(),
+        milestones: Vec::new(),
+        resources: None,
+        source_identifying_vulnerability: None,
+        raw_severity: None,
+        severity: None,
+        relevance_of_threat: None,
+        likelihood: None,
+        impact: None,
+        residual_risk: None,
+        mitigations: None,
+        devices_affected: None,
+    }
+}
+
+pub fn test_case(id: &str, nist_control: &str) -> TestCase {
+    TestCase {
+        id: id.to_string(),
+        nist_control: nist_control.to_string(),
+        cci_ref: String::new(),
+        stig_vuln_id: String::new(),
+        test_description: format!("Test {}", id),
+        test_procedure: String::new(),
+        expected_result: String::new(),
+        actual_result: None,
+        status: "Not Started".to_string(),
+        notes: None,
+        evidence_files: None,
+        tested_by: None,
+        tested_date: None,
+        risk_rating: "Medium".to_string(),
+        evidence_file_names: None,
+    }
+}
+
+pub fn test_plan(id: &str, test_cases: Vec<TestCase>) -> SecurityTestPlan {
+    SecurityTestPlan {
+        id: id.to_string(),
+        name: format!("Plan {}", id),
+        description: None,
+        created_date: "2024-01-01T00:00:00Z".to_string(),
+        updated_date: "2024-01-01T00:00:00Z".to_string(),
+        status: "Draft".to_string(),
+        poam_id: None,
+        stig_mapping_id: None,
+        test_cases,
+        overall_score: None,
+    }
+}
+
+pub fn stig_vuln(vuln_num: &str, status: &str, cci_refs: &[&str]) -> STIGVulnerability {
+    STIGVulnerability {
+        vuln_num: vuln_num.to_string(),
+        severity: "medium".to_string(),
+        rule_id: format!("SV-{}r1_rule", vuln_num.trim_start_matches("V-")),
+        rule_title: format!("Rule for {}", vuln_num),
+        cci_refs: cci_refs.iter().map(|c| c.to_string()).collect(),
+        status: status.to_string(),
+        ..Default::default()
+    }
+}
+
+// One mapped control per (control, vulnerabilities) pair
+pub fn stig_mapping(id: &str, controls: Vec<(&str, Vec<STIGVulnerability>)>) -> STIGMappingData {
+    let mapped_controls: Vec<MappedControl> = controls
+        .into_iter()
+        .map(|(control, stigs)| MappedControl {
+            nist_control: control.to_string(),
+            ccis: stigs.iter().flat_map(|v| v.cci_refs.clone()).collect(),
+            findings_count: stigs.iter().filter(|v| v.status == "Open").count() as i32,
+            compliance_status: if stigs.iter().any(|v| v.status == "Open") { "non-compliant" } else { "compliant" }.to_string(),
+            risk_level: "medium".to_string(),
+            stigs,
+        })
+        .collect();
+
+    STIGMappingData {
+        id: id.to_string(),
+        name: format!("Mapping {}", id),
+        description: None,
+        created_date: "2024-01-01T00:00:00Z".to_string(),
+        updated_date: "2024-01-01T00:00:00Z".to_string(),
+        stig_info: Default::default(),
+        asset_info: Default::default(),
+        mapping_result: STIGMappingResult {
+            total_vulnerabilities: mapped_controls.iter().map(|c| c.stigs.len() as i32).sum(),
+            mapped_controls,
+            summary: Default::default(),
+        },
+        cci_mappings: None,
+    }
+}
+
+// A system backup payload with only the system and its POAMs filled in
+pub fn export_data(system_id: &str, poams: Vec<POAM>) -> SystemExportData {
+    SystemExportData {
+        system: system(system_id),
+        poams,
+        notes: Vec::new(),
+        stig_mappings: None,
+        test_plans: None,
+        prep_lists: None,
+        baseline_controls: None,
+        poam_control_associations: None,
+        nessus_scans: None,
+        nessus_findings: None,
+        nessus_prep_lists: None,
+        poam_references: None,
+        evidence_omitted: None,
+        export_date: Some("2024-07-01T00:00:00Z".to_string()),
+        export_version: Some("1.0".to_string()),
+    }
+}
+
+pub fn nessus_scan(id: &str) -> NessusScanMeta {
+    NessusScanMeta {
+        id: id.to_string(),
+        name: format!("Scan {}", id),
+        description: None,
+        imported_date: "2024-01-01T00:00:00Z".to_string(),
+        version: 1,
+        source_file: None,
+        scan_info: serde_json::json!({}),
+    }
+}
+
+pub fn nessus_finding(id: &str, scan_id: &str, plugin_id: i64, host: &str, severity: &str) -> NessusFinding {
+    NessusFinding {
+        id: id.to_string(),
+        scan_id: scan_id.to_string(),
+        plugin_id: Some(plugin_id),
+        plugin_name: Some(format!("Plugin {}", plugin_id)),
+        severity: Some(severity.to_string()),
+        risk_factor: None,
+        cve: None,
+        cvss_base_score: None,
+        host: Some(host.to_string()),
+        port: Some(443),
+        protocol: Some("tcp".to_string()),
+        synopsis: None,
+        description: None,
+        solution: None,
+        raw_json: serde_json::json!({}),
+    }
+}
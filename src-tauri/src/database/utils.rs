@@ -22,6 +22,15 @@ pub enum DatabaseError {
 
     #[error("Not Found: {0}")]
     NotFound(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Invalid data: {0}")]
+    Invalid(String),
+
+    #[error("Database schema version {found} is newer than this application supports ({supported}). Please update the application.")]
+    SchemaTooNew { found: i32, supported: i32 },
 }
 
 // Function to normalize date formats for storage
@@ -56,6 +56,14 @@ pub fn normalize_date_format(date_str: &str) -> String {
     date_str.to_string()
 }
 
+/// Normalize a date string and confirm it is a real calendar date, returning `YYYY-MM-DD`
+pub fn parse_iso_date(date_str: &str) -> Option<String> {
+    let normalized = normalize_date_format(date_str.trim());
+    chrono::NaiveDate::parse_from_str(&normalized, "%Y-%m-%d")
+        .ok()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
 // Helper function to split date strings by slashes or dashes
 fn parse_date_slashes(date_str: &str) -> Option<Vec<&str>> {
     let parts: Vec<&str>;
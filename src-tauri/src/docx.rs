@@ -0,0 +1,73 @@
+use std::fs;
+use docx_rs::{AlignmentType, BreakType, Docx, Paragraph, Run, Table, TableCell, TableRow};
+
+// Small builder over docx-rs for report exports, so callers only deal with headings,
+// labeled values, plain paragraphs and bordered tables.
+
+#[derive(Default)]
+pub struct DocxDocument {
+    docx: Docx,
+}
+
+impl DocxDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, paragraph: Paragraph) {
+        self.docx = std::mem::take(&mut self.docx).add_paragraph(paragraph);
+    }
+
+    pub fn heading(&mut self, text: &str, size_half_points: usize) {
+        self.push(Paragraph::new().add_run(text_run(text).bold().size(size_half_points)));
+    }
+
+    // Centered bold line used for classification markings at the top and bottom of a document
+    pub fn banner(&mut self, text: &str) {
+        self.push(Paragraph::new().align(AlignmentType::Center).add_run(text_run(text).bold()));
+    }
+
+    pub fn paragraph(&mut self, text: &str) {
+        self.push(Paragraph::new().add_run(text_run(text)));
+    }
+
+    // Renders "Label: value" with a bold label
+    pub fn labeled(&mut self, label: &str, value: &str) {
+        self.push(
+            Paragraph::new()
+                .add_run(Run::new().add_text(format!("{}: ", label)).bold())
+                .add_run(text_run(value)),
+        );
+    }
+
+    pub fn table(&mut self, headers: &[&str], rows: &[Vec<String>]) {
+        let cell = |run: Run| TableCell::new().add_paragraph(Paragraph::new().add_run(run));
+
+        let mut table_rows = vec![TableRow::new(headers.iter().map(|h| cell(text_run(h).bold())).collect())];
+        table_rows.extend(rows.iter().map(|row| TableRow::new(row.iter().map(|value| cell(text_run(value))).collect())));
+
+        self.docx = std::mem::take(&mut self.docx).add_table(Table::new(table_rows));
+        // Word requires a paragraph between consecutive tables and before the section end
+        self.push(Paragraph::new());
+    }
+
+    pub fn write<W: std::io::Write + std::io::Seek>(self, writer: W) -> std::io::Result<()> {
+        self.docx.pack(writer).map_err(std::io::Error::from)
+    }
+
+    pub fn save(self, path: &str) -> std::io::Result<()> {
+        self.write(fs::File::create(path)?)
+    }
+}
+
+// Multi-line values become text segments joined by line breaks
+fn text_run(text: &str) -> Run {
+    let mut run = Run::new();
+    for (index, line) in text.lines().enumerate() {
+        if index > 0 {
+            run = run.add_break(BreakType::TextWrapping);
+        }
+        run = run.add_text(line);
+    }
+    run
+}
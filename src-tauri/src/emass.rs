@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::io::Read;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use serde::Serialize;
+use crate::date_utils;
+use crate::models::{derive_priority, normalize_severity, Milestone, RiskLevel, POAM};
+
+// Reader for eMASS POA&M exports. eMASS puts a few lines of system metadata above the
+// column headers, so the header row is located by its "Control Vulnerability Description"
+// cell and every row after it is treated as one POA&M item.
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmassError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("XLSX error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("XLSX parsing error: {0}")]
+    Xml(String),
+
+    #[error("Invalid eMASS file: {0}")]
+    InvalidFormat(String),
+}
+
+/// A spreadsheet row that could not be turned into a POAM; `row` is 1-based as shown in Excel.
+#[derive(Debug, Serialize, Clone)]
+pub struct EmassSkippedRow {
+    pub row: usize,
+    pub reason: String,
+}
+
+/// POAMs parsed from an eMASS export, with ids left at 0 for the caller to assign.
+#[derive(Debug)]
+pub struct EmassParseResult {
+    pub poams: Vec<POAM>,
+    pub skipped: Vec<EmassSkippedRow>,
+}
+
+/// Reads every row of a CSV or XLSX file as plain strings.
+pub fn read_rows(file_path: &str) -> Result<Vec<Vec<String>>, EmassError> {
+    if file_path.to_lowercase().ends_with(".xlsx") {
+        return read_xlsx_rows(file_path);
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(file_path)?;
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        rows.push(record?.iter().map(|cell| cell.trim_start_matches('\u{feff}').to_string()).collect());
+    }
+    Ok(rows)
+}
+
+// Lowercase letters and digits only, so "POA&M Item ID" and "Controls / APs" compare loosely
+fn normalize_header(header: &str) -> String {
+    header.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+pub fn parse_poams(rows: &[Vec<String>]) -> Result<EmassParseResult, EmassError> {
+    let header_index = rows.iter()
+        .position(|row| row.iter().any(|cell| normalize_header(cell) == "controlvulnerabilitydescription"))
+        .ok_or_else(|| EmassError::InvalidFormat("no \"Control Vulnerability Description\" header row found".to_string()))?;
+
+    let columns: HashMap<String, usize> = rows[header_index].iter()
+        .enumerate()
+        .map(|(i, header)| (normalize_header(header), i))
+        .collect();
+    if !columns.contains_key("scheduledcompletiondate") {
+        return Err(EmassError::InvalidFormat("missing \"Scheduled Completion Date\" column".to_string()));
+    }
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut result = EmassParseResult { poams: Vec::new(), skipped: Vec::new() };
+
+    for (offset, row) in rows[header_index + 1..].iter().enumerate() {
+        let row_number = header_index + offset + 2;
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+
+        let cell = |name: &str| -> Option<String> {
+            columns.get(name)
+                .and_then(|&i| row.get(i))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let Some(description) = cell("controlvulnerabilitydescription") else {
+            result.skipped.push(EmassSkippedRow { row: row_number, reason: "Control Vulnerability Description is blank".to_string() });
+            continue;
+        };
+        let Some(end_date) = cell("scheduledcompletiondate").as_deref().and_then(parse_emass_date) else {
+            result.skipped.push(EmassSkippedRow {
+                row: row_number,
+                reason: format!("Scheduled Completion Date '{}' is not a date", cell("scheduledcompletiondate").unwrap_or_default()),
+            });
+            continue;
+        };
+
+        let status = map_status(cell("status").as_deref());
+        let first_line: String = description.lines().next().unwrap_or_default().chars().take(120).collect();
+        let title = match cell("controlsaps") {
+            Some(controls) => format!("{}: {}", controls, first_line),
+            None => first_line,
+        };
+        let residual_risk = cell("residualrisklevel");
+        // Severity columns may hold CAT levels, so they are normalized before being read as a risk level
+        let rated = residual_risk.clone().or_else(|| {
+            cell("severity").or_else(|| cell("rawseverity"))
+                .map(|severity| normalize_severity(&severity).map(str::to_string).unwrap_or(severity))
+        });
+        let risk_level = match rated.as_deref().map(str::parse::<RiskLevel>) {
+            Some(Ok(level)) => level.to_string(),
+            Some(Err(reason)) => {
+                result.skipped.push(EmassSkippedRow { row: row_number, reason });
+                continue;
+            }
+            None => {
+                result.skipped.push(EmassSkippedRow {
+                    row: row_number,
+                    reason: "Residual Risk Level, Severity and Raw Severity are all blank".to_string(),
+                });
+                continue;
+            }
+        };
+        // Rows rated only by residual risk still get a priority from it
+        let priority = derive_priority(&cell("severity").or_else(|| cell("rawseverity")).unwrap_or_default(), Some(&risk_level));
+        let milestones = cell("milestonewithcompletiondates")
+            .map(|text| parse_milestones(&text, &end_date, &status))
+            .unwrap_or_default();
+
+        result.poams.push(POAM {
+            id: 0,
+            title,
+            description,
+            start_date: today.clone(),
+            end_date,
+            status,
+            priority,
+            risk_level,
+            milestones,
+            resources: cell("resourcesrequired"),
+            source_identifying_vulnerability: cell("sourceidentifyingvulnerability"),
+            responsible_party: cell("pocname").or_else(|| cell("pocorganization")),
+            raw_severity: cell("rawseverity"),
+            severity: cell("severity"),
+            relevance_of_threat: cell("relevanceofthreat"),
+            likelihood: cell("likelihood"),
+            impact: cell("impact"),
+            residual_risk,
+            mitigations: cell("mitigations"),
+            devices_affected: cell("devicesaffected"),
+            source_stig_mapping_id: None,
+            selected_vulnerabilities: None,
+        });
+    }
+
+    Ok(result)
+}
+
+fn map_status(status: Option<&str>) -> String {
+    match status.map(|s| s.trim().to_lowercase()).as_deref() {
+        None | Some("") => "Not Started".to_string(),
+        Some("ongoing") => "In Progress".to_string(),
+        Some("completed") | Some("archived") => "Completed".to_string(),
+        Some("risk accepted") => "Risk Accepted".to_string(),
+        Some(_) => status.unwrap_or_default().trim().to_string(),
+    }
+}
+
+// Accepts the usual text dates plus Excel serial day numbers, which XLSX date cells hold
+fn parse_emass_date(value: &str) -> Option<String> {
+    if let Some(date) = date_utils::parse_iso_date(value) {
+        return Some(date);
+    }
+    let serial = value.trim().parse::<f64>().ok().filter(|days| (1.0..100_000.0).contains(days))?;
+    let epoch = chrono::NaiveDate::from_ymd_opt(1899, 12, 30)?;
+    epoch.checked_add_signed(chrono::Duration::days(serial.trunc() as i64))
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+// eMASS keeps all milestones in one cell, one per line, typically
+// "1. Apply vendor patch (03/15/2024)" or "Milestone 2: Verify fix - Scheduled Completion Date: 2024-04-01".
+// Lines without a date inherit the POAM's scheduled completion date.
+fn parse_milestones(text: &str, poam_end_date: &str, poam_status: &str) -> Vec<Milestone> {
+    let prefix = regex::Regex::new(r"(?i)^\s*(?:milestone\s*)?#?\d+\s*[:.)\-]\s*").expect("valid milestone prefix pattern");
+    let date = regex::Regex::new(r"\d{1,2}/\d{1,2}/\d{4}|\d{4}-\d{2}-\d{2}").expect("valid milestone date pattern");
+    let label = regex::Regex::new(r"(?i)(?:scheduled\s+)?completion\s+date\s*:?").expect("valid milestone label pattern");
+    let status = if poam_status == "Completed" { "Completed" } else { "Not Started" };
+
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let due_date = date.find(line)
+                .and_then(|m| date_utils::parse_iso_date(m.as_str()))
+                .unwrap_or_else(|| poam_end_date.to_string());
+            let without_prefix = prefix.replace(line, "");
+            let without_date = date.replace_all(&without_prefix, "");
+            let cleaned = label.replace_all(&without_date, "");
+            let title = cleaned.trim_matches(|c: char| c.is_whitespace() || "-:;,()[]".contains(c)).to_string();
+            Milestone {
+                id: uuid::Uuid::new_v4().to_string(),
+                title: if title.is_empty() { format!("Milestone {}", i + 1) } else { title },
+                due_date,
+                status: status.to_string(),
+                description: line.to_string(),
+                depends_on: None,
+            }
+        })
+        .collect()
+}
+
+// Minimal XLSX reader: the first worksheet's cell values, with shared strings resolved.
+// Formatting is ignored, so date cells come back as Excel serial numbers.
+fn read_xlsx_rows(file_path: &str) -> Result<Vec<Vec<String>>, EmassError> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(file_path)?)?;
+
+    let shared_strings = match read_zip_entry(&mut archive, "xl/sharedStrings.xml")? {
+        Some(xml) => parse_shared_strings(&xml)?,
+        None => Vec::new(),
+    };
+
+    let mut sheet_names: Vec<String> = archive.file_names()
+        .filter(|name| name.starts_with("xl/worksheets/sheet") && name.ends_with(".xml"))
+        .map(str::to_string)
+        .collect();
+    // sheet1.xml, sheet2.xml, ... ordered numerically
+    sheet_names.sort_by_key(|name| {
+        name.trim_start_matches("xl/worksheets/sheet").trim_end_matches(".xml").parse::<u32>().unwrap_or(u32::MAX)
+    });
+    let sheet_name = sheet_names.first()
+        .ok_or_else(|| EmassError::InvalidFormat("workbook has no worksheets".to_string()))?
+        .clone();
+    let sheet = read_zip_entry(&mut archive, &sheet_name)?.unwrap_or_default();
+
+    parse_sheet(&sheet, &shared_strings)
+}
+
+fn read_zip_entry(archive: &mut zip::ZipArchive<std::fs::File>, name: &str) -> Result<Option<String>, EmassError> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(Some(content))
+}
+
+fn parse_shared_strings(xml: &str) -> Result<Vec<String>, EmassError> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"si" => current.clear(),
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_text => {
+                current.push_str(&e.unescape().unwrap_or_default());
+            }
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"t" => in_text = false,
+                b"si" => strings.push(std::mem::take(&mut current)),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"si" => strings.push(String::new()),
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(EmassError::Xml(format!("Error at position {}: {:?}", reader.buffer_position(), e))),
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+// "AB12" -> 27 (zero-based column index)
+fn column_index(cell_ref: &str) -> Option<usize> {
+    let letters: String = cell_ref.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if letters.is_empty() {
+        return None;
+    }
+    Some(letters.to_ascii_uppercase().bytes().fold(0usize, |acc, b| acc * 26 + (b - b'A' + 1) as usize) - 1)
+}
+
+fn parse_sheet(xml: &str, shared_strings: &[String]) -> Result<Vec<Vec<String>>, EmassError> {
+    let mut reader = Reader::from_str(xml);
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut cell_column = 0usize;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"row" => row = Vec::new(),
+                b"c" => {
+                    cell_column = row.len();
+                    cell_type.clear();
+                    value.clear();
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"r" => {
+                                if let Some(index) = column_index(&String::from_utf8_lossy(&attr.value)) {
+                                    cell_column = index;
+                                }
+                            }
+                            b"t" => cell_type = String::from_utf8_lossy(&attr.value).to_string(),
+                            _ => {}
+                        }
+                    }
+                }
+                b"v" | b"t" => in_value = true,
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_value => {
+                value.push_str(&e.unescape().unwrap_or_default());
+            }
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"v" | b"t" => in_value = false,
+                b"c" => {
+                    let resolved = if cell_type == "s" {
+                        value.trim().parse::<usize>().ok()
+                            .and_then(|i| shared_strings.get(i))
+                            .cloned()
+                            .unwrap_or_default()
+                    } else {
+                        std::mem::take(&mut value)
+                    };
+                    if row.len() <= cell_column {
+                        row.resize(cell_column + 1, String::new());
+                    }
+                    row[cell_column] = resolved;
+                }
+                b"row" => rows.push(std::mem::take(&mut row)),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"row" => rows.push(Vec::new()),
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(EmassError::Xml(format!("Error at position {}: {:?}", reader.buffer_position(), e))),
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_poams, read_rows};
+
+    // Trimmed eMASS POA&M export: metadata lines above the header, then one row per item
+    const EMASS_EXPORT: &str = "\
+System Name,Test System,,,,,,,,
+Date Exported,2024-05-01,,,,,,,,
+POA&M Item ID,Control Vulnerability Description,Controls / APs,Status,Scheduled Completion Date,Milestone with Completion Dates,Raw Severity,Residual Risk Level,POC Name,Source Identifying Vulnerability
+1,Passwords are not rotated,IA-5,Ongoing,06/30/2024,\"1. Update password policy (03/15/2024)
+Milestone 2: Verify enforcement - Scheduled Completion Date: 2024-05-01\",CAT I,,ISSO,V-1001
+2,Audit logs are not reviewed,AU-6,,45473,,Low,Moderate,,
+3,Unrated finding,AC-2,Ongoing,2024-06-30,,Extreme,,,
+4,,AC-3,Ongoing,2024-06-30,,Low,,,
+5,Bad date,AC-4,Ongoing,sometime,,Low,,,
+6,Residual risk only,AC-5,Ongoing,2024-06-30,,,High,,
+,,,,,,,,,
+";
+
+    #[test]
+    fn parses_an_emass_export_and_reports_unusable_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emass.csv");
+        std::fs::write(&path, format!("\u{feff}{}", EMASS_EXPORT)).unwrap();
+
+        let parsed = parse_poams(&read_rows(path.to_str().unwrap()).unwrap()).unwrap();
+        assert_eq!(parsed.poams.len(), 3);
+
+        let passwords = &parsed.poams[0];
+        assert_eq!(passwords.title, "IA-5: Passwords are not rotated");
+        assert_eq!((passwords.status.as_str(), passwords.end_date.as_str()), ("In Progress", "2024-06-30"));
+        assert_eq!((passwords.risk_level.as_str(), passwords.raw_severity.as_deref()), ("High", Some("CAT I")));
+        assert_eq!(passwords.responsible_party.as_deref(), Some("ISSO"));
+        assert_eq!(passwords.source_identifying_vulnerability.as_deref(), Some("V-1001"));
+        let milestones: Vec<(&str, &str, &str)> = passwords.milestones.iter().map(|m| (m.title.as_str(), m.due_date.as_str(), m.status.as_str())).collect();
+        assert_eq!(milestones, [("Update password policy", "2024-03-15", "Not Started"), ("Verify enforcement", "2024-05-01", "Not Started")]);
+
+        // Residual risk wins over raw severity, the date is an Excel serial and a blank status is Not Started
+        let audit = &parsed.poams[1];
+        assert_eq!((audit.risk_level.as_str(), audit.end_date.as_str(), audit.status.as_str()), ("Moderate", "2024-06-30", "Not Started"));
+        assert!(audit.milestones.is_empty() && audit.responsible_party.is_none());
+
+        // Without a severity the priority comes from the residual risk alone
+        let residual_only = &parsed.poams[2];
+        assert_eq!((residual_only.risk_level.as_str(), residual_only.priority.as_str()), ("High", "High"));
+        assert_eq!(passwords.priority, "High");
+
+        let skipped: Vec<usize> = parsed.skipped.iter().map(|s| s.row).collect();
+        assert_eq!(skipped, [6, 7, 8]);
+        assert!(parsed.skipped[0].reason.contains("Extreme"), "{}", parsed.skipped[0].reason);
+        assert_eq!(parsed.skipped[1].reason, "Control Vulnerability Description is blank");
+        assert_eq!(parsed.skipped[2].reason, "Scheduled Completion Date 'sometime' is not a date");
+    }
+
+    #[test]
+    fn export_without_the_header_row_is_rejected() {
+        let rows = vec![vec!["Title".to_string(), "Due".to_string()]];
+        assert!(parse_poams(&rows).is_err());
+    }
+}
+
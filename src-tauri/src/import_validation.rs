@@ -0,0 +1,280 @@
+use serde_json::Value;
+
+// Walks an import file as raw JSON and describes every field that would stop it
+// from deserializing into POAMData, using the key names as they appear in the file.
+
+#[derive(Clone, Copy)]
+enum Kind {
+    String,
+    Integer,
+    Object,
+    Array,
+}
+
+impl Kind {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Kind::String => value.is_string(),
+            Kind::Integer => value.is_i64(),
+            Kind::Object => value.is_object(),
+            Kind::Array => value.is_array(),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Kind::String => "a string",
+            Kind::Integer => "an integer",
+            Kind::Object => "an object",
+            Kind::Array => "an array",
+        }
+    }
+}
+
+struct Field {
+    name: &'static str,
+    kind: Kind,
+    required: bool,
+}
+
+const fn required(name: &'static str, kind: Kind) -> Field {
+    Field { name, kind, required: true }
+}
+
+const fn optional(name: &'static str, kind: Kind) -> Field {
+    Field { name, kind, required: false }
+}
+
+const POAM_FIELDS: &[Field] = &[
+    required("id", Kind::Integer),
+    required("title", Kind::String),
+    required("description", Kind::String),
+    required("startDate", Kind::String),
+    required("endDate", Kind::String),
+    required("status", Kind::String),
+    required("priority", Kind::String),
+    required("riskLevel", Kind::String),
+    required("milestones", Kind::Array),
+    optional("resources", Kind::String),
+    optional("sourceIdentifyingVulnerability", Kind::String),
+    optional("rawSeverity", Kind::String),
+    optional("severity", Kind::String),
+    optional("relevanceOfThreat", Kind::String),
+    optional("likelihood", Kind::String),
+    optional("impact", Kind::String),
+    optional("residualRisk", Kind::String),
+    optional("mitigations", Kind::String),
+    optional("devicesAffected", Kind::String),
+    optional("sourceStigMappingId", Kind::String),
+    optional("selectedVulnerabilities", Kind::Array),
+];
+
+const MILESTONE_FIELDS: &[Field] = &[
+    required("id", Kind::String),
+    required("title", Kind::String),
+    required("dueDate", Kind::String),
+    required("status", Kind::String),
+    required("description", Kind::String),
+];
+
+const NOTE_FIELDS: &[Field] = &[
+    required("id", Kind::String),
+    required("title", Kind::String),
+    required("content", Kind::String),
+    required("date", Kind::String),
+    optional("poam_ids", Kind::Array),
+    optional("poam_titles", Kind::Array),
+    optional("folder", Kind::String),
+    optional("tags", Kind::Array),
+];
+
+const STIG_MAPPING_FIELDS: &[Field] = &[
+    required("id", Kind::String),
+    required("name", Kind::String),
+    optional("description", Kind::String),
+    required("created_date", Kind::String),
+    required("updated_date", Kind::String),
+    required("stig_info", Kind::Object),
+    required("asset_info", Kind::Object),
+    required("mapping_result", Kind::Object),
+    optional("cci_mappings", Kind::Array),
+];
+
+/// Returns one message per problem, e.g. "poams[3].endDate is missing".
+/// An empty list means the structure matches what the importer expects.
+pub fn validate_poam_data(value: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if !value.is_object() {
+        problems.push("The file must contain a JSON object with \"poams\" and \"notes\"".to_string());
+        return problems;
+    }
+
+    let root_fields = [
+        required("poams", Kind::Array),
+        required("notes", Kind::Array),
+        optional("stig_mappings", Kind::Array),
+    ];
+    check_fields(value, "", &root_fields, &mut problems);
+
+    for (i, poam) in array_items(value, "poams").iter().enumerate() {
+        let path = format!("poams[{}]", i);
+        if check_object(poam, &path, POAM_FIELDS, &mut problems) {
+            for (j, milestone) in array_items(poam, "milestones").iter().enumerate() {
+                check_object(milestone, &format!("{}.milestones[{}]", path, j), MILESTONE_FIELDS, &mut problems);
+            }
+            check_elements(poam, &path, "selectedVulnerabilities", Kind::String, &mut problems);
+        }
+    }
+
+    for (i, note) in array_items(value, "notes").iter().enumerate() {
+        let path = format!("notes[{}]", i);
+        if check_object(note, &path, NOTE_FIELDS, &mut problems) {
+            check_elements(note, &path, "poam_ids", Kind::Integer, &mut problems);
+            check_elements(note, &path, "poam_titles", Kind::String, &mut problems);
+            check_elements(note, &path, "tags", Kind::String, &mut problems);
+        }
+    }
+
+    for (i, mapping) in array_items(value, "stig_mappings").iter().enumerate() {
+        check_object(mapping, &format!("stig_mappings[{}]", i), STIG_MAPPING_FIELDS, &mut problems);
+    }
+
+    problems
+}
+
+fn array_items<'v>(value: &'v Value, key: &str) -> &'v [Value] {
+    value.get(key).and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+// Returns false when the value is not an object, so callers skip its children
+fn check_object(value: &Value, path: &str, fields: &[Field], problems: &mut Vec<String>) -> bool {
+    if !value.is_object() {
+        problems.push(format!("{} should be an object", path));
+        return false;
+    }
+    check_fields(value, path, fields, problems);
+    true
+}
+
+fn check_fields(value: &Value, path: &str, fields: &[Field], problems: &mut Vec<String>) {
+    for field in fields {
+        match value.get(field.name) {
+            None | Some(Value::Null) if field.required => {
+                problems.push(format!("{} is missing", join_path(path, field.name)));
+            }
+            None | Some(Value::Null) => {}
+            Some(found) if !field.kind.matches(found) => {
+                problems.push(format!(
+                    "{} should be {} but found {}",
+                    join_path(path, field.name),
+                    field.kind.describe(),
+                    describe_value(found)
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+fn check_elements(value: &Value, path: &str, key: &str, kind: Kind, problems: &mut Vec<String>) {
+    for (i, element) in array_items(value, key).iter().enumerate() {
+        if !kind.matches(element) {
+            problems.push(format!(
+                "{}[{}] should be {} but found {}",
+                join_path(path, key),
+                i,
+                kind.describe(),
+                describe_value(element)
+            ));
+        }
+    }
+}
+
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => format!("boolean {}", b),
+        Value::Number(n) => format!("number {}", n),
+        Value::String(s) if s.chars().count() > 40 => {
+            format!("string \"{}...\"", s.chars().take(40).collect::<String>())
+        }
+        Value::String(s) => format!("string \"{}\"", s),
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_poam_data;
+    use serde_json::json;
+
+    fn poam(id: i64) -> serde_json::Value {
+        json!({
+            "id": id,
+            "title": "Patch web servers",
+            "description": "Apply vendor patches",
+            "startDate": "2024-01-01",
+            "endDate": "2024-06-30",
+            "status": "Open",
+            "priority": "High",
+            "riskLevel": "Moderate",
+            "milestones": [
+                { "id": "m-1", "title": "Test", "dueDate": "2024-02-01", "status": "Not Started", "description": "" }
+            ]
+        })
+    }
+
+    #[test]
+    fn valid_file_has_no_problems_and_deserializes() {
+        let file = json!({ "poams": [poam(1)], "notes": [] });
+        assert!(validate_poam_data(&file).is_empty());
+        assert!(serde_json::from_value::<crate::models::POAMData>(file).is_ok());
+    }
+
+    #[test]
+    fn missing_required_field_names_its_path() {
+        let mut broken = poam(4);
+        broken.as_object_mut().unwrap().remove("endDate");
+        broken["milestones"][0].as_object_mut().unwrap().remove("dueDate");
+        let file = json!({ "poams": [poam(1), poam(2), poam(3), broken], "notes": [] });
+
+        assert_eq!(
+            validate_poam_data(&file),
+            vec!["poams[3].endDate is missing", "poams[3].milestones[0].dueDate is missing"]
+        );
+    }
+
+    #[test]
+    fn wrong_type_names_path_and_found_value() {
+        let mut broken = poam(1);
+        broken["id"] = json!("POAM-1");
+        let file = json!({
+            "poams": [broken],
+            "notes": [{ "id": "n-1", "title": "t", "content": "", "date": "2024-01-01", "poam_ids": [1, "2"] }]
+        });
+
+        assert_eq!(
+            validate_poam_data(&file),
+            vec![
+                "poams[0].id should be an integer but found string \"POAM-1\"",
+                "notes[0].poam_ids[1] should be an integer but found string \"2\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn non_object_root_and_missing_collections_are_reported() {
+        assert_eq!(validate_poam_data(&json!([])).len(), 1);
+        assert_eq!(validate_poam_data(&json!({})), vec!["poams is missing", "notes is missing"]);
+    }
+}
@@ -9,6 +9,28 @@ pub struct POAMData {
     pub stig_mappings: Option<Vec<STIGMappingData>>,
 }
 
+/// How an import treats data already stored for the system.
+/// `Replace` deletes every POAM and note in the system first; `Merge` upserts by id
+/// and leaves everything else untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    #[default]
+    Replace,
+    Merge,
+}
+
+// Criteria for exporting a subset of POAMs. Empty or missing lists match everything;
+// the end date bounds are inclusive and compared as YYYY-MM-DD.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct POAMExportFilter {
+    pub statuses: Option<Vec<String>>,
+    pub risk_levels: Option<Vec<String>>,
+    pub priorities: Option<Vec<String>>,
+    pub end_date_from: Option<String>,
+    pub end_date_to: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct POAM {
     pub id: i64,
@@ -51,6 +73,238 @@ pub struct POAM {
     pub source_stig_mapping_id: Option<String>,
     #[serde(rename = "selectedVulnerabilities", skip_serializing_if = "Option::is_none")]
     pub selected_vulnerabilities: Option<Vec<String>>, // Array of vuln_num values
+    #[serde(rename = "responsibleParty", skip_serializing_if = "Option::is_none")]
+    pub responsible_party: Option<String>,
+}
+
+// Previous value of a tracked POAM field, recorded when update_poam changes it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct POAMFieldChange {
+    pub id: i64,
+    pub poam_id: i64,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_at: String,
+}
+
+/// Fields to overwrite on every selected POAM; `None` leaves the stored value unchanged.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct POAMBulkChanges {
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    #[serde(rename = "riskLevel")]
+    pub risk_level: Option<String>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<String>,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<String>,
+    pub resources: Option<String>,
+    pub severity: Option<String>,
+    #[serde(rename = "relevanceOfThreat")]
+    pub relevance_of_threat: Option<String>,
+    pub likelihood: Option<String>,
+    pub impact: Option<String>,
+    #[serde(rename = "residualRisk")]
+    pub residual_risk: Option<String>,
+    pub mitigations: Option<String>,
+    #[serde(rename = "devicesAffected")]
+    pub devices_affected: Option<String>,
+}
+
+/// POAM priority, ordered so that `Critical > High > Medium > Low`.
+/// Parsing is case-insensitive and rejects anything outside that vocabulary; deserializing
+/// stays lenient for legacy data and reads unknown or blank values as `Low`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl std::str::FromStr for Priority {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "critical" => Ok(Priority::Critical),
+            "high" => Ok(Priority::High),
+            "medium" => Ok(Priority::Medium),
+            "low" => Ok(Priority::Low),
+            _ => Err(format!("Unknown priority '{}'; expected Low, Medium, High or Critical", value.trim())),
+        }
+    }
+}
+
+impl Priority {
+    /// Reads a stored or imported priority, falling back to `Low` with a warning
+    pub fn from_legacy(value: &str) -> Self {
+        value.parse().unwrap_or_else(|_| {
+            println!("Warning: unknown priority '{}' read as Low", value.trim());
+            Priority::Low
+        })
+    }
+}
+
+impl From<String> for Priority {
+    fn from(value: String) -> Self {
+        Priority::from_legacy(&value)
+    }
+}
+
+impl From<Priority> for String {
+    fn from(value: Priority) -> Self {
+        value.to_string()
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+            Priority::Critical => "Critical",
+        })
+    }
+}
+
+/// Finding severity to POAM priority. Severity labels cover STIG categories,
+/// Nessus risk factors and the free-text values used on POAMs.
+pub const SEVERITY_PRIORITY_MAP: &[(&str, Priority)] = &[
+    ("critical", Priority::High),
+    ("high", Priority::High),
+    ("cat i", Priority::High),
+    ("very high", Priority::High),
+    ("medium", Priority::Medium),
+    ("moderate", Priority::Medium),
+    ("cat ii", Priority::Medium),
+    ("low", Priority::Low),
+    ("cat iii", Priority::Low),
+    ("very low", Priority::Low),
+    ("info", Priority::Low),
+    ("informational", Priority::Low),
+    ("none", Priority::Low),
+];
+
+/// Residual risk that raises the derived priority; it never lowers it.
+pub const RESIDUAL_RISK_PRIORITY_MAP: &[(&str, Priority)] = &[
+    ("critical", Priority::Critical),
+    ("very high", Priority::Critical),
+    ("high", Priority::High),
+    ("medium", Priority::Medium),
+    ("moderate", Priority::Medium),
+];
+
+fn lookup_priority(table: &[(&str, Priority)], value: &str) -> Option<Priority> {
+    let value = value.trim().to_lowercase();
+    table.iter().find(|(label, _)| *label == value).map(|(_, priority)| *priority)
+}
+
+/// Priority for a POAM generated from a finding, e.g. a High severity finding becomes High priority
+pub fn derive_priority(severity: &str, residual_risk: Option<&str>) -> String {
+    let from_severity = lookup_priority(SEVERITY_PRIORITY_MAP, severity).unwrap_or(Priority::Low);
+    let from_residual = residual_risk.and_then(|risk| lookup_priority(RESIDUAL_RISK_PRIORITY_MAP, risk));
+
+    from_residual.map_or(from_severity, |residual| residual.max(from_severity)).to_string()
+}
+
+/// Checks the ratings a user entered on a POAM. A blank priority is allowed when the POAM has a
+/// severity to derive it from, and values unchanged from the stored POAM are not rechecked so
+/// legacy POAMs can still be saved.
+pub fn check_entered_ratings(poam: &POAM, stored: Option<&POAM>) -> Result<(), String> {
+    let has_severity = poam.severity.as_deref().or(poam.raw_severity.as_deref()).is_some_and(|s| !s.trim().is_empty());
+    let priority_derived = has_severity && poam.priority.trim().is_empty();
+    if !priority_derived && stored.is_none_or(|s| s.priority != poam.priority) {
+        poam.priority.parse::<Priority>()?;
+    }
+    if stored.is_none_or(|s| s.risk_level != poam.risk_level) {
+        poam.risk_level.parse::<RiskLevel>()?;
+    }
+    Ok(())
+}
+
+/// Canonical severity label ("Critical", "High", "Medium", "Low" or "Info") for any casing,
+/// STIG category or Nessus numeric severity; None when the value is not recognized.
+pub fn normalize_severity(value: &str) -> Option<&'static str> {
+    match value.trim().to_lowercase().as_str() {
+        "critical" | "4" => Some("Critical"),
+        "high" | "cat i" | "cat 1" | "3" => Some("High"),
+        "medium" | "moderate" | "cat ii" | "cat 2" | "2" => Some("Medium"),
+        "low" | "cat iii" | "cat 3" | "1" => Some("Low"),
+        "info" | "informational" | "none" | "0" => Some("Info"),
+        _ => None,
+    }
+}
+
+/// POAM risk level on the Very Low to Very High scale used by eMASS and OSCAL, plus Critical,
+/// ordered from `VeryLow` up. Parsing is case-insensitive and accepts "Medium" for `Moderate`;
+/// anything else is rejected. Deserializing reads unknown or blank legacy values as `Low`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum RiskLevel {
+    VeryLow,
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+    Critical,
+}
+
+impl std::str::FromStr for RiskLevel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "critical" => Ok(RiskLevel::Critical),
+            "very high" | "very_high" | "very-high" | "veryhigh" => Ok(RiskLevel::VeryHigh),
+            "high" => Ok(RiskLevel::High),
+            "moderate" | "medium" => Ok(RiskLevel::Moderate),
+            "low" => Ok(RiskLevel::Low),
+            "very low" | "very_low" | "very-low" | "verylow" => Ok(RiskLevel::VeryLow),
+            _ => Err(format!(
+                "Unknown risk level '{}'; expected Very Low, Low, Moderate, High, Very High or Critical",
+                value.trim()
+            )),
+        }
+    }
+}
+
+impl RiskLevel {
+    /// Reads a stored or imported risk level, falling back to `Low` with a warning
+    pub fn from_legacy(value: &str) -> Self {
+        value.parse().unwrap_or_else(|_| {
+            println!("Warning: unknown risk level '{}' read as Low", value.trim());
+            RiskLevel::Low
+        })
+    }
+}
+
+impl From<String> for RiskLevel {
+    fn from(value: String) -> Self {
+        RiskLevel::from_legacy(&value)
+    }
+}
+
+impl From<RiskLevel> for String {
+    fn from(value: RiskLevel) -> Self {
+        value.to_string()
+    }
+}
+
+impl std::fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RiskLevel::VeryLow => "Very Low",
+            RiskLevel::Low => "Low",
+            RiskLevel::Moderate => "Moderate",
+            RiskLevel::High => "High",
+            RiskLevel::VeryHigh => "Very High",
+            RiskLevel::Critical => "Critical",
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -61,6 +315,86 @@ pub struct Milestone {
     pub due_date: String,
     pub status: String,
     pub description: String,
+    /// Ids of milestones in the same POAM that must be finished first
+    #[serde(rename = "dependsOn", default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>,
+}
+
+/// Orders milestones so each comes after everything it depends on, keeping the stored (due date)
+/// order among milestones that are free to go. Fails on a dependency that isn't one of the POAM's
+/// milestones, or on a cycle, which is spelled out as "A -> B -> A" where A waits on B.
+pub fn order_milestones_by_dependencies(milestones: &[Milestone]) -> Result<Vec<Milestone>, String> {
+    let index: std::collections::HashMap<&str, usize> = milestones.iter()
+        .enumerate()
+        .map(|(i, m)| (m.id.as_str(), i))
+        .collect();
+    let label = |i: usize| format!("'{}' ({})", milestones[i].title, milestones[i].id);
+
+    let mut prerequisites: Vec<Vec<usize>> = Vec::with_capacity(milestones.len());
+    for milestone in milestones {
+        let mut deps = Vec::new();
+        for dep in milestone.depends_on.iter().flatten() {
+            let Some(&i) = index.get(dep.as_str()) else {
+                return Err(format!("Milestone '{}' depends on unknown milestone {}", milestone.title, dep));
+            };
+            if !deps.contains(&i) {
+                deps.push(i);
+            }
+        }
+        prerequisites.push(deps);
+    }
+
+    let mut placed = vec![false; milestones.len()];
+    let mut ordered = Vec::with_capacity(milestones.len());
+    while ordered.len() < milestones.len() {
+        let Some(next) = (0..milestones.len())
+            .find(|&i| !placed[i] && prerequisites[i].iter().all(|&dep| placed[dep])) else {
+            // Every unplaced milestone waits on another unplaced one, so walking prerequisites
+            // from any of them must come back around
+            let mut path = vec![(0..milestones.len()).find(|&i| !placed[i]).unwrap_or_default()];
+            loop {
+                let current = path[path.len() - 1];
+                let next = prerequisites[current].iter().copied().find(|&dep| !placed[dep]).unwrap_or(current);
+                if let Some(start) = path.iter().position(|&i| i == next) {
+                    let mut cycle: Vec<String> = path[start..].iter().map(|&i| label(i)).collect();
+                    cycle.push(label(next));
+                    return Err(format!("Milestone dependencies form a cycle (each waits on the next): {}", cycle.join(" -> ")));
+                }
+                path.push(next);
+            }
+        };
+        placed[next] = true;
+        ordered.push(milestones[next].clone());
+    }
+    Ok(ordered)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DuplicateMilestoneId {
+    pub milestone_id: String,
+    pub poam_ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MilestoneRef {
+    #[serde(rename = "poamId")]
+    pub poam_id: i64,
+    #[serde(rename = "milestoneId")]
+    pub milestone_id: String,
+}
+
+/// Status of a POAM after some of its milestones were completed in bulk.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct POAMMilestoneProgress {
+    #[serde(rename = "poamId")]
+    pub poam_id: i64,
+    pub status: String,
+    #[serde(rename = "completedMilestones")]
+    pub completed_milestones: i32,
+    #[serde(rename = "totalMilestones")]
+    pub total_milestones: i32,
+    #[serde(rename = "autoCompleted")]
+    pub auto_completed: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -81,6 +415,21 @@ pub struct NotePOAMAssociation {
     pub poam_id: i64,
 }
 
+/// Notes without a folder are reported under "(unfiled)".
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoteFolderStats {
+    pub folder: String,
+    pub note_count: i32,
+    pub last_note_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoteStats {
+    pub total_notes: i32,
+    pub folders: Vec<NoteFolderStats>,
+    pub tags: Vec<TagCount>,
+}
+
 // STIG Mapping Data Structures for Storage
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct STIGMappingData {
@@ -158,6 +507,11 @@ pub struct STIGVulnerability {
     pub severity_override: Option<String>,
     pub severity_justification: Option<String>,
     pub stig_id: String,
+    // CVE and CWE ids mentioned in the discussion or check text; absent in older saved data
+    #[serde(default)]
+    pub cve_refs: Vec<String>,
+    #[serde(default)]
+    pub cwe_refs: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -203,6 +557,25 @@ pub struct TestCase {
     pub tested_by: Option<String>,
     pub tested_date: Option<String>,
     pub risk_rating: String,
+    /// Original file names of content-addressed evidence, keyed by stored path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence_file_names: Option<std::collections::BTreeMap<String, String>>,
+}
+
+// Reusable test case library entry; system_id of None means the template is global
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TestCaseTemplate {
+    pub id: String,
+    pub name: String,
+    pub nist_control: String,
+    pub cci_ref: String,
+    pub test_description: String,
+    pub test_procedure: String,
+    pub expected_result: String,
+    pub risk_rating: String,
+    pub system_id: Option<String>,
+    pub created_date: String,
+    pub updated_date: String,
 }
 
 // STP Prep List Data Structures
@@ -243,6 +616,51 @@ pub struct ControlPOAMAssociation {
     pub notes: Option<String>,
 }
 
+// External link (ticket, advisory, vendor page) attached to a POAM
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct POAMReference {
+    pub id: String,
+    pub poam_id: i64,
+    pub label: String,
+    pub url: String,
+    pub kind: Option<String>,
+    pub created_date: String,
+}
+
+// One weighted factor of a posture score; `deduction` is what it took off the 100 start
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostureComponent {
+    pub name: String,
+    pub measure: f64,
+    pub weight: f64,
+    pub deduction: f64,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SystemPosture {
+    pub system_id: String,
+    pub score: f64,
+    pub components: Vec<PostureComponent>,
+    pub computed_at: String,
+}
+
+// Ties a STIG vulnerability to Nessus evidence: it is Open when a finding matches the plugin or CVE
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NessusStigCorrelationRule {
+    pub vuln_num: String,
+    pub plugin_id: Option<i64>,
+    pub cve: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NessusStigCorrelationResult {
+    pub opened: Vec<String>,
+    pub not_a_finding: Vec<String>,
+    // Rule vuln numbers that don't appear in the mapping
+    pub unmatched_rules: Vec<String>,
+}
+
 // Baseline Control Structure
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BaselineControl {
@@ -256,7 +674,160 @@ pub struct BaselineControl {
     pub system_id: String,   // The system this baseline control belongs to
 }
 
+// Per-family rollup of a system's baseline controls and STIG-mapped NIST controls
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ControlFamilySummary {
+    pub family: String,
+    pub baseline_controls: usize,
+    pub implementation_status: std::collections::BTreeMap<String, usize>,
+    pub stig_mapped_controls: usize,
+}
+
+// A system whose baseline includes a given control
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ControlSystemUsage {
+    pub system_id: String,
+    pub system_name: String,
+    pub control_id: String,
+    pub implementation_status: String,
+    pub poam_count: usize,
+}
+
+// A STIG vulnerability found by search, with every control it maps to within its mapping
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StigVulnerabilityMatch {
+    pub mapping_id: String,
+    pub mapping_name: String,
+    pub nist_controls: Vec<String>,
+    pub vulnerability: STIGVulnerability,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ControlStigEntry {
+    pub mapping_id: String,
+    pub mapping_name: String,
+    pub mapped_control: MappedControl,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ControlTestCase {
+    pub plan_id: String,
+    pub plan_name: String,
+    pub test_case: TestCase,
+}
+
+// Everything the control detail view shows for one NIST control
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ControlDetail {
+    pub control_id: String,
+    pub baseline_control: Option<BaselineControl>,
+    pub associations: Vec<ControlPOAMAssociation>,
+    pub poams: Vec<POAM>,
+    pub stig_entries: Vec<ControlStigEntry>,
+    pub test_cases: Vec<ControlTestCase>,
+}
+
+// Timeline rows for a Gantt view; all dates are YYYY-MM-DD
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GanttMilestone {
+    pub title: String,
+    pub due: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GanttPOAM {
+    pub poam_id: i64,
+    pub title: String,
+    pub start: String,
+    pub end: String,
+    pub milestones: Vec<GanttMilestone>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GanttSkippedPOAM {
+    pub poam_id: i64,
+    pub title: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GanttData {
+    pub poams: Vec<GanttPOAM>,
+    pub skipped: Vec<GanttSkippedPOAM>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct POAMReminder {
+    pub poam_id: i64,
+    pub title: String,
+    pub status: String,
+    pub end_date: String,
+    /// Negative once the end date has passed
+    pub days_remaining: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct POAMReminders {
+    pub reminders: Vec<POAMReminder>,
+    pub invalid_dates: usize,
+}
+
 // System Package Data Structures
+/// Security classification of a system, as shown on export banners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Classification {
+    Unclassified,
+    Cui,
+    Confidential,
+    Secret,
+    TopSecret,
+}
+
+impl std::str::FromStr for Classification {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized = value.trim().to_uppercase().replace(['_', '-'], " ");
+        match normalized.as_str() {
+            "UNCLASSIFIED" | "U" => Ok(Classification::Unclassified),
+            "CUI" | "CONTROLLED UNCLASSIFIED INFORMATION" => Ok(Classification::Cui),
+            "CONFIDENTIAL" | "C" => Ok(Classification::Confidential),
+            "SECRET" | "S" => Ok(Classification::Secret),
+            "TOP SECRET" | "TOPSECRET" | "TS" => Ok(Classification::TopSecret),
+            _ => Err(format!(
+                "Invalid classification '{}'. Expected one of: UNCLASSIFIED, CUI, CONFIDENTIAL, SECRET, TOP SECRET",
+                value.trim()
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Classification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Classification::Unclassified => "UNCLASSIFIED",
+            Classification::Cui => "CUI",
+            Classification::Confidential => "CONFIDENTIAL",
+            Classification::Secret => "SECRET",
+            Classification::TopSecret => "TOP SECRET",
+        })
+    }
+}
+
+/// Banner text for a stored classification. Unset values default to UNCLASSIFIED; values
+/// saved before validation existed are shown as written so a banner never understates them.
+pub fn classification_banner(classification: Option<&str>) -> String {
+    match classification.map(str::trim).filter(|c| !c.is_empty()) {
+        None => Classification::Unclassified.to_string(),
+        Some(value) => value
+            .parse::<Classification>()
+            .map(|c| c.to_string())
+            .unwrap_or_else(|_| value.to_uppercase()),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct System {
     pub id: String,
@@ -290,6 +861,24 @@ pub struct SystemSummary {
     pub group_id: Option<String>, // Reference to system group
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i32,
+}
+
+// Data categories cleared by a system reset; anything left false is kept
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SystemDataResetOptions {
+    pub poams: bool,
+    pub notes: bool,
+    pub stig_mappings: bool,
+    pub test_plans: bool,
+    pub nessus: bool,
+    pub baseline_controls: bool,
+}
+
 // Update POAMData to include system information
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SystemExportData {
@@ -304,6 +893,9 @@ pub struct SystemExportData {
     pub nessus_scans: Option<Vec<crate::database::nessus::NessusScanMeta>>,
     pub nessus_findings: Option<Vec<crate::database::nessus::NessusFinding>>,
     pub nessus_prep_lists: Option<Vec<crate::database::nessus::NessusPrepList>>,
+    pub poam_references: Option<Vec<POAMReference>>,
+    // Set on metadata-only backups, whose test cases still reference evidence that isn't in the archive
+    pub evidence_omitted: Option<bool>,
     pub export_date: Option<String>,
     pub export_version: Option<String>,
 }
@@ -357,6 +949,53 @@ pub struct GroupExportData {
     pub export_version: Option<String>,
 }
 
+// Top-level index of a full application backup; each system's data lives in its own folder
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FullApplicationBackup {
+    pub systems: Vec<FullBackupSystemEntry>,
+    pub groups: Vec<FullBackupGroup>,
+    pub export_date: Option<String>,
+    pub export_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FullBackupSystemEntry {
+    pub folder: String, // Archive folder holding system_backup.json and evidence/
+    pub system_id: String,
+    pub name: String,
+    pub poam_count: usize,
+    pub evidence_file_count: usize,
+}
+
+// Group-level data that is not part of any single system's backup
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FullBackupGroup {
+    pub group: SystemGroup,
+    pub system_ids: Vec<String>,
+    pub baseline_controls: Vec<crate::database::GroupBaselineControl>,
+    pub poams: Vec<GroupPOAM>,
+    pub control_poam_associations: Vec<crate::database::GroupControlPOAMAssociation>,
+    pub poam_links: Vec<GroupPOAMLink>,
+    #[serde(default)] // Absent from backups written before CCI mappings were included
+    pub cci_mappings: Vec<GroupCCIMapping>,
+}
+
+// One row of a group's uploaded CCI list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GroupCCIMapping {
+    pub cci_id: String,
+    pub nist_control: String,
+    pub definition: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GroupPOAMLink {
+    pub group_poam_id: i64,
+    pub system_id: String,
+    pub poam_id: i64,
+}
+
 // Group-level POAM structure for cross-system POAMs
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GroupPOAM {
@@ -384,6 +1023,33 @@ pub struct GroupPOAM {
     pub devices_affected: Option<String>,
 }
 
+// Link between a group POAM and a member system's POAM
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SystemPOAMRef {
+    pub system_id: String,
+    pub poam_id: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinkedSystemPOAM {
+    pub system_id: String,
+    pub system_name: String,
+    pub poam_id: i64,
+    pub title: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GroupPOAMRollup {
+    pub group_poam_id: i64,
+    pub total_linked: usize,
+    pub completed: usize,
+    pub completion_percentage: f64,
+    pub status_counts: std::collections::HashMap<String, usize>,
+    pub summary: String,
+    pub linked_poams: Vec<LinkedSystemPOAM>,
+}
+
 // Group-level Security Test Plan
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GroupSecurityTestPlan {
@@ -414,4 +1080,99 @@ pub struct STIGFileRecord {
     pub tags: Vec<String>,
     pub version: String,
     pub created_by: String,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_entered_ratings, classification_banner, derive_priority, Classification, Priority, RiskLevel};
+
+    #[test]
+    fn priority_parses_case_insensitively_and_defaults_legacy_values_to_low() {
+        assert_eq!("HIGH".parse::<Priority>(), Ok(Priority::High));
+        assert_eq!(" medium ".parse::<Priority>(), Ok(Priority::Medium));
+        assert_eq!("critical".parse::<Priority>().map(|p| p.to_string()), Ok("Critical".to_string()));
+        assert!("urgent".parse::<Priority>().is_err());
+        for legacy in ["", "  ", "Moderate", "urgent"] {
+            assert_eq!(Priority::from_legacy(legacy), Priority::Low, "{:?}", legacy);
+        }
+    }
+
+    #[test]
+    fn risk_level_keeps_the_moderate_vocabulary() {
+        assert_eq!("moderate".parse::<RiskLevel>().map(|r| r.to_string()), Ok("Moderate".to_string()));
+        // Legacy "Medium" values are read as Moderate rather than rejected
+        assert_eq!("Medium".parse::<RiskLevel>(), Ok(RiskLevel::Moderate));
+        assert_eq!("very-high".parse::<RiskLevel>(), Ok(RiskLevel::VeryHigh));
+        assert_eq!("Very Low".parse::<RiskLevel>().map(|r| r.to_string()), Ok("Very Low".to_string()));
+        assert!("severe".parse::<RiskLevel>().is_err());
+        for legacy in ["", "Unknown", "severe"] {
+            assert_eq!(RiskLevel::from_legacy(legacy), RiskLevel::Low, "{:?}", legacy);
+        }
+    }
+
+    #[test]
+    fn ratings_order_from_lowest_to_highest() {
+        let mut priorities: Vec<Priority> = ["High", "Low", "Critical", "Medium"].iter().map(|p| p.parse().unwrap()).collect();
+        priorities.sort();
+        assert_eq!(priorities, [Priority::Low, Priority::Medium, Priority::High, Priority::Critical]);
+
+        let mut levels: Vec<RiskLevel> = ["High", "Very Low", "Critical", "Moderate", "Very High", "Low"]
+            .iter()
+            .map(|r| r.parse().unwrap())
+            .collect();
+        levels.sort();
+        assert_eq!(levels, [
+            RiskLevel::VeryLow, RiskLevel::Low, RiskLevel::Moderate, RiskLevel::High, RiskLevel::VeryHigh, RiskLevel::Critical,
+        ]);
+    }
+
+    #[test]
+    fn ratings_round_trip_through_serde_and_read_unknown_values_as_low() {
+        assert_eq!(serde_json::to_string(&RiskLevel::Moderate).unwrap(), "\"Moderate\"");
+        assert_eq!(serde_json::from_str::<Priority>("\"high\"").unwrap(), Priority::High);
+        assert_eq!(serde_json::from_str::<RiskLevel>("\"bogus\"").unwrap(), RiskLevel::Low);
+        assert_eq!(serde_json::from_str::<Priority>("\"\"").unwrap(), Priority::Low);
+    }
+
+    #[test]
+    fn entered_ratings_are_checked_unless_unchanged_from_the_stored_poam() {
+        let mut legacy = crate::database::test_support::poam(1);
+        legacy.priority = String::new();
+        legacy.risk_level = "Unknown".to_string();
+        assert!(check_entered_ratings(&legacy, None).is_err());
+        assert!(check_entered_ratings(&legacy, Some(&legacy)).is_ok());
+
+        let mut typo = legacy.clone();
+        typo.risk_level = "Hgih".to_string();
+        assert!(check_entered_ratings(&typo, Some(&legacy)).is_err());
+
+        let mut derived = legacy.clone();
+        derived.severity = Some("CAT II".to_string());
+        derived.risk_level = "moderate".to_string();
+        assert!(check_entered_ratings(&derived, None).is_ok());
+    }
+
+    #[test]
+    fn derived_priority_takes_the_higher_of_severity_and_residual_risk() {
+        assert_eq!(derive_priority("CAT I", None), "High");
+        assert_eq!(derive_priority("low", Some("Moderate")), "Medium");
+        assert_eq!(derive_priority("high", Some("very high")), "Critical");
+    }
+
+    #[test]
+    fn classification_accepts_common_spellings_and_rejects_others() {
+        assert_eq!("top_secret".parse::<Classification>(), Ok(Classification::TopSecret));
+        assert_eq!("ts".parse::<Classification>(), Ok(Classification::TopSecret));
+        assert_eq!(" cui ".parse::<Classification>().map(|c| c.to_string()), Ok("CUI".to_string()));
+        assert!("Secret".parse::<Classification>().unwrap() > Classification::Cui);
+        assert!("FOUO".parse::<Classification>().is_err());
+    }
+
+    #[test]
+    fn classification_banner_defaults_to_unclassified_and_keeps_legacy_values() {
+        assert_eq!(classification_banner(None), "UNCLASSIFIED");
+        assert_eq!(classification_banner(Some("  ")), "UNCLASSIFIED");
+        assert_eq!(classification_banner(Some("s")), "SECRET");
+        assert_eq!(classification_banner(Some("fouo")), "FOUO");
+    }
+}
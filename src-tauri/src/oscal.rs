@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use serde_json::{json, Value};
+use crate::date_utils;
+use crate::models::{normalize_severity, Milestone, Priority, RiskLevel, System, POAM};
+
+// Writer for NIST OSCAL plan-of-action-and-milestones documents (OSCAL 1.1.2, JSON). Each POAM
+// becomes a poam-item with one observation and one risk; the risk's remediation carries the
+// milestones as tasks. The system is declared once as a "this-system" component in
+// local-definitions and every observation names it as its subject. Values OSCAL has no field
+// for (the tracker's POAM id, priority, risk level and status) go into props under TOOL_NS.
+//
+// The reader goes the other way for documents from any tool: a poam-item is followed through its
+// related risks and observations, and those props are used when present.
+
+pub const OSCAL_VERSION: &str = "1.1.2";
+pub const TOOL_NAME: &str = "Simple POAM Tracker";
+pub const TOOL_NS: &str = "urn:simple-poam-tracker";
+
+fn new_uuid() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn prop(name: &str, value: &str) -> Value {
+    json!({ "name": name, "ns": TOOL_NS, "value": value })
+}
+
+// OSCAL dates are date-times with a timezone; POAM dates are plain days
+fn oscal_datetime(date: &str) -> Option<String> {
+    date_utils::parse_iso_date(date).map(|d| format!("{}T00:00:00Z", d))
+}
+
+// OSCAL requires non-empty markup for descriptions
+fn non_empty(text: &str, fallback: &str) -> String {
+    match text.trim() {
+        "" => fallback.to_string(),
+        text => text.to_string(),
+    }
+}
+
+fn risk_status(poam_status: &str) -> &'static str {
+    match poam_status.trim().to_lowercase().as_str() {
+        "completed" | "closed" => "closed",
+        "risk accepted" => "deviation-approved",
+        "in progress" => "remediating",
+        _ => "open",
+    }
+}
+
+pub fn build_poam_document(system: &System, poams: &[POAM], generated_at: &str) -> Value {
+    let component_uuid = new_uuid();
+    let mut observations = Vec::new();
+    let mut risks = Vec::new();
+    let mut poam_items = Vec::new();
+
+    for poam in poams {
+        let observation_uuid = new_uuid();
+        let risk_uuid = new_uuid();
+        let description = non_empty(&poam.description, &poam.title);
+
+        let mut observation = json!({
+            "uuid": observation_uuid,
+            "title": poam.title,
+            "description": description,
+            "methods": ["EXAMINE"],
+            "subjects": [{ "subject-uuid": component_uuid, "type": "component" }],
+            "collected": oscal_datetime(&poam.start_date).unwrap_or_else(|| generated_at.to_string()),
+        });
+        if let Some(source) = poam.source_identifying_vulnerability.as_deref().filter(|s| !s.trim().is_empty()) {
+            observation["remarks"] = json!(format!("Source: {}", source.trim()));
+        }
+        observations.push(observation);
+
+        let tasks: Vec<Value> = poam.milestones.iter().map(|milestone| {
+            let mut task = json!({
+                "uuid": new_uuid(),
+                "type": "milestone",
+                "title": non_empty(&milestone.title, "Milestone"),
+                "props": [prop("milestone-status", &milestone.status)],
+            });
+            if !milestone.description.trim().is_empty() {
+                task["description"] = json!(milestone.description);
+            }
+            if let Some(due) = oscal_datetime(&milestone.due_date) {
+                task["timing"] = json!({ "on-date": { "date": due } });
+            }
+            task
+        }).collect();
+
+        let mut remediation = json!({
+            "uuid": new_uuid(),
+            "lifecycle": "planned",
+            "title": format!("Remediation for POAM {}", poam.id),
+            "description": non_empty(poam.mitigations.as_deref().unwrap_or_default(), &description),
+        });
+        if !tasks.is_empty() {
+            remediation["tasks"] = json!(tasks);
+        }
+
+        let mut risk = json!({
+            "uuid": risk_uuid,
+            "title": poam.title,
+            "description": description,
+            "statement": non_empty(poam.impact.as_deref().unwrap_or_default(), &format!("Risk level: {}", poam.risk_level)),
+            "props": [prop("risk-level", &poam.risk_level)],
+            "status": risk_status(&poam.status),
+            "related-observations": [{ "observation-uuid": observation_uuid }],
+            "remediations": [remediation],
+        });
+        if let Some(deadline) = oscal_datetime(&poam.end_date) {
+            risk["deadline"] = json!(deadline);
+        }
+        risks.push(risk);
+
+        poam_items.push(json!({
+            "uuid": new_uuid(),
+            "title": poam.title,
+            "description": description,
+            "props": [
+                prop("poam-id", &poam.id.to_string()),
+                prop("status", &poam.status),
+                prop("priority", &poam.priority),
+            ],
+            "related-observations": [{ "observation-uuid": observation_uuid }],
+            "related-risks": [{ "risk-uuid": risk_uuid }],
+        }));
+    }
+
+    let mut document = json!({
+        "uuid": new_uuid(),
+        "metadata": {
+            "title": format!("{} Plan of Action and Milestones", system.name),
+            "last-modified": generated_at,
+            "version": generated_at,
+            "oscal-version": OSCAL_VERSION,
+            "parties": [{ "uuid": new_uuid(), "type": "organization", "name": TOOL_NAME }],
+            "remarks": format!("Generated by {}", TOOL_NAME),
+        },
+        "system-id": { "id": system.id },
+        "local-definitions": {
+            "components": [{
+                "uuid": component_uuid,
+                "type": "this-system",
+                "title": system.name,
+                "description": non_empty(system.description.as_deref().unwrap_or_default(), &system.name),
+                "status": { "state": "operational" },
+            }],
+        },
+        "poam-items": poam_items,
+    });
+    // OSCAL does not allow empty arrays, so these are left out when there are no POAMs
+    if !observations.is_empty() {
+        document["observations"] = json!(observations);
+        document["risks"] = json!(risks);
+    }
+
+    json!({ "plan-of-action-and-milestones": document })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OscalError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid OSCAL document: {0}")]
+    InvalidFormat(String),
+}
+
+/// A poam-item that could not be turned into a POAM, identified by its uuid and title.
+#[derive(Debug, Serialize, Clone)]
+pub struct OscalSkippedItem {
+    pub uuid: String,
+    pub title: String,
+    pub reason: String,
+}
+
+/// POAMs parsed from an OSCAL document, with ids left at 0 for the caller to assign.
+#[derive(Debug)]
+pub struct OscalParseResult {
+    pub poams: Vec<POAM>,
+    pub skipped: Vec<OscalSkippedItem>,
+}
+
+fn text<'v>(value: &'v Value, key: &str) -> Option<&'v str> {
+    value.get(key).and_then(Value::as_str).map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn array<'v>(value: &'v Value, key: &str) -> impl Iterator<Item = &'v Value> {
+    value.get(key).and_then(Value::as_array).into_iter().flatten()
+}
+
+// First prop with the given name, ignoring the namespace so props from other tools count too
+fn prop_value<'v>(value: &'v Value, name: &str) -> Option<&'v str> {
+    array(value, "props").find(|p| text(p, "name") == Some(name)).and_then(|p| text(p, "value"))
+}
+
+// Date part of an OSCAL date-time, validated the same way as every other imported date
+fn oscal_date(value: &str) -> Option<String> {
+    date_utils::parse_iso_date(value.get(..10).unwrap_or(value))
+}
+
+fn poam_status(risk_status: &str) -> &'static str {
+    match risk_status.trim().to_lowercase().as_str() {
+        "closed" => "Completed",
+        "deviation-approved" => "Risk Accepted",
+        "remediating" | "deviation-requested" => "In Progress",
+        _ => "Not Started",
+    }
+}
+
+// Facet values from a risk's characterizations, first occurrence of each name winning
+fn facets(risk: &Value) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    for facet in array(risk, "characterizations").flat_map(|c| array(c, "facets")) {
+        if let (Some(name), Some(value)) = (text(facet, "name"), text(facet, "value")) {
+            values.entry(name.to_lowercase()).or_insert_with(|| value.to_string());
+        }
+    }
+    values
+}
+
+fn task_due_date(task: &Value) -> Option<String> {
+    let timing = task.get("timing")?;
+    timing.pointer("/on-date/date")
+        .or_else(|| timing.pointer("/within-date-range/end"))
+        .and_then(Value::as_str)
+        .and_then(oscal_date)
+}
+
+pub fn parse_poam_document(content: &str) -> Result<OscalParseResult, OscalError> {
+    let root: Value = serde_json::from_str(content)?;
+    let document = root.get("plan-of-action-and-milestones")
+        .ok_or_else(|| OscalError::InvalidFormat("no \"plan-of-action-and-milestones\" object found".to_string()))?;
+    let items = document.get("poam-items").and_then(Value::as_array)
+        .ok_or_else(|| OscalError::InvalidFormat("no \"poam-items\" array found".to_string()))?;
+
+    let by_uuid = |key: &str| -> HashMap<&str, &Value> {
+        array(document, key).filter_map(|v| text(v, "uuid").map(|uuid| (uuid, v))).collect()
+    };
+    let risks = by_uuid("risks");
+    let observations = by_uuid("observations");
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut result = OscalParseResult { poams: Vec::new(), skipped: Vec::new() };
+
+    for item in items {
+        let uuid = text(item, "uuid").unwrap_or_default().to_string();
+        let item_risks: Vec<&Value> = array(item, "related-risks")
+            .filter_map(|r| text(r, "risk-uuid"))
+            .filter_map(|uuid| risks.get(uuid).copied())
+            .collect();
+        let item_observations: Vec<&Value> = array(item, "related-observations")
+            .chain(item_risks.iter().flat_map(|r| array(r, "related-observations")))
+            .filter_map(|o| text(o, "observation-uuid"))
+            .filter_map(|uuid| observations.get(uuid).copied())
+            .collect();
+        let risk = item_risks.first().copied();
+
+        let Some(title) = text(item, "title").or_else(|| risk.and_then(|r| text(r, "title"))) else {
+            result.skipped.push(OscalSkippedItem { uuid, title: String::new(), reason: "poam-item has no title".to_string() });
+            continue;
+        };
+        let description = text(item, "description").or_else(|| risk.and_then(|r| text(r, "description"))).unwrap_or(title);
+
+        let status = prop_value(item, "status").map(str::to_string)
+            .or_else(|| risk.and_then(|r| text(r, "status")).map(|s| poam_status(s).to_string()))
+            .unwrap_or_else(|| "Not Started".to_string());
+
+        let mut milestones = Vec::new();
+        for task in item_risks.iter()
+            .flat_map(|r| array(r, "remediations"))
+            .flat_map(|r| array(r, "tasks"))
+            .filter(|t| text(t, "type") == Some("milestone"))
+        {
+            let default_status = if status == "Completed" { "Completed" } else { "Not Started" };
+            milestones.push((task, task_due_date(task), prop_value(task, "milestone-status").unwrap_or(default_status)));
+        }
+
+        let end_date = risk.and_then(|r| text(r, "deadline")).and_then(oscal_date)
+            .or_else(|| milestones.iter().filter_map(|(_, due, _)| due.clone()).max());
+        let Some(end_date) = end_date else {
+            result.skipped.push(OscalSkippedItem {
+                uuid,
+                title: title.to_string(),
+                reason: "no risk deadline or milestone date to use as the scheduled completion date".to_string(),
+            });
+            continue;
+        };
+        let start_date = item_observations.iter()
+            .filter_map(|o| text(o, "collected").and_then(oscal_date))
+            .min()
+            .unwrap_or_else(|| today.clone());
+
+        let risk_facets = risk.map(facets).unwrap_or_default();
+        // OSCAL levels are lowercase tokens such as "moderate" or "very-high"; without one the
+        // severity facet stands in, and items with neither can't be rated
+        let level = risk.and_then(|r| prop_value(r, "risk-level")).map(str::to_string)
+            .or_else(|| risk_facets.get("risk").or_else(|| risk_facets.get("risk-level")).cloned())
+            .or_else(|| risk_facets.get("severity").and_then(|s| normalize_severity(s)).map(str::to_string))
+            .ok_or_else(|| "no risk level or severity to rate it by".to_string())
+            .and_then(|value| value.parse::<RiskLevel>());
+        let priority = prop_value(item, "priority")
+            .map(|p| p.parse::<Priority>().map(|p| p.to_string()))
+            .transpose();
+        let (level, priority) = match (level, priority) {
+            (Ok(level), Ok(priority)) => (level, priority),
+            (Err(reason), _) | (_, Err(reason)) => {
+                result.skipped.push(OscalSkippedItem { uuid, title: title.to_string(), reason });
+                continue;
+            }
+        };
+        let severity = match level {
+            RiskLevel::Critical => "Critical",
+            RiskLevel::VeryHigh | RiskLevel::High => "High",
+            RiskLevel::Moderate => "Medium",
+            RiskLevel::Low | RiskLevel::VeryLow => "Low",
+        };
+
+        result.poams.push(POAM {
+            id: 0,
+            title: title.to_string(),
+            description: description.to_string(),
+            start_date,
+            end_date: end_date.clone(),
+            status,
+            // Left blank so create_poam derives it from the severity when no priority prop is given
+            priority: priority.unwrap_or_default(),
+            risk_level: level.to_string(),
+            milestones: milestones.into_iter().enumerate().map(|(i, (task, due, status))| Milestone {
+                id: uuid::Uuid::new_v4().to_string(),
+                title: text(task, "title").map(str::to_string).unwrap_or_else(|| format!("Milestone {}", i + 1)),
+                due_date: due.unwrap_or_else(|| end_date.clone()),
+                status: status.to_string(),
+                description: text(task, "description").unwrap_or_default().to_string(),
+                depends_on: None,
+            }).collect(),
+            resources: None,
+            source_identifying_vulnerability: item_observations.iter()
+                .find_map(|o| text(o, "remarks"))
+                .map(|remarks| remarks.strip_prefix("Source: ").unwrap_or(remarks).to_string()),
+            responsible_party: None,
+            raw_severity: None,
+            severity: Some(severity.to_string()),
+            relevance_of_threat: None,
+            likelihood: risk_facets.get("likelihood").cloned(),
+            impact: risk_facets.get("impact").cloned().or_else(|| risk.and_then(|r| text(r, "statement")).map(str::to_string)),
+            residual_risk: None,
+            mitigations: item_risks.iter()
+                .flat_map(|r| array(r, "remediations"))
+                .find_map(|r| text(r, "description"))
+                .filter(|d| *d != description)
+                .map(str::to_string),
+            devices_affected: None,
+            source_stig_mapping_id: None,
+            selected_vulnerabilities: None,
+        });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_poam_document, parse_poam_document, OSCAL_VERSION, TOOL_NAME};
+    use crate::database::test_support::{db_with_systems, milestone, poam, system};
+    use crate::models::{Milestone, POAM};
+    use serde_json::Value;
+
+    #[test]
+    fn exported_document_round_trips_with_required_oscal_keys() {
+        let mut system = system("sys-1");
+        system.description = Some("Payroll enclave".to_string());
+        let first = POAM {
+            status: "In Progress".to_string(),
+            priority: "High".to_string(),
+            risk_level: "High".to_string(),
+            mitigations: Some("Apply vendor patch".to_string()),
+            source_identifying_vulnerability: Some("V-220706".to_string()),
+            milestones: vec![
+                Milestone { status: "Completed".to_string(), ..milestone("m-1") },
+                Milestone { due_date: "2024-05-15".to_string(), ..milestone("m-2") },
+            ],
+            ..poam(7)
+        };
+        let generated_at = "2024-07-01T12:00:00Z";
+
+        let written = serde_json::to_string_pretty(&build_poam_document(&system, &[first, poam(8)], generated_at)).unwrap();
+        let document: Value = serde_json::from_str(&written).unwrap();
+        let plan = &document["plan-of-action-and-milestones"];
+        assert!(plan.is_object());
+        assert_eq!(plan["metadata"]["oscal-version"], OSCAL_VERSION);
+        assert_eq!(plan["metadata"]["last-modified"], generated_at);
+        assert_eq!(plan["metadata"]["parties"][0]["name"], TOOL_NAME);
+        assert_eq!(plan["system-id"]["id"], "sys-1");
+
+        let items = plan["poam-items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["title"], "POAM 7");
+        assert_eq!(items[0]["description"], "Description 7");
+
+        // Every observation names the system component as its subject
+        let component = &plan["local-definitions"]["components"][0];
+        assert_eq!(component["type"], "this-system");
+        let observations = plan["observations"].as_array().unwrap();
+        assert_eq!(observations.len(), 2);
+        assert!(observations.iter().all(|o| o["subjects"][0]["subject-uuid"] == component["uuid"]));
+        assert_eq!(observations[0]["remarks"], "Source: V-220706");
+
+        let risk = &plan["risks"][0];
+        assert_eq!(items[0]["related-risks"][0]["risk-uuid"], risk["uuid"]);
+        assert_eq!(risk["status"], "remediating");
+        assert_eq!(risk["deadline"], "2024-06-30T00:00:00Z");
+        let tasks = risk["remediations"][0]["tasks"].as_array().unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1]["type"], "milestone");
+        assert_eq!(tasks[1]["timing"]["on-date"]["date"], "2024-05-15T00:00:00Z");
+        assert!(plan["risks"][1]["remediations"][0].get("tasks").is_none(), "OSCAL forbids empty arrays");
+
+        // The reader gets the same POAMs back
+        let parsed = parse_poam_document(&written).unwrap();
+        assert!(parsed.skipped.is_empty());
+        let round_tripped = &parsed.poams[0];
+        assert_eq!((round_tripped.title.as_str(), round_tripped.status.as_str()), ("POAM 7", "In Progress"));
+        assert_eq!((round_tripped.start_date.as_str(), round_tripped.end_date.as_str()), ("2024-01-01", "2024-06-30"));
+        assert_eq!(round_tripped.priority, "High");
+        assert_eq!(round_tripped.mitigations.as_deref(), Some("Apply vendor patch"));
+        assert_eq!(round_tripped.source_identifying_vulnerability.as_deref(), Some("V-220706"));
+        let milestones: Vec<(&str, &str)> = round_tripped.milestones.iter().map(|m| (m.due_date.as_str(), m.status.as_str())).collect();
+        assert_eq!(milestones, [("2024-03-01", "Completed"), ("2024-05-15", "Not Started")]);
+    }
+
+    // Trimmed from a third-party assessment tool's export: no tracker props, levels and severity
+    // carried in characterizations, observations reached only through the risks
+    const THIRD_PARTY_POAM: &str = r#"{
+      "plan-of-action-and-milestones": {
+        "uuid": "4b3f0f2e-0000-4000-8000-000000000001",
+        "metadata": { "title": "Scanner POA&M", "last-modified": "2024-06-12T09:00:00Z", "version": "3", "oscal-version": "1.1.2" },
+        "observations": [
+          { "uuid": "obs-1", "description": "OpenSSL 1.1.1 detected", "methods": ["TEST"],
+            "collected": "2024-06-10T14:22:00Z", "remarks": "Nessus plugin 148125" },
+          { "uuid": "obs-2", "description": "Minimum password length is 8", "methods": ["EXAMINE"],
+            "collected": "2024-05-02T08:00:00Z" }
+        ],
+        "risks": [
+          { "uuid": "risk-1", "title": "Outdated OpenSSL", "description": "End-of-life OpenSSL on web tier",
+            "statement": "Known vulnerabilities are exploitable remotely", "status": "open",
+            "deadline": "2024-09-30T00:00:00Z",
+            "characterizations": [{ "origin": { "actors": [] }, "facets": [
+              { "name": "likelihood", "system": "https://fedramp.gov", "value": "high" },
+              { "name": "impact", "system": "https://fedramp.gov", "value": "moderate" },
+              { "name": "severity", "system": "https://fedramp.gov", "value": "high" }
+            ]}],
+            "related-observations": [{ "observation-uuid": "obs-1" }],
+            "remediations": [{ "uuid": "rem-1", "lifecycle": "planned", "title": "Upgrade", "description": "Upgrade to OpenSSL 3.0",
+              "tasks": [
+                { "uuid": "task-1", "type": "milestone", "title": "Stage package", "timing": { "on-date": { "date": "2024-08-01T00:00:00Z" } } },
+                { "uuid": "task-2", "type": "action", "title": "Notify owners" },
+                { "uuid": "task-3", "type": "milestone", "title": "Deploy to production",
+                  "timing": { "within-date-range": { "start": "2024-09-01T00:00:00Z", "end": "2024-09-15T00:00:00Z" } } }
+              ]}] },
+          { "uuid": "risk-2", "title": "Weak password policy", "description": "Password length below policy",
+            "statement": "Brute force is feasible", "status": "deviation-approved",
+            "props": [{ "name": "risk-level", "ns": "https://example.com/ns", "value": "very-high" }],
+            "remediations": [{ "uuid": "rem-2", "lifecycle": "planned", "title": "Policy", "description": "Raise minimum length",
+              "tasks": [{ "uuid": "task-4", "type": "milestone", "title": "Update GPO", "timing": { "on-date": { "date": "2024-10-01T00:00:00Z" } } }] }] },
+          { "uuid": "risk-3", "title": "Unrated", "description": "No rating supplied", "statement": "Unknown", "status": "open",
+            "deadline": "2024-12-31T00:00:00Z" }
+        ],
+        "poam-items": [
+          { "uuid": "item-1", "title": "Outdated OpenSSL", "description": "Upgrade OpenSSL on the web tier",
+            "related-risks": [{ "risk-uuid": "risk-1" }] },
+          { "uuid": "item-2", "title": "Weak password policy", "description": "Enforce 15 character passwords",
+            "related-observations": [{ "observation-uuid": "obs-2" }], "related-risks": [{ "risk-uuid": "risk-2" }] },
+          { "uuid": "item-3", "title": "Unrated finding", "description": "Nothing to rate it by",
+            "related-risks": [{ "risk-uuid": "risk-3" }] },
+          { "uuid": "item-4", "title": "Undated finding", "description": "No deadline anywhere" }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn third_party_document_maps_onto_poams_and_milestones() {
+        let parsed = parse_poam_document(THIRD_PARTY_POAM).unwrap();
+
+        let skipped: Vec<(&str, &str)> = parsed.skipped.iter().map(|s| (s.uuid.as_str(), s.title.as_str())).collect();
+        assert_eq!(skipped, [("item-3", "Unrated finding"), ("item-4", "Undated finding")]);
+        assert!(parsed.skipped[0].reason.contains("risk level"), "{}", parsed.skipped[0].reason);
+        assert!(parsed.skipped[1].reason.contains("completion date"), "{}", parsed.skipped[1].reason);
+        assert_eq!(parsed.poams.len(), 2);
+
+        let openssl = &parsed.poams[0];
+        assert_eq!((openssl.title.as_str(), openssl.description.as_str()), ("Outdated OpenSSL", "Upgrade OpenSSL on the web tier"));
+        assert_eq!((openssl.start_date.as_str(), openssl.end_date.as_str()), ("2024-06-10", "2024-09-30"));
+        assert_eq!(openssl.status, "Not Started");
+        assert_eq!((openssl.risk_level.as_str(), openssl.severity.as_deref()), ("High", Some("High")));
+        assert_eq!(openssl.likelihood.as_deref(), Some("high"));
+        assert_eq!(openssl.impact.as_deref(), Some("moderate"));
+        assert_eq!(openssl.mitigations.as_deref(), Some("Upgrade to OpenSSL 3.0"));
+        assert_eq!(openssl.source_identifying_vulnerability.as_deref(), Some("Nessus plugin 148125"));
+        assert_eq!(openssl.priority, "", "left for create_poam to derive");
+        let milestones: Vec<(&str, &str, &str)> = openssl.milestones.iter()
+            .map(|m| (m.title.as_str(), m.due_date.as_str(), m.status.as_str()))
+            .collect();
+        assert_eq!(milestones, [("Stage package", "2024-08-01", "Not Started"), ("Deploy to production", "2024-09-15", "Not Started")]);
+
+        // No deadline, so the latest milestone date stands in
+        let passwords = &parsed.poams[1];
+        assert_eq!((passwords.start_date.as_str(), passwords.end_date.as_str()), ("2024-05-02", "2024-10-01"));
+        assert_eq!(passwords.status, "Risk Accepted");
+        assert_eq!((passwords.risk_level.as_str(), passwords.severity.as_deref()), ("Very High", Some("High")));
+        assert_eq!(passwords.impact.as_deref(), Some("Brute force is feasible"));
+
+        let mut db = db_with_systems(&["sys-1"]);
+        db.create_poam(&poam(41), "sys-1").unwrap();
+        let mut poams = parsed.poams;
+        db.create_poams(&mut poams, "sys-1").unwrap();
+        assert_eq!(poams.iter().map(|p| p.id).collect::<Vec<_>>(), [42, 43]);
+
+        let stored = db.get_poam_by_id(42, "sys-1").unwrap().unwrap();
+        assert_eq!(stored.title, "Outdated OpenSSL");
+        assert_eq!(stored.priority, "High");
+        assert_eq!(stored.milestones.len(), 2);
+        assert!(stored.milestones.iter().all(|m| !m.id.is_empty()));
+        assert_eq!(db.get_poam_by_id(43, "sys-1").unwrap().unwrap().risk_level, "Very High");
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::sync::LazyLock;
 use quick_xml::Reader;
 use quick_xml::events::Event;
 // use regex::Regex;
@@ -49,6 +50,11 @@ pub struct STIGVulnerability {
     pub severity_override: Option<String>,
     pub severity_justification: Option<String>,
     pub stig_id: String,
+    // CVE and CWE ids mentioned in the discussion or check text; absent in older saved data
+    #[serde(default)]
+    pub cve_refs: Vec<String>,
+    #[serde(default)]
+    pub cwe_refs: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -239,8 +245,85 @@ pub fn parse_cci_list(file_path: String) -> Result<Vec<CCIMapping>, StigError> {
     Ok(cci_mappings)
 }
 
+/// CVE and CWE identifiers found in a vulnerability's free text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct References {
+    pub cve_refs: Vec<String>,
+    pub cwe_refs: Vec<String>,
+}
+
+// Compiled once; extract_references runs for every VULN in a checklist
+static CVE_PATTERN: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(?i)\bCVE-\d{4}-\d+\b").expect("valid CVE pattern"));
+static CWE_PATTERN: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(?i)\bCWE-\d+\b").expect("valid CWE pattern"));
+
+/// Scans the discussion and check text for ids such as "CVE-2021-44228" and "CWE-79".
+/// Ids are uppercased and listed once each in order of first appearance.
+pub fn extract_references(vuln: &STIGVulnerability) -> References {
+    let mut references = References::default();
+    for text in [&vuln.vuln_discuss, &vuln.check_content] {
+        for (pattern, found) in [(&*CVE_PATTERN, &mut references.cve_refs), (&*CWE_PATTERN, &mut references.cwe_refs)] {
+            for m in pattern.find_iter(text) {
+                let id = m.as_str().to_uppercase();
+                if !found.contains(&id) {
+                    found.push(id);
+                }
+            }
+        }
+    }
+    references
+}
+
+// Confirms the DISA .ckl layout (a CHECKLIST root with ASSET and VULN elements) so unrelated
+// XML is rejected instead of parsing into an empty checklist
+fn check_checklist_structure(content: &str, file_path: &str) -> Result<(), StigError> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut root: Option<String> = None;
+    let (mut has_asset, mut has_vuln) = (false, false);
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if root.is_none() {
+                    if name != "CHECKLIST" {
+                        return Err(StigError::InvalidFormat(format!(
+                            "'{}' is not a STIG checklist: the root element is <{}>, expected <CHECKLIST>. Export the checklist from STIG Viewer as a .ckl file.",
+                            file_path, name
+                        )));
+                    }
+                    root = Some(name);
+                } else if name == "ASSET" {
+                    has_asset = true;
+                } else if name == "VULN" {
+                    has_vuln = true;
+                }
+                if has_asset && has_vuln {
+                    return Ok(());
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(StigError::XmlParsing(format!("Error at position {}: {:?}", reader.buffer_position(), e))),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let missing: Vec<&str> = [
+        (root.is_some(), "<CHECKLIST>"),
+        (has_asset, "<ASSET>"),
+        (has_vuln, "<VULN>"),
+    ].iter().filter(|(found, _)| !found).map(|(_, name)| *name).collect();
+    Err(StigError::InvalidFormat(format!(
+        "'{}' is not a complete STIG checklist: missing {}",
+        file_path,
+        missing.join(", ")
+    )))
+}
+
 pub fn parse_stig_checklist(file_path: String) -> Result<STIGChecklist, StigError> {
     let content = fs::read_to_string(&file_path)?;
+    check_checklist_structure(&content, &file_path)?;
     let mut reader = Reader::from_str(&content);
     reader.config_mut().trim_text(true);
     
@@ -318,6 +401,8 @@ pub fn parse_stig_checklist(file_path: String) -> Result<STIGChecklist, StigErro
                             severity_override: None,
                             severity_justification: None,
                             stig_id: String::new(),
+                            cve_refs: Vec::new(),
+                            cwe_refs: Vec::new(),
                         });
                         stig_data_map.clear();
                     }
@@ -441,6 +526,10 @@ pub fn parse_stig_checklist(file_path: String) -> Result<STIGChecklist, StigErro
                                 }
                             }
                             
+                            let references = extract_references(&vuln);
+                            vuln.cve_refs = references.cve_refs;
+                            vuln.cwe_refs = references.cwe_refs;
+                            
                             println!("Final vulnerability: vuln_num='{}', severity='{}', cci_refs={:?}", 
                                    vuln.vuln_num, vuln.severity, vuln.cci_refs);
                             
@@ -505,16 +594,7 @@ pub fn map_stig_to_nist_controls(
                     }
                     
                     // Update compliance status (prioritize worst status)
-                    match vuln.status.as_str() {
-                        "Open" => control.compliance_status = "non-compliant".to_string(),
-                        "NotAFinding" if control.compliance_status != "non-compliant" => {
-                            control.compliance_status = "compliant".to_string();
-                        }
-                        "NotApplicable" if control.compliance_status == "not-reviewed" => {
-                            control.compliance_status = "not-applicable".to_string();
-                        }
-                        _ => {}
-                    }
+                    update_compliance_status(&mut control.compliance_status, &vuln.status);
                     
                     // Update risk level (prioritize highest risk)
                     match vuln.severity.to_lowercase().as_str() {
@@ -573,6 +653,131 @@ pub fn map_stig_to_nist_controls(
     mapped_controls
 }
 
+// Folds one vulnerability status into a control's compliance status, keeping the worst seen
+fn update_compliance_status(compliance_status: &mut String, vuln_status: &str) {
+    match vuln_status {
+        "Open" => *compliance_status = "non-compliant".to_string(),
+        "NotAFinding" if compliance_status != "non-compliant" => {
+            *compliance_status = "compliant".to_string();
+        }
+        "NotApplicable" if compliance_status == "not-reviewed" => {
+            *compliance_status = "not-applicable".to_string();
+        }
+        _ => {}
+    }
+}
+
+/// Rebuilds a checklist from a stored mapping. Stored mappings keep each vulnerability under
+/// every control it maps to, so vulnerabilities are collected once each in first-seen order.
+pub fn checklist_from_mapping(mapping: &crate::models::STIGMappingData) -> STIGChecklist {
+    let mut seen = std::collections::HashSet::new();
+    let mut vulnerabilities = Vec::new();
+    for control in &mapping.mapping_result.mapped_controls {
+        for stig in &control.stigs {
+            if seen.insert(stig.vuln_num.clone()) {
+                vulnerabilities.push(STIGVulnerability {
+                    vuln_num: stig.vuln_num.clone(),
+                    severity: stig.severity.clone(),
+                    group_title: stig.group_title.clone(),
+                    rule_id: stig.rule_id.clone(),
+                    rule_ver: stig.rule_ver.clone(),
+                    rule_title: stig.rule_title.clone(),
+                    vuln_discuss: stig.vuln_discuss.clone(),
+                    check_content: stig.check_content.clone(),
+                    fix_text: stig.fix_text.clone(),
+                    cci_refs: stig.cci_refs.clone(),
+                    status: stig.status.clone(),
+                    finding_details: stig.finding_details.clone(),
+                    comments: stig.comments.clone(),
+                    severity_override: stig.severity_override.clone(),
+                    severity_justification: stig.severity_justification.clone(),
+                    stig_id: stig.stig_id.clone(),
+                    cve_refs: stig.cve_refs.clone(),
+                    cwe_refs: stig.cwe_refs.clone(),
+                });
+            }
+        }
+    }
+
+    let asset = &mapping.asset_info;
+    let info = &mapping.stig_info;
+    STIGChecklist {
+        asset: AssetInfo {
+            role: "None".to_string(),
+            asset_type: asset.asset_type.clone(),
+            marking: info.classification.clone(),
+            host_name: asset.host_name.clone().unwrap_or_default(),
+            host_ip: asset.host_ip.clone().unwrap_or_default(),
+            host_mac: asset.host_mac.clone().unwrap_or_default(),
+            host_fqdn: asset.host_fqdn.clone().unwrap_or_default(),
+            target_comment: asset.target_comment.clone().unwrap_or_default(),
+            tech_area: String::new(),
+            target_key: String::new(),
+            web_or_database: false,
+            web_db_site: String::new(),
+            web_db_instance: String::new(),
+        },
+        stig_info: STIGInfo {
+            version: info.version.clone(),
+            classification: info.classification.clone(),
+            custom_name: String::new(),
+            stig_id: vulnerabilities.first().map(|v| v.stig_id.clone()).unwrap_or_default(),
+            description: info.description.clone().unwrap_or_default(),
+            file_name: String::new(),
+            release_info: info.release_info.clone(),
+            title: info.title.clone(),
+            uuid: String::new(),
+            notice: String::new(),
+            source: String::new(),
+        },
+        vulnerabilities,
+    }
+}
+
+/// Copies statuses, finding details, comments and severity overrides from an edited checklist
+/// onto a stored mapping by Vuln_Num, then recomputes control compliance and the summary.
+/// Returns the number of distinct vulnerabilities updated.
+pub fn apply_checklist_statuses(mapping: &mut crate::models::STIGMappingData, checklist: &STIGChecklist) -> usize {
+    let edited: HashMap<&str, &STIGVulnerability> = checklist.vulnerabilities.iter()
+        .map(|v| (v.vuln_num.as_str(), v))
+        .collect();
+    let mut updated = std::collections::HashSet::new();
+
+    for control in &mut mapping.mapping_result.mapped_controls {
+        control.compliance_status = "not-reviewed".to_string();
+        for stig in &mut control.stigs {
+            if let Some(vuln) = edited.get(stig.vuln_num.as_str()) {
+                stig.status = vuln.status.clone();
+                stig.finding_details = vuln.finding_details.clone();
+                stig.comments = vuln.comments.clone();
+                stig.severity_override = vuln.severity_override.clone();
+                stig.severity_justification = vuln.severity_justification.clone();
+                updated.insert(stig.vuln_num.clone());
+            }
+            update_compliance_status(&mut control.compliance_status, &stig.status);
+        }
+        control.findings_count = control.stigs.iter().filter(|s| s.status == "Open").count() as i32;
+    }
+
+    let vulnerabilities = checklist_from_mapping(mapping).vulnerabilities;
+    let controls = &mapping.mapping_result.mapped_controls;
+    let count_controls = |status: &str| controls.iter().filter(|c| c.compliance_status == status).count() as i32;
+    let count_open = |severity: &str| vulnerabilities.iter()
+        .filter(|v| v.severity.to_lowercase() == severity && v.status == "Open")
+        .count() as i32;
+    let summary = &mut mapping.mapping_result.summary;
+    summary.total_controls = controls.len() as i32;
+    summary.compliant_controls = count_controls("compliant");
+    summary.non_compliant_controls = count_controls("non-compliant");
+    summary.not_applicable_controls = count_controls("not-applicable");
+    summary.not_reviewed_controls = count_controls("not-reviewed");
+    summary.high_risk_findings = count_open("high");
+    summary.medium_risk_findings = count_open("medium");
+    summary.low_risk_findings = count_open("low");
+
+    updated.len()
+}
+
 pub fn parse_and_merge_stig_checklists(file_paths: Vec<String>) -> Result<STIGChecklist, StigError> {
     if file_paths.is_empty() {
         return Err(StigError::InvalidFormat("No checklist files provided.".to_string()));
@@ -593,6 +798,115 @@ pub fn parse_and_merge_stig_checklists(file_paths: Vec<String>) -> Result<STIGCh
     merged_checklist.ok_or_else(|| StigError::InvalidFormat("Could not process any checklist files.".to_string()))
 }
 
+/// CCI references from a checklist that the loaded CCI list doesn't know about.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CCICoverageReport {
+    pub unknown_ccis: Vec<String>,
+    // Vulnerabilities referencing at least one unknown CCI
+    pub affected_vulnerabilities: usize,
+    // Vulnerabilities whose CCIs are all unknown, so they map to no control at all
+    pub unmapped_vulnerabilities: usize,
+}
+
+pub fn validate_checklist_cci_coverage(checklist: &STIGChecklist, cci_mappings: &[CCIMapping]) -> CCICoverageReport {
+    let known: std::collections::HashSet<&str> = cci_mappings.iter().map(|m| m.id.as_str()).collect();
+    let mut unknown_ccis = std::collections::BTreeSet::new();
+    let mut affected_vulnerabilities = 0;
+    let mut unmapped_vulnerabilities = 0;
+
+    for vuln in &checklist.vulnerabilities {
+        let unknown: Vec<&String> = vuln.cci_refs.iter().filter(|cci| !known.contains(cci.as_str())).collect();
+        if unknown.is_empty() {
+            continue;
+        }
+        affected_vulnerabilities += 1;
+        if unknown.len() == vuln.cci_refs.len() {
+            unmapped_vulnerabilities += 1;
+        }
+        unknown_ccis.extend(unknown.into_iter().cloned());
+    }
+
+    CCICoverageReport {
+        unknown_ccis: unknown_ccis.into_iter().collect(),
+        affected_vulnerabilities,
+        unmapped_vulnerabilities,
+    }
+}
+
+/// Outcome of comparing a checklist's asset with the system it is about to be saved to.
+/// `warning` is set only when the host clearly belongs somewhere else.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetMatchReport {
+    pub matches: bool,
+    pub host_identifiers: Vec<String>,
+    pub warning: Option<String>,
+}
+
+// Lowercase alphanumeric runs, so "WEB-01.corp.local" and "web01" both yield comparable tokens
+fn match_tokens(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn compact(text: &str) -> String {
+    text.to_lowercase().chars().filter(|c| c.is_ascii_alphanumeric()).collect()
+}
+
+/// Compares the checklist host name, FQDN and IP with the system's name, description, owner and
+/// tags. Case is ignored and an FQDN also matches by its short host name. A checklist without
+/// any host details is not flagged.
+pub fn check_asset_system_match(checklist: &STIGChecklist, system: &crate::models::System) -> AssetMatchReport {
+    let asset = &checklist.asset;
+    let mut identifiers: Vec<String> = Vec::new();
+    for value in [&asset.host_name, &asset.host_fqdn, &asset.host_ip] {
+        let value = value.trim().to_lowercase();
+        if value.is_empty() {
+            continue;
+        }
+        // The short name of an FQDN; skipped for IP addresses, whose first octet means nothing
+        if value.parse::<std::net::IpAddr>().is_err() {
+            if let Some(short) = value.split('.').next().filter(|s| !s.is_empty()) {
+                if !identifiers.iter().any(|i| i == short) {
+                    identifiers.push(short.to_string());
+                }
+            }
+        }
+        if !identifiers.contains(&value) {
+            identifiers.push(value);
+        }
+    }
+
+    if identifiers.is_empty() {
+        return AssetMatchReport { matches: true, host_identifiers: identifiers, warning: None };
+    }
+
+    let mut system_text = vec![system.name.clone()];
+    system_text.extend(system.description.clone());
+    system_text.extend(system.owner.clone());
+    system_text.extend(system.tags.clone().unwrap_or_default());
+    let haystack = system_text.join(" ").to_lowercase();
+    let haystack_compact = compact(&haystack);
+    let system_tokens: Vec<String> = match_tokens(&system.name);
+
+    let matches = identifiers.iter().any(|id| {
+        let id_compact = compact(id);
+        haystack.contains(id.as_str())
+            || (id_compact.len() >= 3 && haystack_compact.contains(&id_compact))
+            // The system is named after part of the host, e.g. system "WEB01" for "web01-dmz"
+            || system_tokens.iter().any(|t| t.len() >= 3 && match_tokens(id).contains(t))
+    });
+
+    let warning = (!matches).then(|| format!(
+        "Checklist host ({}) does not appear to belong to system '{}'",
+        identifiers.join(", "),
+        system.name
+    ));
+    AssetMatchReport { matches, host_identifiers: identifiers, warning }
+}
+
 pub fn create_mapping_result(
     checklist: STIGChecklist,
     cci_mappings: Vec<CCIMapping>,
@@ -775,4 +1089,172 @@ fn add_stig_data(xml: &mut String, attribute: &str, value: &str) {
     xml.push_str(&format!("\t\t\t\t\t<VULN_ATTRIBUTE>{}</VULN_ATTRIBUTE>\n", escape_xml(attribute)));
     xml.push_str(&format!("\t\t\t\t\t<ATTRIBUTE_DATA>{}</ATTRIBUTE_DATA>\n", escape_xml(value)));
     xml.push_str("\t\t\t\t</STIG_DATA>\n");
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::{apply_checklist_statuses, checklist_from_mapping, generate_ckl_xml, parse_stig_checklist, validate_checklist_cci_coverage, CCIMapping};
+    use crate::database::test_support::{stig_mapping, stig_vuln};
+
+    #[test]
+    fn stored_mapping_round_trips_through_a_ckl_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mapping.ckl");
+
+        // V-1 maps to two controls but appears once in the checklist
+        let mut mapping = stig_mapping("map-1", vec![
+            ("AC-2", vec![stig_vuln("V-1", "Open", &["CCI-000015"]), stig_vuln("V-2", "NotAFinding", &["CCI-000016"])]),
+            ("AU-3", vec![stig_vuln("V-1", "Open", &["CCI-000130"])]),
+        ]);
+        mapping.mapping_result.mapped_controls[0].stigs[0].comments = "Ticket <123> & \"follow-up\"".to_string();
+
+        let checklist = checklist_from_mapping(&mapping);
+        assert_eq!(checklist.vulnerabilities.iter().map(|v| v.vuln_num.as_str()).collect::<Vec<_>>(), ["V-1", "V-2"]);
+        std::fs::write(&path, generate_ckl_xml(&checklist).unwrap()).unwrap();
+
+        let mut parsed = parse_stig_checklist(path.to_string_lossy().to_string()).unwrap();
+        let statuses: Vec<(&str, &str)> = parsed.vulnerabilities.iter().map(|v| (v.vuln_num.as_str(), v.status.as_str())).collect();
+        assert_eq!(statuses, [("V-1", "Open"), ("V-2", "NotAFinding")]);
+        assert_eq!(parsed.vulnerabilities[0].comments, "Ticket <123> & \"follow-up\"");
+
+        // Closing V-1 in the checklist updates every control it maps to
+        parsed.vulnerabilities[0].status = "NotAFinding".to_string();
+        parsed.vulnerabilities[0].finding_details = "Fixed in build 42".to_string();
+        assert_eq!(apply_checklist_statuses(&mut mapping, &parsed), 2);
+
+        let controls = &mapping.mapping_result.mapped_controls;
+        assert!(controls.iter().all(|c| c.compliance_status == "compliant" && c.findings_count == 0));
+        assert_eq!(controls[1].stigs[0].finding_details, "Fixed in build 42");
+        assert_eq!(mapping.mapping_result.summary.compliant_controls, 2);
+        assert_eq!(mapping.mapping_result.summary.medium_risk_findings, 0);
+    }
+
+    #[test]
+    fn checklist_ccis_missing_from_the_cci_list_are_reported() {
+        let cci = |id: &str| CCIMapping {
+            id: id.to_string(),
+            title: String::new(),
+            definition: String::new(),
+            nist_controls: vec!["AC-2".to_string()],
+            cci_type: String::new(),
+            status: String::new(),
+            publish_date: String::new(),
+        };
+        let checklist = checklist_from_mapping(&stig_mapping("map-1", vec![("AC-2", vec![
+            stig_vuln("V-1", "Open", &["CCI-000015"]),
+            stig_vuln("V-2", "Open", &["CCI-000015", "CCI-999999"]),
+            stig_vuln("V-3", "Open", &["CCI-999999", "CCI-888888"]),
+            stig_vuln("V-4", "Open", &[]),
+        ])]));
+
+        let report = validate_checklist_cci_coverage(&checklist, &[cci("CCI-000015")]);
+        assert_eq!(report.unknown_ccis, ["CCI-888888", "CCI-999999"]);
+        assert_eq!((report.affected_vulnerabilities, report.unmapped_vulnerabilities), (2, 1));
+
+        let covered = validate_checklist_cci_coverage(&checklist, &[cci("CCI-000015"), cci("CCI-999999"), cci("CCI-888888")]);
+        assert!(covered.unknown_ccis.is_empty());
+        assert_eq!(covered.affected_vulnerabilities, 0);
+    }
+
+    fn checklist_for_host(host_name: &str, host_fqdn: &str, host_ip: &str) -> super::STIGChecklist {
+        let mut checklist = checklist_from_mapping(&stig_mapping("map", vec![("AC-2", vec![stig_vuln("V-1", "Open", &[])])]));
+        checklist.asset.host_name = host_name.to_string();
+        checklist.asset.host_fqdn = host_fqdn.to_string();
+        checklist.asset.host_ip = host_ip.to_string();
+        checklist
+    }
+
+    fn named_system(name: &str) -> crate::models::System {
+        crate::models::System { name: name.to_string(), ..crate::database::test_support::system("sys") }
+    }
+
+    #[test]
+    fn asset_matching_the_system_by_short_name_is_not_flagged() {
+        let checklist = checklist_for_host("", "WEB01.corp.example.mil", "10.1.2.3");
+        let report = super::check_asset_system_match(&checklist, &named_system("web01 Production"));
+        assert!(report.matches);
+        assert!(report.warning.is_none());
+        assert_eq!(report.host_identifiers, ["web01", "web01.corp.example.mil", "10.1.2.3"]);
+
+        // A system named after part of the host also matches
+        assert!(super::check_asset_system_match(&checklist_for_host("WEB01-DMZ", "", ""), &named_system("WEB01")).matches);
+        // No host details at all is not treated as a mismatch
+        assert!(super::check_asset_system_match(&checklist_for_host("", "", ""), &named_system("Anything")).matches);
+    }
+
+    #[test]
+    fn asset_for_another_host_is_flagged() {
+        let checklist = checklist_for_host("db-07", "db-07.corp.example.mil", "10.9.9.9");
+        let report = super::check_asset_system_match(&checklist, &named_system("Web Portal"));
+        assert!(!report.matches);
+        let warning = report.warning.unwrap();
+        assert!(warning.contains("db-07") && warning.contains("Web Portal"), "{}", warning);
+    }
+
+    #[test]
+    fn references_are_extracted_once_each_in_order() {
+        let mut vuln = checklist_from_mapping(&stig_mapping("map", vec![("SI-10", vec![stig_vuln("V-1", "Open", &[])])])).vulnerabilities.remove(0);
+        assert!(super::extract_references(&vuln).cve_refs.is_empty());
+
+        vuln.vuln_discuss = "Log4Shell (cve-2021-44228) is a CWE-917 issue; see CVE-2021-44228 and CVE-2021-45046.".to_string();
+        vuln.check_content = "Verify input handling per CWE-20 and cwe-917. Ignore CVE-21-1 and XCWE-5.".to_string();
+        let references = super::extract_references(&vuln);
+        assert_eq!(references.cve_refs, ["CVE-2021-44228", "CVE-2021-45046"]);
+        assert_eq!(references.cwe_refs, ["CWE-917", "CWE-20"]);
+    }
+
+    #[test]
+    fn parsed_checklists_carry_references_and_old_json_still_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("refs.ckl");
+        let mut checklist = checklist_from_mapping(&stig_mapping("map", vec![("SI-10", vec![stig_vuln("V-1", "Open", &[])])]));
+        checklist.vulnerabilities[0].vuln_discuss = "Unvalidated input (CWE-79) enables CVE-2023-1234.".to_string();
+        std::fs::write(&path, generate_ckl_xml(&checklist).unwrap()).unwrap();
+
+        let parsed = parse_stig_checklist(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(parsed.vulnerabilities[0].cve_refs, ["CVE-2023-1234"]);
+        assert_eq!(parsed.vulnerabilities[0].cwe_refs, ["CWE-79"]);
+
+        // Mappings saved before the fields existed deserialize with empty lists
+        let mut json = serde_json::to_value(stig_vuln("V-1", "Open", &[])).unwrap();
+        json.as_object_mut().unwrap().retain(|key, _| key != "cve_refs" && key != "cwe_refs");
+        let old: crate::models::STIGVulnerability = serde_json::from_value(json.clone()).unwrap();
+        assert!(old.cve_refs.is_empty() && old.cwe_refs.is_empty());
+        let old: super::STIGVulnerability = serde_json::from_value(json).unwrap();
+        assert!(old.cve_refs.is_empty() && old.cwe_refs.is_empty());
+    }
+
+    #[test]
+    fn generated_checklist_passes_the_structure_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("valid.ckl");
+        let checklist = checklist_from_mapping(&stig_mapping("map", vec![("AC-2", vec![stig_vuln("V-1", "Open", &[])])]));
+        std::fs::write(&path, generate_ckl_xml(&checklist).unwrap()).unwrap();
+
+        let parsed = parse_stig_checklist(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(parsed.vulnerabilities.len(), 1);
+    }
+
+    #[test]
+    fn unrelated_or_incomplete_xml_is_rejected_with_a_clear_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let parse = |name: &str, xml: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, xml).unwrap();
+            parse_stig_checklist(path.to_string_lossy().to_string())
+        };
+
+        match parse("feed.xml", "<?xml version=\"1.0\"?><rss><channel><item>News</item></channel></rss>") {
+            Err(super::StigError::InvalidFormat(message)) => {
+                assert!(message.contains("root element is <rss>") && message.contains(".ckl"), "{}", message);
+            }
+            other => panic!("expected InvalidFormat, got {:?}", other.map(|c| c.vulnerabilities.len())),
+        }
+
+        match parse("empty.ckl", "<CHECKLIST><ASSET><HOST_NAME>web01</HOST_NAME></ASSET><STIGS></STIGS></CHECKLIST>") {
+            Err(super::StigError::InvalidFormat(message)) => {
+                assert!(message.contains("missing <VULN>") && !message.contains("<ASSET>"), "{}", message);
+            }
+            other => panic!("expected InvalidFormat, got {:?}", other.map(|c| c.vulnerabilities.len())),
+        }
+    }
+}
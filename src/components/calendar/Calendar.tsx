@@ -7,6 +7,7 @@ import { Icon } from '../ui/icon';
 import { Button } from '../ui/button';
 import { Milestone } from '../../types/Milestone';
 import { formatDateDisplay } from '../../utils/dateUtils';
+import { getErrorMessage } from '../../lib/utils';
 // Unified styles via global patterns and Tailwind
 
 interface POAM {
@@ -98,7 +99,7 @@ export default function Calendar({ onNavigateToMilestone }: CalendarProps) {
       setMilestones(allMilestones);
     } catch (error) {
       console.error('Calendar: Error loading milestones:', error);
-      showToast('error', `Failed to load milestones: ${error}`);
+      showToast('error', `Failed to load milestones: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -17,7 +17,7 @@ import { Badge } from '../ui/badge'
 import { useToast } from '../../context/ToastContext'
 import { useSystem } from '../../context/SystemContext'
 import { useTabNavigation } from '../../context/TabContext'
-import { formatDate } from '../../lib/utils'
+import { formatDate, getErrorMessage } from '../../lib/utils'
 import { Doughnut } from 'react-chartjs-2'
 import { Chart, registerables } from 'chart.js'
 import { Milestone } from '../../types/Milestone'
@@ -242,7 +242,7 @@ export default function ModernDashboard() {
 
     } catch (error) {
       console.error('Error loading dashboard data:', error)
-      showToast('error', `Failed to load dashboard data: ${error}`)
+      showToast('error', `Failed to load dashboard data: ${getErrorMessage(error)}`)
     } finally {
       setLoading(false)
     }
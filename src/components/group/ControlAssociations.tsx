@@ -18,6 +18,7 @@ import { useToast } from '../../context/ToastContext';
 import { useSystem } from '../../context/SystemContext';
 import { BaselineControl, AssociatedItem } from './types';
 import { useEditPOAM } from '../../App';
+import { getErrorMessage } from '../../lib/utils';
 
 interface ControlAssociationsProps {
   baselineControls: BaselineControl[];
@@ -62,7 +63,7 @@ export default function ControlAssociations({
           await generateAssociations();
         } catch (error) {
           console.error('Error generating associations:', error);
-          addToast(`Failed to generate associations: ${error instanceof Error ? error.message : String(error)}`, 'error');
+          addToast(`Failed to generate associations: ${getErrorMessage(error)}`, 'error');
         } finally {
           setLoading(false);
         }
@@ -225,7 +226,7 @@ export default function ControlAssociations({
       setModalOpen(false);
     } catch (error) {
       console.error('Failed to associate POAM with control:', error);
-      addToast(`Failed to associate POAM with control: ${error instanceof Error ? error.message : String(error)}`, 'error');
+      addToast(`Failed to associate POAM with control: ${getErrorMessage(error)}`, 'error');
     } finally {
       setAssociationLoading(false);
     }
@@ -259,7 +260,7 @@ export default function ControlAssociations({
       addToast(`${itemType} association removed successfully`, 'success');
     } catch (error) {
       console.error('Error removing association:', error);
-      addToast(`Failed to remove association: ${error instanceof Error ? error.message : String(error)}`, 'error');
+      addToast(`Failed to remove association: ${getErrorMessage(error)}`, 'error');
     } finally {
       setAssociationLoading(false);
     }
@@ -2,6 +2,7 @@ import { useEffect, useMemo, useState, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { useToast } from '../../context/ToastContext';
 import { Card, CardContent, CardHeader, CardTitle } from '../ui/card';
+import { getErrorMessage } from '../../lib/utils';
 import { Input } from '../ui/input';
 import { Button } from '../ui/button';
 import { Badge } from '../ui/badge';
@@ -295,7 +296,7 @@ export default function EnhancedGroupOverview({
 
       setStats(results);
     } catch (e) {
-      const msg = `Failed to load overview stats: ${e}`;
+      const msg = `Failed to load overview stats: ${getErrorMessage(e)}`;
       setError(msg);
       showToast('error', msg);
     } finally {
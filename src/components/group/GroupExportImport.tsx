@@ -46,6 +46,7 @@ import {
 } from 'lucide-react';
 import { Card, CardContent, CardHeader, CardTitle } from '../ui/card';
 import { Button } from '../ui/button';
+import { getErrorMessage } from '../../lib/utils';
 
 interface GroupExportImportProps {
   groupId: string;
@@ -205,7 +206,7 @@ export default function GroupExportImport({ groupId, groupName, systems }: Group
 
     } catch (error) {
       console.error('Group export error:', error);
-      showToast('error', `Failed to export group: ${error}`);
+      showToast('error', `Failed to export group: ${getErrorMessage(error)}`);
     } finally {
       setIsExporting(false);
       setExportProgress(null);
@@ -287,7 +288,7 @@ export default function GroupExportImport({ groupId, groupName, systems }: Group
 
     } catch (error) {
       console.error('System import error:', error);
-      showToast('error', `Failed to import system: ${error}`);
+      showToast('error', `Failed to import system: ${getErrorMessage(error)}`);
     } finally {
       setTimeout(() => {
         setIsImporting(false);
@@ -323,7 +324,7 @@ export default function GroupExportImport({ groupId, groupName, systems }: Group
 
     } catch (error) {
       console.error('Group report export error:', error);
-      showToast('error', `Failed to export group report: ${error}`);
+      showToast('error', `Failed to export group report: ${getErrorMessage(error)}`);
     } finally {
       setIsExportingReport(false);
     }
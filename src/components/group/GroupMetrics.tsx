@@ -36,6 +36,7 @@ import PriorityDistribution from '../metrics/PriorityDistribution';
 import STIGComplianceChart from '../metrics/STIGComplianceChart';
 import SecurityTestingChart from '../metrics/SecurityTestingChart';
 import NessusVulnerabilityChart from '../metrics/NessusVulnerabilityChart';
+import { getErrorMessage } from '../../lib/utils';
 
 interface POAM {
   id: number;
@@ -243,8 +244,8 @@ export default function GroupMetrics({ groupId, systems, onSwitchToSystem }: Gro
 
     } catch (error) {
       console.error('Error loading group metrics:', error);
-      setError(String(error));
-      showToast('error', `Failed to load group metrics: ${error}`);
+      setError(getErrorMessage(error));
+      showToast('error', `Failed to load group metrics: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -6,6 +6,7 @@ import { formatDateDisplay } from '../../utils/dateUtils';
 import { Milestone } from '../../types/Milestone';
 import { useNotificationGenerator } from '../../hooks/useNotificationGenerator';
 import { Target } from 'lucide-react';
+import { getErrorMessage } from '../../lib/utils';
 
 interface GroupPOAM {
   id: number;
@@ -236,7 +237,7 @@ export default function GroupMilestones({ groupId }: GroupMilestonesProps) {
       setMilestones(allMilestones);
     } catch (error) {
       console.error('GroupMilestones: Error loading milestones:', error);
-      showToast('error', `Failed to load group milestones: ${error}`);
+      showToast('error', `Failed to load group milestones: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -388,13 +389,13 @@ export default function GroupMilestones({ groupId }: GroupMilestonesProps) {
       }
     } catch (error) {
       console.error('GroupMilestones: Error updating milestone:', error);
-      showToast('error', `Failed to update group milestone: ${error}`);
+      showToast('error', `Failed to update group milestone: ${getErrorMessage(error)}`);
       
       notifySystemEvent({
         type: 'error',
         message: `Failed to update group milestone "${updatedMilestone.title}"`,
         success: false,
-        details: String(error)
+        details: getErrorMessage(error)
       });
     } finally {
       closeMilestoneModal();
@@ -37,6 +37,7 @@ import {
 } from '../ui/tabs';
 import NoteModal from '../notes/NoteModal';
 import { createEmptyNote } from '../notes/noteUtils';
+import { getErrorMessage } from '../../lib/utils';
 
 interface GroupNote {
   id: string;
@@ -181,7 +182,7 @@ export default function GroupNotes({ groupId, systems, onSwitchToSystem }: Group
       
     } catch (error) {
       console.error('Error loading group notes:', error);
-      showToast('error', `Failed to load group notes: ${error}`);
+      showToast('error', `Failed to load group notes: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -364,7 +365,7 @@ export default function GroupNotes({ groupId, systems, onSwitchToSystem }: Group
       setModal(null);
     } catch (error) {
       console.error('Error saving group note:', error);
-      showToast('error', `Failed to save group note: ${error}`);
+      showToast('error', `Failed to save group note: ${getErrorMessage(error)}`);
     }
   };
 
@@ -378,7 +379,7 @@ export default function GroupNotes({ groupId, systems, onSwitchToSystem }: Group
       setModal(null);
     } catch (error) {
       console.error('Error deleting group note:', error);
-      showToast('error', `Failed to delete group note: ${error}`);
+      showToast('error', `Failed to delete group note: ${getErrorMessage(error)}`);
     }
   };
 
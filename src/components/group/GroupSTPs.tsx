@@ -1,4 +1,5 @@
 import { useState, useEffect, useCallback } from 'react';
+import { getErrorMessage } from '../../lib/utils';
 import { invoke } from '@tauri-apps/api/core';
 import { useToast } from '../../context/ToastContext';
 import { 
@@ -150,7 +151,7 @@ export default function GroupSTPs({ systems, onSwitchToSystem }: GroupSTPs) {
       
     } catch (error) {
       console.error('Error loading group STPs:', error);
-      showToast('error', `Failed to load group STPs: ${error}`);
+      showToast('error', `Failed to load group STPs: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
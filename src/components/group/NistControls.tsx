@@ -11,6 +11,7 @@ import BaselineControls from './BaselineControls';
 import ControlAssociations from './ControlAssociations';
 import { NistControl, BaselineControl } from './types';
 import catalogData from './catalog.json';
+import { getErrorMessage } from '../../lib/utils';
 
 export default function NistControls() {
   const [activeTab, setActiveTab] = useState('catalog');
@@ -199,7 +200,7 @@ export default function NistControls() {
       addToast(`Added ${controlId} to your baseline controls.`, 'success');
     } catch (error) {
       console.error('Error adding control to baseline:', error);
-      addToast(`Failed to add ${controlId} to baseline: ${error instanceof Error ? error.message : String(error)}`, 'error');
+      addToast(`Failed to add ${controlId} to baseline: ${getErrorMessage(error)}`, 'error');
     }
   };
 
@@ -221,7 +222,7 @@ export default function NistControls() {
       addToast(`Removed ${controlId} from your baseline controls.`, 'info');
     } catch (error) {
       console.error('Error removing control from baseline:', error);
-      addToast(`Failed to remove ${controlId} from baseline: ${error instanceof Error ? error.message : String(error)}`, 'error');
+      addToast(`Failed to remove ${controlId} from baseline: ${getErrorMessage(error)}`, 'error');
     }
   };
 
@@ -257,7 +258,7 @@ export default function NistControls() {
       addToast(`Updated control ${updatedControl.id}.`, 'success');
     } catch (error) {
       console.error('Error updating baseline control:', error);
-      addToast(`Failed to update ${updatedControl.id}: ${error instanceof Error ? error.message : String(error)}`, 'error');
+      addToast(`Failed to update ${updatedControl.id}: ${getErrorMessage(error)}`, 'error');
     }
   };
 
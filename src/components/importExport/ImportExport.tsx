@@ -7,6 +7,7 @@ import { useSystem } from '../../context/SystemContext';
 import { useNotificationGenerator } from '../../hooks/useNotificationGenerator';
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '../ui/card';
 import { Button } from '../ui/button';
+import { getErrorMessage } from '../../lib/utils';
 
 // Add interfaces for the data types
 interface POAM {
@@ -168,14 +169,14 @@ export default function ImportExport() {
 
     } catch (error) {
       console.error('Import error:', error);
-      showToast('error', `Import failed: ${error}`);
+      showToast('error', `Import failed: ${getErrorMessage(error)}`);
       
       // Notify about import failure
       notifySystemEvent({
         type: 'import',
         message: `Data import failed`,
         success: false,
-        details: String(error)
+        details: getErrorMessage(error)
       });
     } finally {
       setIsImporting(false);
@@ -214,7 +215,7 @@ export default function ImportExport() {
       showToast('success', `POAMs and Notes exported successfully from ${currentSystem.name}`);
     } catch (error) {
       console.error('Export error:', error);
-      showToast('error', `Export failed: ${error}`);
+      showToast('error', `Export failed: ${getErrorMessage(error)}`);
     } finally {
       setIsExporting(false);
     }
@@ -252,7 +253,7 @@ export default function ImportExport() {
       showToast('success', `STIG Mappings exported successfully from ${currentSystem.name}`);
     } catch (error) {
       console.error('Export error:', error);
-      showToast('error', `Export failed: ${error}`);
+      showToast('error', `Export failed: ${getErrorMessage(error)}`);
     } finally {
       setIsExporting(false);
     }
@@ -290,7 +291,7 @@ export default function ImportExport() {
       showToast('success', `Security Test Plans exported successfully from ${currentSystem.name}`);
     } catch (error) {
       console.error('Export error:', error);
-      showToast('error', `Export failed: ${error}`);
+      showToast('error', `Export failed: ${getErrorMessage(error)}`);
     } finally {
       setIsExporting(false);
     }
@@ -328,7 +329,7 @@ export default function ImportExport() {
       showToast('success', `Complete system backup exported successfully from ${currentSystem.name}`);
     } catch (error) {
       console.error('Export error:', error);
-      showToast('error', `Export failed: ${error}`);
+      showToast('error', `Export failed: ${getErrorMessage(error)}`);
     } finally {
       setIsExporting(false);
     }
@@ -398,7 +399,7 @@ export default function ImportExport() {
       
     } catch (error) {
       console.error('Report generation error:', error);
-      showToast('error', `Report generation failed: ${error}`);
+      showToast('error', `Report generation failed: ${getErrorMessage(error)}`);
     } finally {
       setIsGeneratingReport(false);
     }
@@ -1319,7 +1320,7 @@ export default function ImportExport() {
       window.location.reload();
     } catch (error) {
       console.error('Import error:', error);
-      showToast('error', `Import failed: ${error}`);
+      showToast('error', `Import failed: ${getErrorMessage(error)}`);
     } finally {
       setIsImporting(false);
     }
@@ -13,6 +13,7 @@ import NessusVulnerabilityChart from './NessusVulnerabilityChart';
 import { NistAssociationSummary } from '.';
 // Unified styles via global patterns and Tailwind
 import { BarChart3 } from 'lucide-react';
+import { getErrorMessage } from '../../lib/utils';
 
 interface POAM {
   id: number;
@@ -155,7 +156,7 @@ const MetricsDashboard: React.FC = () => {
       setNessusFindings(allFindings || []);
     } catch (err) {
       console.error('MetricsDashboard: Error loading data:', err);
-      const errorMessage = `Failed to load metrics data: ${err}`;
+      const errorMessage = `Failed to load metrics data: ${getErrorMessage(err)}`;
       setError(errorMessage);
       showToast('error', errorMessage);
     } finally {
@@ -7,6 +7,7 @@ import { formatDateDisplay } from '../../utils/dateUtils';
 import { Milestone } from '../../types/Milestone';
 import { useNotificationGenerator } from '../../hooks/useNotificationGenerator';
 import { Target } from 'lucide-react';
+import { getErrorMessage } from '../../lib/utils';
 
 interface POAM {
   id: number;
@@ -242,7 +243,7 @@ export default function MilestoneTracker() {
       setMilestones(allMilestones);
     } catch (error) {
       console.error('MilestoneTracker: Error loading milestones:', error);
-      showToast('error', `Failed to load milestones: ${error}`);
+      showToast('error', `Failed to load milestones: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -398,13 +399,13 @@ export default function MilestoneTracker() {
       }
     } catch (error) {
       console.error('MilestoneTracker: Error updating milestone:', error);
-      showToast('error', `Failed to update milestone: ${error}`);
+      showToast('error', `Failed to update milestone: ${getErrorMessage(error)}`);
       
       notifySystemEvent({
         type: 'error',
         message: `Failed to update milestone "${updatedMilestone.title}"`,
         success: false,
-        details: String(error)
+        details: getErrorMessage(error)
       });
     } finally {
       closeMilestoneModal();
@@ -32,6 +32,7 @@ import { NessusProcessingSteps } from './NessusProcessingSteps';
 import { NessusSummaryCards } from './NessusSummaryCards';
 import { NessusFindingsTable } from './NessusFindingsTable';
 import { NessusPrepDialog } from './NessusPrepDialog';
+import { getErrorMessage } from '../../lib/utils';
 
 export default function NessusCenter() {
   const { currentSystem } = useSystem();
@@ -194,7 +195,7 @@ export default function NessusCenter() {
       }
     } catch (error) {
       console.error('Error processing Nessus file(s):', error);
-      const errorMessage = error instanceof Error ? error.message : 'Unknown error';
+      const errorMessage = getErrorMessage(error);
       
       setProcessingSteps(prev => {
         const currentStep = prev.find(step => step.status === 'processing');
@@ -244,7 +245,7 @@ export default function NessusCenter() {
       addToast(`Loaded scan: ${selectedScan.name}`, 'success');
     } catch (error) {
       console.error('Error loading scan:', error);
-      addToast(`Failed to load scan: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to load scan: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -424,7 +425,7 @@ export default function NessusCenter() {
       addToast(`Nessus prep list "${nessusPrepDialog.name}" saved successfully with ${findingsToPrep.length} findings and ${cveCount} CVE milestones`, 'success');
     } catch (error) {
       console.error('Error saving Nessus prep list:', error);
-      addToast(`Failed to save prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to save prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -5,6 +5,7 @@ import { Badge } from '../ui/badge';
 import { useToast } from '../../context/ToastContext';
 import { useSystem } from '../../context/SystemContext';
 import { getNessusPrepListById, updateNessusPrepList, deleteNessusPrepList } from '../../utils/tauriApi';
+import { getErrorMessage } from '../../lib/utils';
 
 interface NessusPrepList {
   id: string;
@@ -154,7 +155,7 @@ export const NessusPrepListManager: React.FC<NessusPrepListManagerProps> = ({
       addToast(`Prep list "${name}" deleted successfully`, 'success');
     } catch (error) {
       console.error('Error deleting prep list:', error);
-      addToast(`Failed to delete prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to delete prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -172,7 +173,7 @@ export const NessusPrepListManager: React.FC<NessusPrepListManagerProps> = ({
       addToast('Prep list updated successfully', 'success');
     } catch (error) {
       console.error('Error updating prep list:', error);
-      addToast(`Failed to update prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to update prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -205,7 +206,7 @@ export const NessusPrepListManager: React.FC<NessusPrepListManagerProps> = ({
       }
     } catch (error) {
       console.error('Error exporting prep list:', error);
-      addToast(`Failed to export prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to export prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -29,6 +29,7 @@ import { Card, CardContent, CardHeader, CardTitle } from '../ui/card';
 import { Button } from '../ui/button';
 import NoteModal from './NoteModal';
 import { createEmptyNote, sortNotes, filterNotes } from './noteUtils';
+import { getErrorMessage } from '../../lib/utils';
 
 type ViewMode = 'grid' | 'list';
 
@@ -173,7 +174,7 @@ const Notes: React.FC = () => {
       
     } catch (error) {
       console.error('Error loading notes:', error);
-      showToast('error', `Failed to load notes: ${error}`);
+      showToast('error', `Failed to load notes: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -256,7 +257,7 @@ const Notes: React.FC = () => {
       }
     } catch (error) {
       console.error('Error deleting note:', error);
-      showToast('error', `Failed to delete note: ${error}`);
+      showToast('error', `Failed to delete note: ${getErrorMessage(error)}`);
     }
   };
 
@@ -280,7 +281,7 @@ const Notes: React.FC = () => {
       closeModal();
     } catch (error) {
       console.error('Error saving note:', error);
-      showToast('error', `Failed to save note: ${error}`);
+      showToast('error', `Failed to save note: ${getErrorMessage(error)}`);
     }
   };
 
@@ -5,6 +5,7 @@ import { useSystem } from '../../context/SystemContext';
 import { useNotificationGenerator } from '../../hooks/useNotificationGenerator';
 import { SimpleDateInput } from '../common';
 import { Button } from '../ui/button';
+import { getErrorMessage } from '../../lib/utils';
 
 interface POAM {
   id: number;
@@ -420,14 +421,14 @@ export default function CreatePOAM() {
       
     } catch (error) {
       console.error('Error creating POAM:', error);
-      showToast('error', `Failed to create POAM: ${error}`);
+      showToast('error', `Failed to create POAM: ${getErrorMessage(error)}`);
       
       // Notify about creation failure
       notifySystemEvent({
         type: 'error',
         message: `Failed to create POAM "${title}"`,
         success: false,
-        details: String(error)
+        details: getErrorMessage(error)
       });
     } finally {
       setIsSubmitting(false);
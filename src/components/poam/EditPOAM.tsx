@@ -10,6 +10,7 @@ import TabNavigation from '../tabNavigation/TabNavigation';
 import catalogData from '../nistControls/catalog.json';
 import { X } from 'lucide-react';
 import { useTabNavigation } from '../../context/TabContext';
+import { getErrorMessage } from '../../lib/utils';
 
 interface Milestone {
   id: string;
@@ -155,7 +156,7 @@ export default function EditPOAM({ poamId, onSave }: EditPOAMProps) {
       }
     } catch (error) {
       console.error('Error loading POAM:', error);
-      showToast('error', `Failed to load POAM: ${error}`);
+      showToast('error', `Failed to load POAM: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -293,14 +294,14 @@ export default function EditPOAM({ poamId, onSave }: EditPOAMProps) {
       if (onSave) onSave();
     } catch (error) {
       console.error('Error saving POAM:', error);
-      showToast('error', `Failed to save POAM: ${error}`);
+      showToast('error', `Failed to save POAM: ${getErrorMessage(error)}`);
       
       // Notify about update failure
       notifySystemEvent({
         type: 'error',
         message: `Failed to update POAM "${editedPOAM.title}"`,
         success: false,
-        details: String(error)
+        details: getErrorMessage(error)
       });
     }
   };
@@ -581,7 +582,7 @@ export default function EditPOAM({ poamId, onSave }: EditPOAMProps) {
       
     } catch (error) {
       console.error('Error deleting POAM:', error);
-      showToast('error', `Failed to delete POAM: ${error}`);
+      showToast('error', `Failed to delete POAM: ${getErrorMessage(error)}`);
     } finally {
       setIsDeleting(false);
       setShowDeleteConfirmation(false);
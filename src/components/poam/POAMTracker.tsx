@@ -4,6 +4,7 @@ import { useToast } from '../../context/ToastContext';
 import { useSystem } from '../../context/SystemContext';
 import { formatDateDisplay } from '../../utils/dateUtils';
 import { useEditPOAM } from '../../App';
+import { getErrorMessage } from '../../lib/utils';
 
 interface POAM {
   id: number;
@@ -113,7 +114,7 @@ export default function POAMTracker() {
       setPOAMs(data || []);
     } catch (error) {
       console.error('POAMTracker: Error loading POAMs:', error);
-      showToast('error', `Failed to load POAMs: ${error}`);
+      showToast('error', `Failed to load POAMs: ${getErrorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -28,6 +28,7 @@ import { useSystem } from '../../context/SystemContext';
 import { useTabNavigation } from '../../context/TabContext';
 import TestPlanList from './TestPlanList';
 import TestCaseModal from './TestCaseModal';
+import { getErrorMessage } from '../../lib/utils';
 // Unified styles via global patterns and Tailwind
 
 interface TestCase {
@@ -637,7 +638,7 @@ Note: Detailed finding data not available for individual CVE test case generatio
     } catch (error) {
       console.error('Evidence package import error:', error);
       console.error('Error details:', JSON.stringify(error, null, 2));
-      addToast(`Evidence package import failed: ${error}`, 'error');
+      addToast(`Evidence package import failed: ${getErrorMessage(error)}`, 'error');
     } finally {
       setIsImporting(false);
     }
@@ -669,7 +670,7 @@ Note: Detailed finding data not available for individual CVE test case generatio
       addToast(`Evidence package exported successfully for "${plan.name}"`, 'success');
     } catch (error) {
       console.error('Evidence export error:', error);
-      addToast(`Evidence export failed: ${error}`, 'error');
+      addToast(`Evidence export failed: ${getErrorMessage(error)}`, 'error');
     } finally {
       setIsExporting(false);
     }
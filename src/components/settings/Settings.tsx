@@ -28,6 +28,7 @@ import { Button } from '../ui/button';
 import TabNavigation from '../tabNavigation/TabNavigation';
 import { clearNessusData, clearStigData } from '../../utils/tauriApi';
 import { useAppLock } from '../../context/AppLockContext';
+import { getErrorMessage } from '../../lib/utils';
 
 interface SettingsState {
   notificationsEnabled: boolean;
@@ -244,7 +245,7 @@ export default function Settings() {
       addToLog(`Database result: ${result}`);
       return true;
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       addToLog(`Error deleting database file: ${errorMessage}`);
       console.error('[Database] Delete error:', error);
       return false;
@@ -263,7 +264,7 @@ export default function Settings() {
       addToLog(msg);
       showToast('success', 'Nessus scan data cleared');
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : String(err);
+      const errorMessage = getErrorMessage(err);
       addToLog(`Failed to clear scans: ${errorMessage}`);
       showToast('error', 'Failed to clear scan data');
     } finally {
@@ -283,7 +284,7 @@ export default function Settings() {
       addToLog(msg);
       showToast('success', 'STIG mapping data cleared');
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : String(err);
+      const errorMessage = getErrorMessage(err);
       addToLog(`Failed to clear STIG data: ${errorMessage}`);
       showToast('error', 'Failed to clear STIG data');
     } finally {
@@ -339,7 +340,7 @@ export default function Settings() {
       }
       
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : 'Unknown error';
+      const errorMessage = getErrorMessage(error);
       console.error('[DataClear] Error clearing data:', error);
       addToLog(`Fatal error during data clearing: ${errorMessage}`);
       showToast('error', `Failed to clear data: ${errorMessage}`);
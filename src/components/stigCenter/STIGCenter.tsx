@@ -10,6 +10,7 @@ import { useToast } from '../../context/ToastContext';
 import { useSystem } from '../../context/SystemContext';
 import STIGPrepListManager from './STIGPrepListManager';
 import STIGFileManager from './STIGFileManager';
+import { getErrorMessage } from '../../lib/utils';
 
 interface FileUploadState {
   checklistFilePath: string | null;
@@ -303,7 +304,7 @@ export default function STIGCenter() {
       }
     } catch (error) {
       console.error('Error processing checklist file(s):', error);
-      const errorMessage = error instanceof Error ? error.message : 'Unknown error';
+      const errorMessage = getErrorMessage(error);
       
       // Update current processing step to error
       setProcessingSteps(prev => {
@@ -506,7 +507,7 @@ export default function STIGCenter() {
       addToast(`STP prep list "${stpPrepDialog.name}" saved successfully with ${vulnsToPrep.length} vulnerabilities`, 'success');
     } catch (error) {
       console.error('Error saving STP prep list:', error);
-      addToast(`Failed to save STP prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to save STP prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -537,7 +538,7 @@ export default function STIGCenter() {
 
     } catch (error) {
       console.error('Error exporting checklist:', error);
-      addToast(`Failed to export checklist: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to export checklist: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -7,6 +7,7 @@ import { Button } from '../ui/button';
 import { getAllStpPrepLists, getStpPrepListById, deleteStpPrepList, updateStpPrepList } from '../../utils/tauriApi';
 import { useToast } from '../../context/ToastContext';
 import { useSystem } from '../../context/SystemContext';
+import { getErrorMessage } from '../../lib/utils';
 
 interface StpPrepList {
   id: string;
@@ -122,7 +123,7 @@ export default function STIGPrepListManager() {
       addToast(`Prep list "${name}" deleted successfully`, 'success');
     } catch (error) {
       console.error('Error deleting prep list:', error);
-      addToast(`Failed to delete prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to delete prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -140,7 +141,7 @@ export default function STIGPrepListManager() {
       addToast('Prep list updated successfully', 'success');
     } catch (error) {
       console.error('Error updating prep list:', error);
-      addToast(`Failed to update prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to update prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
@@ -173,7 +174,7 @@ export default function STIGPrepListManager() {
       }
     } catch (error) {
       console.error('Error exporting prep list:', error);
-      addToast(`Failed to export prep list: ${error instanceof Error ? error.message : 'Unknown error'}`, 'error');
+      addToast(`Failed to export prep list: ${getErrorMessage(error)}`, 'error');
     } finally {
       setLoading(false);
     }
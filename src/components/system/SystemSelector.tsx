@@ -31,6 +31,7 @@ import CreateGroupModal from './CreateGroupModal';
 import EditGroupModal from './EditGroupModal';
 import { DropdownMenu, DropdownMenuContent, DropdownMenuItem, DropdownMenuTrigger, DropdownMenuSeparator } from '../ui/dropdown-menu';
 import * as api from '../../utils/tauriApi';
+import { getErrorMessage } from '../../lib/utils';
 
 interface SystemSelectorProps {
   onSystemSelected: () => void;
@@ -205,7 +206,7 @@ export default function SystemSelector({ onSystemSelected, onGroupSelected }: Sy
       console.error('Failed to update system:', error);
       
       // Provide more specific error messages
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       if (errorMessage.includes('UNIQUE constraint failed: systems.name')) {
         showToast('error', 'A system with this name already exists. Please choose a different name.');
       } else {
@@ -297,7 +298,7 @@ export default function SystemSelector({ onSystemSelected, onGroupSelected }: Sy
       
     } catch (error) {
       console.error('System import error:', error);
-      showToast('error', `System import failed: ${error}`);
+      showToast('error', `System import failed: ${getErrorMessage(error)}`);
     } finally {
       // Clean up loading state after successful import or error
       setTimeout(async () => {
@@ -349,7 +350,7 @@ export default function SystemSelector({ onSystemSelected, onGroupSelected }: Sy
       
     } catch (error) {
       console.error('Group import error:', error);
-      showToast('error', `Group import failed: ${error}`);
+      showToast('error', `Group import failed: ${getErrorMessage(error)}`);
     } finally {
       // Clean up loading state after successful import or error
       setTimeout(() => {
@@ -128,4 +128,17 @@ export function compareNistControlIdStrings(a: string, b: string): number {
 
   // Fallback to lexicographic if everything else equal
   return a.localeCompare(b)
-}
\ No newline at end of file
+}
+
+// Tauri commands reject with a { code, message } object; anything else is shown as-is
+export type CommandError = { code: string; message: string }
+
+export function isCommandError(error: unknown): error is CommandError {
+  return typeof error === 'object' && error !== null && 'code' in error && 'message' in error
+}
+
+export function getErrorMessage(error: unknown): string {
+  if (isCommandError(error)) return error.message
+  if (error instanceof Error) return error.message
+  return String(error)
+}